@@ -145,7 +145,6 @@ pub fn smooth_damp_angle(current: f64, target: f64, velocity: &mut f64, smooth_t
 // ============================================================================
 
 /// Sine wave pulse (0 to 1 range) based on time
-#[allow(dead_code)]
 pub fn pulse_sine(time: f64, frequency: f64) -> f64 {
     ((time * frequency * 2.0 * PI).sin() + 1.0) / 2.0
 }
@@ -2,12 +2,31 @@
 
 #![allow(dead_code)]
 
-use crate::animation::{ease_breath, smooth_damp};
+use crate::animation::{ease_breath, lerp, pulse_sine, smooth_damp};
+use crate::audio::{AudioConfig, AudioStatus};
+use crate::clock::{Clock, SystemClock};
+use crate::history;
+use crate::keys::Action;
+use crate::i18n::Lang;
 use crate::particles::ParticleSystem;
-use crate::techniques::{all_techniques, Phase, PhaseName, Technique};
-use crate::theme::{blend_phase_colors, default_theme, PhaseColors};
+use crate::prefs::Prefs;
+use crate::rng::{time_seed, Rng};
+use crate::techniques::{
+    all_categories, get_technique, get_techniques_by_category, Category, Difficulty, Phase,
+    PhaseName, Technique,
+};
+use crate::theme::{
+    blend_phase_colors, default_theme, night_shift, no_color, with_opacity, PhaseColors, Theme,
+};
 use crate::ui::celebration::CelebrationAnimation;
+use chrono::{Local, NaiveDate};
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Color;
 use ratatui::widgets::ListState;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 /// Phase transition duration in seconds
@@ -16,18 +35,95 @@ const PHASE_TRANSITION_DURATION: f64 = 0.3;
 /// Smooth damp time for transitions
 const TRANSITION_SMOOTH_TIME: f64 = 0.15;
 
+/// Smooth damp time for the circle easing in from its resting size when a
+/// session starts (mostly there after ~0.5s)
+const START_RAMP_SMOOTH_TIME: f64 = 0.2;
+
+/// Circle size shown before the first phase takes over
+const START_RAMP_SCALE: f64 = 0.5;
+
+/// How long the cue indicator stays visible after a tone fires (seconds)
+const CUE_FLASH_DURATION: f64 = 0.6;
+
+/// How long manual-mode pace feedback stays on screen (seconds)
+const PACE_FEEDBACK_DURATION: f64 = 1.5;
+
+/// Default manual-mode tolerance: within ±15% of the target counts as on pace
+pub const DEFAULT_PACE_TOLERANCE: f64 = 0.15;
+
+/// Maximum length of a session note, in characters
+pub const MAX_NOTE_LEN: usize = 120;
+
+/// How long the "up next" card shows between interval segments (seconds)
+pub const SEGMENT_TRANSITION_SECS: f64 = 5.0;
+
+/// --warmup: calm cycles to ease into the practice, and recovery cycles to
+/// ease out of it
+const WARMUP: (&str, u32) = ("coherent", 5);
+const COOLDOWN: (&str, u32) = ("recovery", 4);
+
+/// The cycle count set on the ready screen stops at about an hour of
+/// breathing, however long the technique's cycle is
+const MAX_SESSION_SECS: f64 = 60.0 * 60.0;
+
+/// Frame rate range for --fps, and the default
+pub const MIN_FPS: u32 = 5;
+pub const MAX_FPS: u32 = 120;
+pub const DEFAULT_FPS: u32 = 60;
+
+/// Particle cap on a 120x40 terminal; other sizes scale it by area, within
+/// the limits below, so the density stays the same
+const DEFAULT_MAX_PARTICLES: usize = 150;
+const PARTICLE_REFERENCE_AREA: (u16, u16) = (120, 40);
+const MIN_SCALED_PARTICLES: usize = 10;
+const MAX_SCALED_PARTICLES: usize = 400;
+
+/// Battery saver: frame rate and particle cap
+const BATTERY_SAVER_FPS: u32 = 15;
+const BATTERY_SAVER_MAX_PARTICLES: usize = 40;
+
+/// Night mode: overall brightness and particle cap
+pub const NIGHT_BRIGHTNESS: f64 = 0.5;
+const NIGHT_MAX_PARTICLES: usize = 60;
+
+/// How much one +/- press changes the cue volume
+pub const VOLUME_STEP: f32 = 0.05;
+
+/// Pace multiplier range and the change per [ / ] press
+pub const PACE_MIN: f64 = 0.5;
+pub const PACE_MAX: f64 = 2.0;
+pub const PACE_STEP: f64 = 0.1;
+
+/// How long "audio unavailable" shows after trying to turn audio on (seconds)
+const AUDIO_NOTICE_DURATION: f64 = 2.5;
+
+/// Maximum natural-mode variation of a phase duration (±10%)
+pub const NATURAL_JITTER: f64 = 0.1;
+
 /// The main application state
 pub struct App {
     pub techniques: Vec<Technique>,
     pub selected_index: usize,
     pub list_state: ListState,
+    /// Filled in by each render; behind a RefCell because the footer is
+    /// drawn from screens that only borrow the app
+    pub hit_areas: RefCell<HitAreas>,
+    /// Selector filter, matched against technique names and ids
+    pub search_query: String,
+    /// Whether the selector's search box is capturing keystrokes
+    pub searching: bool,
     pub technique: Option<Technique>,
     pub state: AppState,
+    /// Cycles to breathe before completing; 0 means endless
     pub cycles_target: u32,
     pub cycles_completed: u32,
     pub current_phase_index: usize,
+    /// Phase the first cycle starts on (lets users drill a specific phase)
+    pub start_phase_index: usize,
     pub phase_start_time: Instant,
     pub session_start_time: Instant,
+    /// When the Ready screen was entered (drives its idle animation)
+    pub ready_since: Instant,
 
     // Enhanced particle system (replaces old particles Vec)
     pub particle_system: ParticleSystem,
@@ -36,26 +132,238 @@ pub struct App {
     pub phase_transition_progress: f64,
     phase_transition_velocity: f64,
     previous_phase: Option<PhaseName>,
+    /// Blend from the resting circle size into the phase's, 0.0 to 1.0, so
+    /// the circle doesn't pop at the start of a session
+    start_ramp: f64,
+    start_ramp_velocity: f64,
 
     // Celebration animation
     pub celebration: Option<CelebrationAnimation>,
 
     pub show_help: bool,
     pub show_guide: bool,
+    pub show_disclaimer: bool,
+    /// The intro to the controls shown on first run (or with --intro)
+    pub show_intro: bool,
+    /// Language of the interface
+    pub lang: Lang,
+    /// Show the nose/mouth hint next to the phase name
+    pub show_route_hint: bool,
+    /// Skip non-essential motion effects
+    pub reduced_motion: bool,
+    /// How often the session updates and animated screens redraw
+    pub tick_rate: Duration,
+    /// Fewer frames and particles, and slower redraws during holds
+    pub battery_saver: bool,
+    /// Particle cap at the reference terminal size, lowered by night mode
+    /// and battery saver
+    particle_budget: usize,
+    /// Size of the area the particles are drawn in
+    particle_area: (u16, u16),
+    /// --particles: a fixed cap whatever the terminal size
+    pub particle_override: Option<usize>,
+    /// --max-cycles: a fixed cap for the ready screen's cycle count instead
+    /// of the hour-long default
+    pub max_cycles: Option<u32>,
+    /// Draw with plain ASCII for terminals without Unicode or braille
+    pub use_ascii: bool,
+    /// Render into a fixed cols x rows region centered in the terminal
+    pub fixed_size: Option<(u16, u16)>,
+    /// Global brightness multiplier applied to every rendered color
+    pub brightness: f64,
+    /// Night mode: red-shift every rendered color to preserve night vision
+    pub night_mode: bool,
+    /// NO_COLOR: flatten every rendered color to basic grays
+    pub monochrome: bool,
+    /// Active color theme, built once and shared by every render function
+    pub theme: Theme,
     pub audio_enabled: bool,
+    /// Remembered settings, saved to `prefs_path` when they change
+    pub prefs: Prefs,
+    pub prefs_path: Option<PathBuf>,
+    /// Local dates with a session in the history log, for the streak
+    pub practice_days: BTreeSet<NaiveDate>,
+    /// Minutes breathed so far this week, from the history log
+    pub week_minutes: u32,
+    /// History log each completed session is appended to (None = don't log)
+    pub history_path: Option<PathBuf>,
+    /// Cue volume, 0.0 to 1.0
+    pub volume: f32,
+    /// Play a soft continuous tone while breathing
+    pub drone: bool,
+    /// Ring the terminal bell on each phase change, for when there's no audio
+    pub bell: bool,
+    /// Pulse a dot (and tick, with audio on) every second of each phase
+    pub metronome: bool,
+    /// Always use the compact session layout, without the visualizer
+    pub compact: bool,
+    /// The safety note of an advanced technique has been accepted, so the
+    /// session may start
+    pub safety_acknowledged: bool,
+    /// Readiness of the audio output, as last reported by the audio thread
+    pub audio_status: AudioStatus,
+    /// Audio was muted automatically because the device wasn't available
+    audio_auto_muted: bool,
+    /// When audio was last switched on with no device to play it
+    audio_notice_at: Option<Instant>,
+
+    // Exit automatically this many seconds after completing (None = stay)
+    pub auto_quit_after: Option<f64>,
+    /// Give up on a session left paused this long (None = wait forever)
+    pub pause_timeout: Option<Duration>,
+    paused_at: Option<Instant>,
+    /// Natural mode: jitter each phase duration by up to NATURAL_JITTER
+    pub natural: bool,
+    /// Multiplier on every phase duration: above 1.0 is slower, below is faster
+    pub pace: f64,
+    /// Seed for natural-mode jitter and the visuals (--seed)
+    pub seed: u64,
+    /// When the last phase cue fired (or would have, with audio off)
+    pub last_cue_at: Option<Instant>,
+    /// Number of phase transitions so far, for detecting phase changes
+    pub phase_serial: u64,
+    /// Manual mode: phases advance only when the user taps
+    pub manual: bool,
+    /// Fraction of the target duration a tapped phase may be off by
+    pub pace_tolerance: f64,
+    /// Per-phase accuracy recorded in manual mode
+    pub pace_results: Vec<PaceResult>,
+    /// Optional journal note typed on the complete screen
+    pub note: String,
+    /// Whether the note prompt is capturing keystrokes
+    pub editing_note: bool,
+
+    // Interval training: techniques run back to back (empty = single technique)
+    pub segments: Vec<Segment>,
+    pub segment_index: usize,
+    /// How many times the interval list repeats
+    pub rounds: u32,
+    /// Totals for each finished segment, for the summary
+    pub segment_results: Vec<SegmentResult>,
+
+    // Breath holds practiced this session (seconds actually held)
+    pub total_hold_secs: f64,
+    pub longest_hold: Option<(f64, Cow<'static, str>)>,
+    /// Times the session was paused
+    pub pauses: u32,
+    segment_start_time: Instant,
+    transition_started: Option<Instant>,
+    completed_at: Option<Instant>,
+    /// Seconds counted in before breathing starts (0 = start immediately)
+    pub countdown_secs: u32,
+    countdown_started: Instant,
 
     // Pause tracking
     phase_elapsed_at_pause: f64,
     session_elapsed_at_pause: Duration,
+    /// Colour blend progress and velocity when paused, restored on resume
+    transition_at_pause: (f64, f64),
+    /// The first tick after resuming spans the paused frames, so the blends
+    /// skip it rather than jump ahead
+    resumed: bool,
+
+    /// Source of the current time for all session timing
+    clock: Box<dyn Clock>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     Selecting,  // Choosing a technique
     Ready,      // Technique selected, waiting to start
+    Countdown,  // Counting in before the first breath
     Breathing,  // Active session
     Paused,     // Session paused
     Complete,   // Session finished
+    Transition, // Between interval segments, showing what's next
+}
+
+/// One row of the technique selector: a category heading or a technique
+/// (by index into `App::techniques`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorRow {
+    /// Heading over the starred techniques pinned to the top
+    Favorites,
+    Header(Category),
+    Technique(usize),
+}
+
+/// Where clickable things were last drawn, so mouse clicks can find them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HitAreas {
+    /// The technique list; its first visible row is `list_state`'s offset
+    pub list: Rect,
+    /// Footer hints, by the action clicking them performs
+    pub hints: Vec<(Rect, Action)>,
+}
+
+/// Time between frames at `fps` frames a second
+fn fps_interval(fps: u32) -> Duration {
+    Duration::from_secs_f64(1.0 / fps.max(1) as f64)
+}
+
+/// Every technique, grouped by category in menu order
+fn techniques_by_category() -> Vec<Technique> {
+    all_categories().into_iter().flat_map(get_techniques_by_category).cloned().collect()
+}
+
+/// One technique run within an interval session
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub technique: Technique,
+    pub cycles: u32,
+    /// End-of-round retention and recovery: follows the previous segment
+    /// without a rest card and doesn't count toward cycle totals
+    pub retention: bool,
+    /// Warm-up before or cool-down after the practice (--warmup): outside
+    /// the rounds
+    pub warmup: bool,
+}
+
+/// What was actually done in a finished segment
+#[derive(Debug, Clone)]
+pub struct SegmentResult {
+    pub technique_name: Cow<'static, str>,
+    pub cycles: u32,
+    pub breaths: u32,
+    pub duration: Duration,
+}
+
+/// How a manually paced phase compared with its target duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pace {
+    OnTarget,
+    Fast,
+    Slow,
+}
+
+impl Pace {
+    /// Judge a phase from its actual/target duration ratio
+    pub fn judge(ratio: f64, tolerance: f64) -> Self {
+        if ratio < 1.0 - tolerance {
+            Pace::Fast
+        } else if ratio > 1.0 + tolerance {
+            Pace::Slow
+        } else {
+            Pace::OnTarget
+        }
+    }
+
+    pub fn feedback(&self) -> &'static str {
+        match self {
+            Pace::OnTarget => "nice, right on pace",
+            Pace::Fast => "a bit fast",
+            Pace::Slow => "a bit slow",
+        }
+    }
+}
+
+/// Accuracy of a single manually paced phase
+#[derive(Debug, Clone, Copy)]
+pub struct PaceResult {
+    pub phase_index: usize,
+    /// Actual duration divided by the target duration
+    pub ratio: f64,
+    pub pace: Pace,
 }
 
 // Legacy Particle struct kept for compatibility (but we use ParticleSystem now)
@@ -100,65 +408,279 @@ impl Particle {
 impl App {
     /// Create app in interactive mode (technique selector)
     pub fn new_interactive() -> Self {
-        let now = Instant::now();
-        let techniques = all_techniques();
+        let now = SystemClock.now();
+        let techniques = techniques_by_category();
         let mut list_state = ListState::default();
-        list_state.select(Some(0));
+        // Row 0 is the first category's header
+        list_state.select(Some(1));
 
         Self {
             techniques,
             selected_index: 0,
             list_state,
+            hit_areas: RefCell::default(),
+            search_query: String::new(),
+            searching: false,
             technique: None,
             state: AppState::Selecting,
             cycles_target: 4,
             cycles_completed: 0,
             current_phase_index: 0,
+            start_phase_index: 0,
             phase_start_time: now,
             session_start_time: now,
-            particle_system: ParticleSystem::new(150), // 150 max particles (up from 50)
+            ready_since: now,
+            particle_system: ParticleSystem::new(DEFAULT_MAX_PARTICLES),
             phase_transition_progress: 1.0,
             phase_transition_velocity: 0.0,
+            start_ramp: 1.0,
+            start_ramp_velocity: 0.0,
             previous_phase: None,
             celebration: None,
             show_help: false,
             show_guide: false,
+            show_disclaimer: false,
+            show_intro: false,
+            lang: Lang::default(),
+            show_route_hint: true,
+            reduced_motion: false,
+            tick_rate: fps_interval(DEFAULT_FPS),
+            battery_saver: false,
+            particle_budget: DEFAULT_MAX_PARTICLES,
+            particle_area: PARTICLE_REFERENCE_AREA,
+            particle_override: None,
+            max_cycles: None,
+            use_ascii: false,
+            brightness: 1.0,
+            night_mode: false,
+            monochrome: no_color(),
+            fixed_size: None,
+            theme: default_theme(),
             audio_enabled: true,
+            prefs: Prefs::default(),
+            prefs_path: None,
+            practice_days: BTreeSet::new(),
+            week_minutes: 0,
+            history_path: None,
+            volume: AudioConfig::default().amplitude,
+            drone: false,
+            bell: false,
+            metronome: false,
+            compact: false,
+            safety_acknowledged: false,
+            audio_status: AudioStatus::Initializing,
+            audio_auto_muted: false,
+            audio_notice_at: None,
+            auto_quit_after: None,
+            pause_timeout: None,
+            paused_at: None,
+            natural: false,
+            pace: 1.0,
+            seed: time_seed(),
+            last_cue_at: None,
+            phase_serial: 0,
+            manual: false,
+            pace_tolerance: DEFAULT_PACE_TOLERANCE,
+            pace_results: Vec::new(),
+            note: String::new(),
+            editing_note: false,
+            segments: Vec::new(),
+            segment_index: 0,
+            rounds: 1,
+            segment_results: Vec::new(),
+            total_hold_secs: 0.0,
+            pauses: 0,
+            longest_hold: None,
+            segment_start_time: now,
+            transition_started: None,
+            completed_at: None,
+            countdown_secs: 0,
+            countdown_started: now,
             phase_elapsed_at_pause: 0.0,
+            transition_at_pause: (1.0, 0.0),
+            resumed: false,
             session_elapsed_at_pause: Duration::ZERO,
+            clock: Box::new(SystemClock),
         }
     }
 
     /// Create app with a specific technique
     pub fn new_with_technique(technique: Technique, cycles: u32) -> Self {
-        let now = Instant::now();
+        let now = SystemClock.now();
         let default_cycles = technique.default_cycles;
-        let techniques = all_techniques();
+        let techniques = techniques_by_category();
         let mut list_state = ListState::default();
-        list_state.select(Some(0));
+        // Row 0 is the first category's header
+        list_state.select(Some(1));
 
         Self {
             techniques,
             selected_index: 0,
             list_state,
+            hit_areas: RefCell::default(),
+            search_query: String::new(),
+            searching: false,
             technique: Some(technique),
             state: AppState::Ready,
             cycles_target: if cycles > 0 { cycles } else { default_cycles },
             cycles_completed: 0,
             current_phase_index: 0,
+            start_phase_index: 0,
             phase_start_time: now,
             session_start_time: now,
-            particle_system: ParticleSystem::new(150),
+            ready_since: now,
+            particle_system: ParticleSystem::new(DEFAULT_MAX_PARTICLES),
             phase_transition_progress: 1.0,
             phase_transition_velocity: 0.0,
+            start_ramp: 1.0,
+            start_ramp_velocity: 0.0,
             previous_phase: None,
             celebration: None,
             show_help: false,
             show_guide: false,
+            show_disclaimer: false,
+            show_intro: false,
+            lang: Lang::default(),
+            show_route_hint: true,
+            reduced_motion: false,
+            tick_rate: fps_interval(DEFAULT_FPS),
+            battery_saver: false,
+            particle_budget: DEFAULT_MAX_PARTICLES,
+            particle_area: PARTICLE_REFERENCE_AREA,
+            particle_override: None,
+            max_cycles: None,
+            use_ascii: false,
+            brightness: 1.0,
+            night_mode: false,
+            monochrome: no_color(),
+            fixed_size: None,
+            theme: default_theme(),
             audio_enabled: true,
+            prefs: Prefs::default(),
+            prefs_path: None,
+            practice_days: BTreeSet::new(),
+            week_minutes: 0,
+            history_path: None,
+            volume: AudioConfig::default().amplitude,
+            drone: false,
+            bell: false,
+            metronome: false,
+            compact: false,
+            safety_acknowledged: false,
+            audio_status: AudioStatus::Initializing,
+            audio_auto_muted: false,
+            audio_notice_at: None,
+            auto_quit_after: None,
+            pause_timeout: None,
+            paused_at: None,
+            natural: false,
+            pace: 1.0,
+            seed: time_seed(),
+            last_cue_at: None,
+            phase_serial: 0,
+            manual: false,
+            pace_tolerance: DEFAULT_PACE_TOLERANCE,
+            pace_results: Vec::new(),
+            note: String::new(),
+            editing_note: false,
+            segments: Vec::new(),
+            segment_index: 0,
+            rounds: 1,
+            segment_results: Vec::new(),
+            total_hold_secs: 0.0,
+            pauses: 0,
+            longest_hold: None,
+            segment_start_time: now,
+            transition_started: None,
+            completed_at: None,
+            countdown_secs: 0,
+            countdown_started: now,
             phase_elapsed_at_pause: 0.0,
+            transition_at_pause: (1.0, 0.0),
+            resumed: false,
             session_elapsed_at_pause: Duration::ZERO,
+            clock: Box::new(SystemClock),
+        }
+    }
+
+    /// Create app for interval training: the plan runs in order, `rounds` times
+    pub fn new_with_segments(plan: Vec<(Technique, u32)>, rounds: u32) -> Self {
+        let rounds = rounds.max(1);
+        let segments: Vec<Segment> = (0..rounds)
+            .flat_map(|_| plan.iter().cloned())
+            .map(|(technique, cycles)| Segment { technique, cycles, retention: false, warmup: false })
+            .collect();
+
+        let first = segments[0].clone();
+        let mut app = Self::new_with_technique(first.technique, first.cycles);
+        app.segments = segments;
+        app.rounds = rounds;
+        app
+    }
+
+    /// Create app for round-based practice: each round is `cycles` of the
+    /// pattern, then an optional retention hold and recovery breath, with a
+    /// short rest between rounds
+    pub fn new_with_rounds(technique: Technique, cycles: u32, rounds: u32, retention: Option<f64>) -> Self {
+        let rounds = rounds.max(1);
+        let mut round = vec![Segment { technique: technique.clone(), cycles, retention: false, warmup: false }];
+        if let Some(hold_secs) = retention {
+            round.push(Segment {
+                technique: technique.retention(hold_secs),
+                cycles: 1,
+                retention: true,
+                warmup: false,
+            });
+        }
+        let segments: Vec<Segment> = (0..rounds).flat_map(|_| round.iter().cloned()).collect();
+
+        let mut app = Self::new_with_technique(technique, cycles);
+        app.segments = segments;
+        app.rounds = rounds;
+        app
+    }
+
+    /// Wrap the session in a calm warm-up and a recovery cool-down, each
+    /// its own segment with an "up next" card between
+    pub fn with_warmup(mut self) -> Self {
+        if self.segments.is_empty() {
+            let technique = self.current_technique().clone();
+            self.segments.push(Segment {
+                technique,
+                cycles: self.cycles_target,
+                retention: false,
+                warmup: false,
+            });
         }
+        let bookend = |(id, cycles): (&str, u32)| Segment {
+            technique: get_technique(id).expect("built-in technique"),
+            cycles,
+            retention: false,
+            warmup: true,
+        };
+        self.segments.insert(0, bookend(WARMUP));
+        self.segments.push(bookend(COOLDOWN));
+        self
+    }
+
+    /// Take the time from another clock (tests use a manual one)
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        let now = clock.now();
+        self.clock = Box::new(clock);
+        self.ready_since = now;
+        self.phase_start_time = now;
+        self.session_start_time = now;
+        self.segment_start_time = now;
+        self
+    }
+
+    fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Time since `earlier`, by the session clock
+    fn since(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
     }
 
     pub fn selected_technique(&self) -> &Technique {
@@ -169,30 +691,234 @@ impl App {
         self.technique.as_ref().unwrap()
     }
 
+    /// Indices into `techniques` of those matching the search query,
+    /// favorites first in the order they were starred
+    pub fn filtered_indices(&self) -> Vec<usize> {
+        let query = self.search_query.to_lowercase();
+        let mut indices: Vec<usize> = self
+            .techniques
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| {
+                query.is_empty()
+                    || t.name.to_lowercase().contains(&query)
+                    || t.id.to_lowercase().contains(&query)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        indices.sort_by_key(|&i| {
+            let id = self.techniques[i].id.as_ref();
+            self.prefs
+                .favorites
+                .iter()
+                .position(|f| f == id)
+                .unwrap_or(usize::MAX)
+        });
+        indices
+    }
+
+    pub fn is_favorite(&self, index: usize) -> bool {
+        self.prefs.is_favorite(&self.techniques[index].id)
+    }
+
+    /// Selector rows for the filtered techniques: favorites under their own
+    /// header, then a header before each category that has any
+    pub fn selector_rows(&self) -> Vec<SelectorRow> {
+        let mut rows = Vec::new();
+        let mut category = None;
+        for i in self.filtered_indices() {
+            if self.is_favorite(i) {
+                if rows.is_empty() {
+                    rows.push(SelectorRow::Favorites);
+                }
+                rows.push(SelectorRow::Technique(i));
+                continue;
+            }
+            let technique_category = self.techniques[i].category;
+            if category != Some(technique_category) {
+                category = Some(technique_category);
+                rows.push(SelectorRow::Header(technique_category));
+            }
+            rows.push(SelectorRow::Technique(i));
+        }
+        rows
+    }
+
+    /// Row of the selected technique, if it's showing
+    fn selected_row(&self) -> Option<usize> {
+        self.selector_rows()
+            .iter()
+            .position(|row| *row == SelectorRow::Technique(self.selected_index))
+    }
+
     pub fn select_next(&mut self) {
         if self.state == AppState::Selecting {
-            self.selected_index = (self.selected_index + 1) % self.techniques.len();
-            self.list_state.select(Some(self.selected_index));
+            let visible = self.filtered_indices();
+            if visible.is_empty() {
+                return;
+            }
+            let next = visible
+                .iter()
+                .position(|&i| i == self.selected_index)
+                .map_or(0, |pos| (pos + 1) % visible.len());
+            self.selected_index = visible[next];
+            self.list_state.select(self.selected_row());
         }
     }
 
     pub fn select_prev(&mut self) {
         if self.state == AppState::Selecting {
-            if self.selected_index == 0 {
-                self.selected_index = self.techniques.len() - 1;
-            } else {
-                self.selected_index -= 1;
+            let visible = self.filtered_indices();
+            if visible.is_empty() {
+                return;
+            }
+            let prev = match visible.iter().position(|&i| i == self.selected_index) {
+                Some(0) | None => visible.len() - 1,
+                Some(pos) => pos - 1,
+            };
+            self.selected_index = visible[prev];
+            self.list_state.select(self.selected_row());
+        }
+    }
+
+    /// Jump a screenful of techniques down (or up, for a negative
+    /// `direction`), stopping at either end of the list
+    pub fn select_page(&mut self, direction: i32) {
+        if self.state != AppState::Selecting {
+            return;
+        }
+        let visible = self.filtered_indices();
+        if visible.is_empty() {
+            return;
+        }
+        let step = (self.hit_areas.borrow().list.height as usize).saturating_sub(1).max(1);
+        let pos = visible.iter().position(|&i| i == self.selected_index).unwrap_or(0);
+        let target = if direction < 0 {
+            pos.saturating_sub(step)
+        } else {
+            (pos + step).min(visible.len() - 1)
+        };
+        self.selected_index = visible[target];
+        self.list_state.select(self.selected_row());
+    }
+
+    /// Scroll the selector so the selection shows in a list `height` rows
+    /// tall, along with its heading when it's the first in a group
+    pub fn scroll_selection_into_view(&mut self, height: usize) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        if height == 0 {
+            return;
+        }
+        let rows = self.selector_rows();
+        let top = match selected.checked_sub(1).map(|above| rows[above]) {
+            Some(SelectorRow::Header(_) | SelectorRow::Favorites) => selected - 1,
+            _ => selected,
+        };
+        let offset = self.list_state.offset_mut();
+        if top < *offset {
+            *offset = top;
+        } else if selected >= *offset + height {
+            *offset = selected + 1 - height;
+        }
+        // Never leave blank rows below the list once it's scrolled
+        *offset = (*offset).min(rows.len().saturating_sub(height));
+    }
+
+    pub fn start_search(&mut self) {
+        if self.state == AppState::Selecting {
+            self.searching = true;
+        }
+    }
+
+    pub fn search_input(&mut self, c: char) {
+        if self.searching && !c.is_control() {
+            self.search_query.push(c);
+            self.sync_selection();
+        }
+    }
+
+    pub fn search_backspace(&mut self) {
+        if self.searching {
+            self.search_query.pop();
+            self.sync_selection();
+        }
+    }
+
+    /// Close the search box and show every technique again
+    pub fn clear_search(&mut self) {
+        self.searching = false;
+        self.search_query.clear();
+        self.sync_selection();
+    }
+
+    /// Keep the selection on a visible technique after the filter changes,
+    /// falling back to the first match
+    fn sync_selection(&mut self) {
+        let visible = self.filtered_indices();
+        if !visible.contains(&self.selected_index) {
+            if let Some(&first) = visible.first() {
+                self.selected_index = first;
             }
-            self.list_state.select(Some(self.selected_index));
+        }
+        self.list_state.select(self.selected_row());
+    }
+
+    /// The selector row drawn at a screen position
+    pub fn row_at(&self, column: u16, row: u16) -> Option<SelectorRow> {
+        let list = self.hit_areas.borrow().list;
+        if !list.contains(Position::new(column, row)) {
+            return None;
+        }
+        let index = self.list_state.offset() + (row - list.y) as usize;
+        self.selector_rows().get(index).copied()
+    }
+
+    /// Action of the footer hint drawn at a screen position
+    pub fn hint_at(&self, column: u16, row: u16) -> Option<Action> {
+        self.hit_areas
+            .borrow()
+            .hints
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|&(_, key)| key)
+    }
+
+    /// Select a clicked technique, or open it if it was already selected
+    pub fn click_technique(&mut self, index: usize) {
+        if self.state != AppState::Selecting {
+            return;
+        }
+        if index == self.selected_index && self.list_state.selected().is_some() {
+            self.confirm_selection();
+        } else {
+            self.selected_index = index;
+            self.list_state.select(self.selected_row());
+        }
+    }
+
+    /// Star or unstar the selected technique, remembering it in prefs
+    pub fn toggle_favorite(&mut self) {
+        if self.state == AppState::Selecting && self.list_state.selected().is_some() {
+            self.prefs.toggle_favorite(&self.techniques[self.selected_index].id);
+            self.save_prefs();
+            self.list_state.select(self.selected_row());
         }
     }
 
     pub fn confirm_selection(&mut self) {
         if self.state == AppState::Selecting {
+            if self.list_state.selected().is_none() {
+                return;
+            }
+            self.searching = false;
             let technique = self.techniques[self.selected_index].clone();
             self.cycles_target = technique.default_cycles;
             self.technique = Some(technique);
+            self.safety_acknowledged = false;
             self.state = AppState::Ready;
+            self.ready_since = self.now();
         }
     }
 
@@ -200,42 +926,320 @@ impl App {
         self.show_guide = !self.show_guide;
     }
 
+    /// Nudge the cue volume, staying within 0-100% in whole steps
+    pub fn adjust_volume(&mut self, delta: f32) {
+        let steps = ((self.volume + delta) / VOLUME_STEP).round();
+        self.volume = (steps * VOLUME_STEP).clamp(0.0, 1.0);
+    }
+
+    /// Nudge the pace multiplier in tenths. The current phase keeps its
+    /// progress so the circle doesn't jump.
+    pub fn adjust_pace(&mut self, delta: f64) {
+        let old = self.pace;
+        let steps = ((self.pace + delta) / PACE_STEP).round();
+        self.pace = (steps * PACE_STEP).clamp(PACE_MIN, PACE_MAX);
+
+        if self.state == AppState::Breathing && self.pace != old {
+            let elapsed = self.phase_elapsed() * self.pace / old;
+            self.phase_start_time = self.now() - Duration::from_secs_f64(elapsed);
+        }
+    }
+
+    pub fn toggle_metronome(&mut self) {
+        self.metronome = !self.metronome;
+    }
+
+    /// Metronome brightness, 0.0 to 1.0, peaking on each whole second of
+    /// the phase. Follows the phase clock, so it holds still while paused.
+    pub fn metronome_pulse(&self) -> f64 {
+        pulse_sine(self.phase_elapsed() + 0.25, 1.0).powi(4)
+    }
+
+    pub fn toggle_drone(&mut self) {
+        self.drone = !self.drone;
+    }
+
+    /// The drone only sounds during active breathing with audio on
+    pub fn drone_playing(&self) -> bool {
+        self.drone && self.audio_enabled && self.state == AppState::Breathing
+    }
+
     pub fn toggle_audio(&mut self) {
         self.audio_enabled = !self.audio_enabled;
+        self.audio_auto_muted = false;
+        self.audio_notice_at = (self.audio_enabled && self.audio_status == AudioStatus::Unavailable)
+            .then(|| self.now());
+        self.prefs.audio_enabled = self.audio_enabled;
+        self.save_prefs();
+    }
+
+    /// Whether to tell the user there's no audio device, shortly after
+    /// they turned audio on anyway
+    pub fn audio_unavailable_notice(&self) -> bool {
+        self.audio_notice_at
+            .is_some_and(|at| self.since(at).as_secs_f64() < AUDIO_NOTICE_DURATION)
+    }
+
+    /// Pick up remembered settings and keep them up to date in `path`
+    pub fn use_prefs(&mut self, path: PathBuf) {
+        self.prefs = Prefs::load_from(&path);
+        self.audio_enabled = self.prefs.audio_enabled;
+        self.prefs_path = Some(path);
+        if self.state == AppState::Selecting {
+            // Favorites now lead the list, so start on the first of them
+            if let Some(&first) = self.filtered_indices().first() {
+                self.selected_index = first;
+            }
+            self.list_state.select(self.selected_row());
+        }
+    }
+
+    /// Days in a row practised, as of today
+    pub fn streak(&self) -> u32 {
+        history::streak(&self.practice_days, Local::now().date_naive())
+    }
+
+    /// The streak once this session is logged: today counts from now on
+    pub fn streak_with_session(&self) -> u32 {
+        let today = Local::now().date_naive();
+        let mut days = self.practice_days.clone();
+        days.insert(today);
+        history::streak(&days, today)
+    }
+
+    /// This week's minutes and the weekly goal, if one is set
+    pub fn weekly_goal(&self) -> Option<(u32, u32)> {
+        self.prefs.weekly_goal_minutes.map(|goal| (self.week_minutes, goal))
+    }
+
+    /// Whether this looks like someone's first session: no intro seen yet
+    /// and nothing in the history
+    pub fn is_first_run(&self) -> bool {
+        self.prefs.first_run && self.practice_days.is_empty()
+    }
+
+    /// Show the controls intro with box breathing selected, the gentlest
+    /// place to start
+    pub fn begin_intro(&mut self) {
+        self.show_intro = true;
+        if self.state == AppState::Selecting {
+            if let Some(index) = self.techniques.iter().position(|t| t.id == "box") {
+                self.selected_index = index;
+                self.list_state.select(self.selected_row());
+            }
+        }
+    }
+
+    pub fn dismiss_intro(&mut self) {
+        self.show_intro = false;
+        if self.prefs.first_run {
+            self.prefs.first_run = false;
+            self.save_prefs();
+        }
+    }
+
+    /// Append the completed session to the history log and count it toward
+    /// the streak and this week's minutes. History is a nice-to-have: a
+    /// failed write never spoils the session.
+    pub fn log_session(&mut self) {
+        let record = history::SessionRecord::from_app(self);
+        if let Some(path) = &self.history_path {
+            let _ = history::append_session_to(path, &record);
+        }
+        self.practice_days.insert(record.timestamp.date_naive());
+        self.week_minutes += (record.duration_secs / 60) as u32;
+    }
+
+    /// Preferences are a convenience: failing to save one never interrupts
+    /// a session
+    fn save_prefs(&self) {
+        if let Some(path) = &self.prefs_path {
+            let _ = self.prefs.save_to(path);
+        }
+    }
+
+    /// Track audio readiness: mute if the device never came up, and unmute
+    /// again if it turns up late
+    pub fn set_audio_status(&mut self, status: AudioStatus) {
+        match status {
+            AudioStatus::Unavailable if self.audio_enabled => {
+                self.audio_enabled = false;
+                self.audio_auto_muted = true;
+            }
+            AudioStatus::Ready if self.audio_auto_muted => {
+                self.audio_enabled = true;
+                self.audio_auto_muted = false;
+            }
+            _ => {}
+        }
+        self.audio_status = status;
     }
 
     pub fn back_to_selection(&mut self) {
         self.state = AppState::Selecting;
         self.technique = None;
+        self.segments.clear();
+        self.segment_index = 0;
+        self.rounds = 1;
+        self.segment_results.clear();
+        self.transition_started = None;
         self.current_phase_index = 0;
+        self.start_phase_index = 0;
         self.cycles_completed = 0;
         self.particle_system.clear();
         self.celebration = None;
+        self.completed_at = None;
+        self.pace_results.clear();
+        self.total_hold_secs = 0.0;
+        self.longest_hold = None;
+        self.pauses = 0;
+        self.note.clear();
+        self.editing_note = false;
         self.phase_elapsed_at_pause = 0.0;
         self.session_elapsed_at_pause = Duration::ZERO;
         self.phase_transition_progress = 1.0;
         self.previous_phase = None;
     }
 
+    /// From the complete screen, run the same session again straight away.
+    /// The finished one is logged first so looping never loses a session.
+    pub fn breathe_again(&mut self) {
+        if self.state == AppState::Complete {
+            self.log_session();
+            self.reset();
+            self.start();
+        }
+    }
+
     pub fn adjust_cycles(&mut self, delta: i32) {
-        if self.state == AppState::Ready {
-            let new_cycles = (self.cycles_target as i32 + delta).max(1).min(99);
-            self.cycles_target = new_cycles as u32;
+        // Interval sessions take their cycle counts from the plan
+        if self.state == AppState::Ready && self.segments.is_empty() && !self.is_endless() {
+            // A target already past the limit (e.g. from -c) is never
+            // lowered by a step up
+            let current = self.cycles_target as i64;
+            let limit = (self.cycle_limit() as i64).max(current);
+            self.cycles_target = (current + delta as i64).clamp(1, limit) as u32;
         }
     }
 
+    /// Most cycles the ready screen allows: --max-cycles, or as many as fit
+    /// in an hour of the current technique
+    pub fn cycle_limit(&self) -> u32 {
+        if let Some(max) = self.max_cycles {
+            return max.max(1);
+        }
+        let cycle = self.technique.as_ref().map_or(0.0, |t| t.cycle_duration());
+        if cycle <= 0.0 {
+            return 1;
+        }
+        ((MAX_SESSION_SECS / cycle).floor() as u32).max(1)
+    }
+
+    /// The cycle being breathed (1-based) for display. Once the last cycle
+    /// finishes this stays on it rather than showing one past the target.
+    pub fn current_cycle(&self) -> u32 {
+        let cycle = self.cycles_completed + 1;
+        if self.is_endless() {
+            cycle
+        } else {
+            cycle.min(self.cycles_target)
+        }
+    }
+
+    /// Endless sessions run until stopped with `finish`
+    pub fn is_endless(&self) -> bool {
+        self.cycles_target == 0
+    }
+
+    /// The safety note to show before starting: the current technique's, or
+    /// the first one in an interval plan
+    pub fn safety_note(&self) -> Option<&str> {
+        let technique = self.technique.as_ref()?;
+        technique
+            .safety_note
+            .as_deref()
+            .or_else(|| self.segments.iter().find_map(|s| s.technique.safety_note.as_deref()))
+    }
+
+    /// Advanced techniques with a safety note won't start until it has been
+    /// acknowledged
+    pub fn needs_safety_ack(&self) -> bool {
+        let Some(technique) = &self.technique else {
+            return false;
+        };
+        let advanced = std::iter::once(technique)
+            .chain(self.segments.iter().map(|s| &s.technique))
+            .any(|t| t.difficulty == Difficulty::Advanced && t.safety_note.is_some());
+        advanced && !self.safety_acknowledged
+    }
+
+    pub fn acknowledge_safety(&mut self) {
+        self.safety_acknowledged = true;
+    }
+
+    /// Start the session, counting in first if a countdown is set. Does
+    /// nothing while a safety note is waiting to be acknowledged.
     pub fn start(&mut self) {
+        if self.needs_safety_ack() {
+            return;
+        }
+        if !self.segments.is_empty() {
+            self.load_segment(0);
+            self.segment_results.clear();
+            self.transition_started = None;
+        }
+
+        if self.countdown_secs > 0 && self.technique.is_some() {
+            self.state = AppState::Countdown;
+            self.countdown_started = self.now();
+            let phase_count = self.current_technique().phases.len();
+            self.current_phase_index = self.start_phase_index.min(phase_count - 1);
+            self.particle_system.clear();
+            return;
+        }
+        self.begin_breathing();
+    }
+
+    /// Seconds left in the countdown, counting down from `countdown_secs`
+    pub fn countdown_remaining(&self) -> f64 {
+        (self.countdown_secs as f64 - self.since(self.countdown_started).as_secs_f64()).max(0.0)
+    }
+
+    /// Number to show in the countdown, or None once it's time to begin.
+    /// "Begin" gets a slot of its own, so each step is a little under a second.
+    pub fn countdown_number(&self) -> Option<u32> {
+        let total = self.countdown_secs as f64;
+        let step = total / (self.countdown_secs + 1) as f64;
+        let shown = (total - self.countdown_remaining()) / step;
+        let number = self.countdown_secs.saturating_sub(shown.floor() as u32);
+        Some(number).filter(|n| *n > 0)
+    }
+
+    /// End the countdown (or skip it) and take the first breath.
+    /// The session timer starts here.
+    pub fn begin_breathing(&mut self) {
         if self.technique.is_some() {
             self.state = AppState::Breathing;
-            self.session_start_time = Instant::now();
-            self.phase_start_time = Instant::now();
-            self.current_phase_index = 0;
+            self.session_start_time = self.now();
+            self.segment_start_time = self.now();
+            self.phase_start_time = self.now();
+            let phase_count = self.current_technique().phases.len();
+            self.current_phase_index = self.start_phase_index.min(phase_count - 1);
             self.cycles_completed = 0;
             self.phase_elapsed_at_pause = 0.0;
             self.session_elapsed_at_pause = Duration::ZERO;
             self.phase_transition_progress = 1.0;
             self.previous_phase = Some(self.current_phase().name);
+            self.start_ramp = 0.0;
+            self.start_ramp_velocity = 0.0;
             self.celebration = None;
+            self.completed_at = None;
+            self.pace_results.clear();
+            self.total_hold_secs = 0.0;
+            self.longest_hold = None;
+            self.pauses = 0;
+            self.note.clear();
+            self.editing_note = false;
 
             // Configure particle system for initial phase
             let scale = self.breath_scale();
@@ -246,30 +1250,89 @@ impl App {
     pub fn toggle_pause(&mut self) {
         match self.state {
             AppState::Breathing => {
-                self.phase_elapsed_at_pause = self.phase_start_time.elapsed().as_secs_f64();
-                self.session_elapsed_at_pause = self.session_start_time.elapsed();
+                self.phase_elapsed_at_pause = self.since(self.phase_start_time).as_secs_f64();
+                self.session_elapsed_at_pause = self.since(self.session_start_time);
+                self.transition_at_pause =
+                    (self.phase_transition_progress, self.phase_transition_velocity);
                 self.state = AppState::Paused;
+                self.paused_at = Some(self.now());
+                self.pauses += 1;
             }
             AppState::Paused => {
+                self.paused_at = None;
                 self.phase_start_time =
-                    Instant::now() - Duration::from_secs_f64(self.phase_elapsed_at_pause);
-                self.session_start_time = Instant::now() - self.session_elapsed_at_pause;
+                    self.now() - Duration::from_secs_f64(self.phase_elapsed_at_pause);
+                self.session_start_time = self.now() - self.session_elapsed_at_pause;
+                (self.phase_transition_progress, self.phase_transition_velocity) =
+                    self.transition_at_pause;
+                self.resumed = true;
                 self.state = AppState::Breathing;
             }
             _ => {}
         }
     }
 
+    fn pause_timed_out(&self) -> bool {
+        match (self.pause_timeout, self.paused_at) {
+            (Some(timeout), Some(paused_at)) => self.since(paused_at) >= timeout,
+            _ => false,
+        }
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
 
+    /// Open the note prompt on the complete screen
+    pub fn start_note(&mut self) {
+        if self.state == AppState::Complete {
+            self.editing_note = true;
+        }
+    }
+
+    pub fn note_input(&mut self, c: char) {
+        if self.editing_note && !c.is_control() && self.note.chars().count() < MAX_NOTE_LEN {
+            self.note.push(c);
+        }
+    }
+
+    pub fn note_backspace(&mut self) {
+        if self.editing_note {
+            self.note.pop();
+        }
+    }
+
+    /// Close the prompt, keeping the note (trimmed)
+    pub fn finish_note(&mut self) {
+        self.editing_note = false;
+        self.note = self.note.trim().to_string();
+    }
+
+    /// Close the prompt and discard the note
+    pub fn cancel_note(&mut self) {
+        self.editing_note = false;
+        self.note.clear();
+    }
+
     pub fn reset(&mut self) {
+        if !self.segments.is_empty() {
+            self.load_segment(0);
+            self.segment_results.clear();
+            self.transition_started = None;
+        }
         self.state = AppState::Ready;
+        self.ready_since = self.now();
         self.current_phase_index = 0;
         self.cycles_completed = 0;
         self.particle_system.clear();
         self.celebration = None;
+        self.completed_at = None;
+        self.pace_results.clear();
+        self.total_hold_secs = 0.0;
+        self.longest_hold = None;
+        self.pauses = 0;
+        self.note.clear();
+        self.editing_note = false;
         self.phase_elapsed_at_pause = 0.0;
         self.session_elapsed_at_pause = Duration::ZERO;
         self.phase_transition_progress = 1.0;
@@ -280,25 +1343,74 @@ impl App {
         &self.current_technique().phases[self.current_phase_index]
     }
 
+    /// Duration of the current phase, including pace and natural-mode jitter.
+    /// The jitter is derived from the seed, cycle and phase, so it stays
+    /// fixed for the whole phase and is reproducible for a given seed.
+    pub fn effective_phase_duration(&self) -> f64 {
+        let technique = self.current_technique();
+        let base = self.current_phase().duration_secs * self.pace;
+        if !self.natural || technique.precise_timing() {
+            return base;
+        }
+
+        let phase_seed = self.seed
+            ^ (self.cycles_completed as u64).wrapping_mul(0x1000_0000_01B3)
+            ^ (self.current_phase_index as u64).wrapping_mul(0x9E37_79B9);
+        let jitter = Rng::new(phase_seed).range(-NATURAL_JITTER, NATURAL_JITTER);
+        base * (1.0 + jitter)
+    }
+
     pub fn phase_elapsed(&self) -> f64 {
         if self.state == AppState::Paused {
             self.phase_elapsed_at_pause
         } else {
-            self.phase_start_time.elapsed().as_secs_f64()
+            self.since(self.phase_start_time).as_secs_f64()
         }
     }
 
     pub fn phase_progress(&self) -> f64 {
         let elapsed = self.phase_elapsed();
-        let duration = self.current_phase().duration_secs;
+        let duration = self.effective_phase_duration();
         (elapsed / duration).min(1.0)
     }
 
+    /// How long the session will take at the current cycle count and
+    /// pace; None for endless sessions
+    pub fn estimated_duration(&self) -> Option<Duration> {
+        if self.is_endless() {
+            return None;
+        }
+        let secs = self.current_technique().cycle_duration() * self.pace * self.cycles_target as f64;
+        Some(Duration::from_secs_f64(secs))
+    }
+
+    /// Share of the whole session done, 0.0 to 1.0, counting the current
+    /// cycle down to the phase; None for endless sessions
+    pub fn session_progress(&self) -> Option<f64> {
+        if self.is_endless() {
+            return None;
+        }
+        let technique = self.current_technique();
+        let cycle = technique.cycle_duration();
+        if cycle <= 0.0 {
+            return None;
+        }
+        let before: f64 = technique.phases[..self.current_phase_index]
+            .iter()
+            .map(|phase| phase.duration_secs)
+            .sum();
+        let within_cycle = before + self.phase_progress() * self.current_phase().duration_secs;
+        let done = self.cycles_completed as f64 * cycle + within_cycle;
+        Some((done / (self.cycles_target as f64 * cycle)).clamp(0.0, 1.0))
+    }
+
     pub fn session_elapsed(&self) -> Duration {
-        if self.state == AppState::Paused || self.state == AppState::Complete {
+        if self.state == AppState::Countdown {
+            Duration::ZERO
+        } else if matches!(self.state, AppState::Paused | AppState::Transition | AppState::Complete) {
             self.session_elapsed_at_pause
         } else {
-            self.session_start_time.elapsed()
+            self.since(self.session_start_time)
         }
     }
 
@@ -314,17 +1426,24 @@ impl App {
         // Use organic breathing easing curve
         let eased = ease_breath(progress);
 
-        match phase {
+        let scale = match phase {
             PhaseName::Inhale => eased,
             PhaseName::Hold => 1.0,
             PhaseName::Exhale => 1.0 - eased,
             PhaseName::HoldAfterExhale => 0.0,
-        }
+        };
+        lerp(START_RAMP_SCALE, scale, self.start_ramp)
+    }
+
+    /// The phase being blended away from: the one that was on screen before
+    /// the current phase, however it was left (timer, skip or step back)
+    pub fn previous_phase(&self) -> Option<PhaseName> {
+        self.previous_phase
     }
 
     /// Get blended phase colors for smooth transitions between phases
     pub fn get_blended_phase_colors(&self) -> PhaseColors {
-        let theme = default_theme();
+        let theme = &self.theme;
         let current_colors = theme.get_phase_colors(self.current_phase().name);
 
         if let Some(prev_phase) = self.previous_phase {
@@ -347,34 +1466,168 @@ impl App {
             }
         }
 
+        if self.state == AppState::Countdown {
+            if self.countdown_remaining() <= 0.0 {
+                self.begin_breathing();
+            }
+            return;
+        }
+
+        // Move on from the interval "up next" card once it has shown long enough
+        if self.state == AppState::Transition {
+            let shown = self.transition_started.map_or(0.0, |at| self.since(at).as_secs_f64());
+            if shown >= SEGMENT_TRANSITION_SECS {
+                self.begin_next_segment();
+            }
+            return;
+        }
+
+        // A session left paused too long ends: with its summary if any
+        // cycles were done, otherwise back to the start screen
+        if self.state == AppState::Paused {
+            if self.pause_timed_out() {
+                self.paused_at = None;
+                if self.total_cycles() > 0 {
+                    self.complete();
+                } else {
+                    self.reset();
+                }
+            }
+            return;
+        }
+
         // Don't update breathing state if paused or complete
         if self.state != AppState::Breathing {
             return;
         }
 
         // Update phase transition progress
+        let blend_dt = if std::mem::take(&mut self.resumed) { 0.0 } else { dt };
         if self.phase_transition_progress < 1.0 {
             self.phase_transition_progress = smooth_damp(
                 self.phase_transition_progress,
                 1.0,
                 &mut self.phase_transition_velocity,
                 TRANSITION_SMOOTH_TIME,
-                dt,
+                blend_dt,
+            );
+        }
+
+        if self.start_ramp < 1.0 {
+            self.start_ramp = smooth_damp(
+                self.start_ramp,
+                1.0,
+                &mut self.start_ramp_velocity,
+                START_RAMP_SMOOTH_TIME,
+                blend_dt,
             );
         }
 
         // Update particle system
         self.particle_system.update(dt);
 
-        // Check for phase transition
-        if self.phase_elapsed() >= self.current_phase().duration_secs {
+        // Check for phase transition (manual mode waits for a tap)
+        if !self.manual && self.phase_elapsed() >= self.effective_phase_duration() {
             self.advance_phase();
         }
     }
 
-    fn advance_phase(&mut self) {
-        // Store previous phase for color blending
-        self.previous_phase = Some(self.current_phase().name);
+    /// Move on to the next phase as if its timer had expired.
+    /// In manual mode this also scores how close the tap was to the target.
+    pub fn skip_phase(&mut self) {
+        if self.state != AppState::Breathing {
+            return;
+        }
+
+        if self.manual {
+            let ratio = self.phase_elapsed() / self.effective_phase_duration();
+            self.pace_results.push(PaceResult {
+                phase_index: self.current_phase_index,
+                ratio,
+                pace: Pace::judge(ratio, self.pace_tolerance),
+            });
+        }
+
+        self.advance_phase();
+    }
+
+    /// Start the current phase over, keeping its place in the cycle
+    pub fn restart_phase(&mut self) {
+        if self.state != AppState::Breathing {
+            return;
+        }
+
+        self.phase_start_time = self.now();
+        self.phase_transition_progress = 1.0;
+        self.phase_transition_velocity = 0.0;
+    }
+
+    /// Step back to the previous phase, into the previous cycle if needed.
+    /// The very first phase of a session just starts over.
+    pub fn prev_phase(&mut self) {
+        if self.state != AppState::Breathing {
+            return;
+        }
+        if self.current_phase_index == 0 && self.cycles_completed == 0 {
+            self.restart_phase();
+            return;
+        }
+
+        self.previous_phase = Some(self.current_phase().name);
+        self.phase_serial += 1;
+
+        if self.current_phase_index == 0 {
+            self.current_phase_index = self.current_technique().phases.len() - 1;
+            self.cycles_completed -= 1;
+        } else {
+            self.current_phase_index -= 1;
+        }
+
+        self.phase_start_time = self.now();
+        self.phase_transition_progress = 0.0;
+        self.phase_transition_velocity = 0.0;
+
+        let scale = self.breath_scale();
+        self.particle_system.configure_for_phase(self.current_phase().name, scale);
+    }
+
+    /// Feedback for the most recent manual tap, while it is still fresh
+    pub fn pace_feedback(&self) -> Option<Pace> {
+        if self.state != AppState::Breathing || self.phase_elapsed() > PACE_FEEDBACK_DURATION {
+            return None;
+        }
+        self.pace_results.last().map(|result| result.pace)
+    }
+
+    /// Phases tapped on pace and total phases tapped, in manual mode
+    pub fn pace_score(&self) -> Option<(usize, usize)> {
+        if !self.manual || self.pace_results.is_empty() {
+            return None;
+        }
+        let on_pace = self.pace_results.iter().filter(|r| r.pace == Pace::OnTarget).count();
+        Some((on_pace, self.pace_results.len()))
+    }
+
+    /// On-pace and total tap counts for each phase of the technique
+    pub fn pace_by_phase(&self) -> Vec<(usize, usize)> {
+        let mut counts = vec![(0, 0); self.current_technique().phases.len()];
+        for result in &self.pace_results {
+            if let Some(entry) = counts.get_mut(result.phase_index) {
+                entry.1 += 1;
+                if result.pace == Pace::OnTarget {
+                    entry.0 += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    fn advance_phase(&mut self) {
+        self.record_hold();
+
+        // Store previous phase for color blending
+        self.previous_phase = Some(self.current_phase().name);
+        self.phase_serial += 1;
 
         self.current_phase_index += 1;
 
@@ -384,21 +1637,34 @@ impl App {
             self.cycles_completed += 1;
 
             // Check if session is complete
-            if self.cycles_completed >= self.cycles_target {
-                // Capture final duration before changing state
-                self.session_elapsed_at_pause = self.session_start_time.elapsed();
-                self.state = AppState::Complete;
-
-                // Start celebration animation
-                let mut celebration = CelebrationAnimation::new();
-                celebration.set_center(0.0, 0.0);
-                celebration.spawn_burst();
-                self.celebration = Some(celebration);
+            if !self.is_endless() && self.cycles_completed >= self.cycles_target {
+                self.record_segment();
+
+                // Interval sessions show what's next before the following
+                // segment; a round's retention follows straight on
+                if let Some(next) = self.next_segment() {
+                    if next.retention {
+                        // Keep blending from the phase that just ended
+                        let previous = self.previous_phase;
+                        self.start_segment(self.segment_index + 1);
+                        self.previous_phase = previous;
+                        self.phase_transition_progress = 0.0;
+                    } else {
+                        // The "up next" card isn't practice: freeze the
+                        // session clock as a pause would
+                        self.session_elapsed_at_pause = self.session_elapsed();
+                        self.state = AppState::Transition;
+                        self.transition_started = Some(self.now());
+                    }
+                    return;
+                }
+
+                self.complete();
                 return;
             }
         }
 
-        self.phase_start_time = Instant::now();
+        self.phase_start_time = self.now();
 
         // Reset transition progress for smooth color blending
         self.phase_transition_progress = 0.0;
@@ -409,6 +1675,251 @@ impl App {
         self.particle_system.configure_for_phase(self.current_phase().name, scale);
     }
 
+    /// End an endless session on the user's word, keeping the cycles
+    /// completed so far for the summary
+    pub fn finish(&mut self) {
+        if matches!(self.state, AppState::Breathing | AppState::Paused) {
+            self.complete();
+        }
+    }
+
+    fn complete(&mut self) {
+        // Capture final duration before changing state
+        self.session_elapsed_at_pause = self.session_elapsed();
+        self.state = AppState::Complete;
+        self.completed_at = Some(self.now());
+
+        // Start celebration animation
+        let mut celebration = CelebrationAnimation::with_seed(self.seed);
+        celebration.set_center(0.0, 0.0);
+        celebration.spawn_burst();
+        self.celebration = Some(celebration);
+    }
+
+    /// Calm mode: no particles or ambient animation, just the breathing
+    /// circle and progress bar
+    pub fn enable_reduced_motion(&mut self) {
+        self.reduced_motion = true;
+        self.particle_system.emitting = false;
+        self.particle_system.clear_emitters();
+        self.particle_system.particles.clear();
+    }
+
+    /// Use a fixed seed so particles, the celebration and natural-mode
+    /// jitter play out the same way every run
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.particle_system.reseed(seed);
+    }
+
+    /// Update and redraw `fps` times a second
+    pub fn set_fps(&mut self, fps: u32) {
+        self.tick_rate = fps_interval(fps.clamp(MIN_FPS, MAX_FPS));
+    }
+
+    /// Battery saver: a lower frame rate and fewer particles for long
+    /// sessions on a laptop
+    pub fn enable_battery_saver(&mut self) {
+        self.battery_saver = true;
+        self.set_fps(BATTERY_SAVER_FPS);
+        self.particle_budget = self.particle_budget.min(BATTERY_SAVER_MAX_PARTICLES);
+        self.update_particle_cap();
+    }
+
+    /// Scale the particle cap to the size of the drawing area (at session
+    /// start and on resize)
+    pub fn fit_particles(&mut self, width: u16, height: u16) {
+        self.particle_area = (width, height);
+        self.update_particle_cap();
+    }
+
+    /// Use exactly `count` particles at most, whatever the terminal size
+    pub fn set_particle_override(&mut self, count: usize) {
+        self.particle_override = Some(count);
+        self.update_particle_cap();
+    }
+
+    fn update_particle_cap(&mut self) {
+        let cap = self.particle_override.unwrap_or_else(|| {
+            let (width, height) = self.particle_area;
+            let (ref_width, ref_height) = PARTICLE_REFERENCE_AREA;
+            let scale = (width as f64 * height as f64) / (ref_width as f64 * ref_height as f64);
+            let scaled = (self.particle_budget as f64 * scale).round() as usize;
+            scaled.clamp(MIN_SCALED_PARTICLES.min(self.particle_budget), MAX_SCALED_PARTICLES)
+        });
+        self.particle_system.max_particles = cap;
+        self.particle_system.density = cap as f64 / DEFAULT_MAX_PARTICLES as f64;
+    }
+
+    /// Dim, red-shifted colors and fewer particles for use in a dark room
+    pub fn enable_night_mode(&mut self) {
+        self.night_mode = true;
+        self.brightness = NIGHT_BRIGHTNESS;
+        self.particle_budget = self.particle_budget.min(NIGHT_MAX_PARTICLES);
+        self.update_particle_cap();
+    }
+
+    /// Apply the global brightness and night-mode shift to a color
+    pub fn grade(&self, color: Color) -> Color {
+        if self.night_mode {
+            night_shift(color, self.brightness)
+        } else if self.brightness < 1.0 {
+            with_opacity(color, self.brightness)
+        } else {
+            color
+        }
+    }
+
+    /// Record that a phase cue fired, for the visual cue indicator
+    pub fn mark_cue(&mut self) {
+        self.last_cue_at = Some(self.now());
+    }
+
+    /// Intensity of the cue indicator (1.0 right after a cue, fading to 0.0)
+    pub fn cue_flash(&self) -> f64 {
+        match self.last_cue_at {
+            Some(at) => (1.0 - self.since(at).as_secs_f64() / CUE_FLASH_DURATION).max(0.0),
+            None => 0.0,
+        }
+    }
+
+    /// Add the phase that just ended to the hold stats, if it was a hold
+    fn record_hold(&mut self) {
+        if !matches!(self.current_phase().name, PhaseName::Hold | PhaseName::HoldAfterExhale) {
+            return;
+        }
+
+        let held = self.phase_elapsed();
+        self.total_hold_secs += held;
+        if self.longest_hold.as_ref().is_none_or(|(longest, _)| held > *longest) {
+            self.longest_hold = Some((held, self.current_technique().name.clone()));
+        }
+    }
+
+    /// Switch to an interval segment's technique and cycle target
+    fn load_segment(&mut self, index: usize) {
+        let segment = self.segments[index].clone();
+        self.technique = Some(segment.technique);
+        self.cycles_target = segment.cycles;
+        self.segment_index = index;
+    }
+
+    /// Record totals for the segment that just finished
+    fn record_segment(&mut self) {
+        if self.segments.get(self.segment_index).is_none_or(|segment| segment.retention) {
+            return;
+        }
+        self.segment_results.push(SegmentResult {
+            technique_name: self.current_technique().name.clone(),
+            cycles: self.cycles_completed,
+            breaths: self.cycles_completed * self.current_technique().inhales_per_cycle(),
+            duration: self.since(self.segment_start_time),
+        });
+    }
+
+    /// The segment after the current one, in an interval session
+    pub fn next_segment(&self) -> Option<&Segment> {
+        self.segments.get(self.segment_index + 1)
+    }
+
+    /// Start the next interval segment (from the transition card)
+    pub fn begin_next_segment(&mut self) {
+        if self.state != AppState::Transition || self.next_segment().is_none() {
+            return;
+        }
+        self.session_start_time = self.now() - self.session_elapsed_at_pause;
+        self.start_segment(self.segment_index + 1);
+    }
+
+    /// Load a segment and begin breathing it from its first phase
+    fn start_segment(&mut self, index: usize) {
+        self.load_segment(index);
+        self.state = AppState::Breathing;
+        self.transition_started = None;
+        self.cycles_completed = 0;
+        self.current_phase_index = 0;
+        self.phase_start_time = self.now();
+        self.segment_start_time = self.now();
+        self.phase_serial += 1;
+        self.previous_phase = Some(self.current_phase().name);
+        self.phase_transition_progress = 1.0;
+
+        let scale = self.breath_scale();
+        self.particle_system.configure_for_phase(self.current_phase().name, scale);
+    }
+
+    /// Seconds left on the transition card
+    pub fn transition_remaining(&self) -> f64 {
+        let shown = self.transition_started.map_or(0.0, |at| self.since(at).as_secs_f64());
+        (SEGMENT_TRANSITION_SECS - shown).max(0.0)
+    }
+
+    /// Which round of an interval session is running (1-based)
+    pub fn current_round(&self) -> u32 {
+        self.round_of(self.segment_index)
+    }
+
+    /// Round (1-based) that a segment belongs to
+    pub fn round_of(&self, segment_index: usize) -> u32 {
+        let rounds = self.rounds.max(1);
+        let in_rounds = self.segments.iter().filter(|s| !s.warmup).count();
+        let per_round = (in_rounds / rounds as usize).max(1);
+        // A warm-up counts as part of the first round, a cool-down the last
+        let before = self.segments.iter().take(segment_index).filter(|s| !s.warmup).count();
+        ((before / per_round) as u32 + 1).min(rounds)
+    }
+
+    /// Cycles completed across every segment of the session
+    pub fn total_cycles(&self) -> u32 {
+        if self.segment_results.is_empty() {
+            self.cycles_completed
+        } else {
+            self.segment_results.iter().map(|r| r.cycles).sum()
+        }
+    }
+
+    /// Breaths (inhales) taken in completed cycles, across all segments
+    pub fn total_breaths(&self) -> u32 {
+        if self.segment_results.is_empty() {
+            self.cycles_completed * self.current_technique().inhales_per_cycle()
+        } else {
+            self.segment_results.iter().map(|r| r.breaths).sum()
+        }
+    }
+
+    /// Cycles and time per technique across an interval session, in the
+    /// order each technique first ran. A single pass (a sequence) keeps a
+    /// row per segment, even when a technique comes up twice.
+    pub fn technique_totals(&self) -> Vec<(&str, u32, Duration)> {
+        let mut totals: Vec<(&str, u32, Duration)> = Vec::new();
+        for result in &self.segment_results {
+            let merge = self.rounds > 1;
+            match totals.iter_mut().find(|(name, _, _)| merge && *name == result.technique_name) {
+                Some(entry) => {
+                    entry.1 += result.cycles;
+                    entry.2 += result.duration;
+                }
+                None => totals.push((&result.technique_name, result.cycles, result.duration)),
+            }
+        }
+        totals
+    }
+
+    /// Whether the auto-quit delay has elapsed on the complete screen.
+    /// A non-zero delay also waits for the celebration to finish.
+    pub fn auto_quit_due(&self) -> bool {
+        let (Some(delay), Some(completed_at)) = (self.auto_quit_after, self.completed_at) else {
+            return false;
+        };
+        if self.state != AppState::Complete || self.editing_note {
+            return false;
+        }
+        if delay <= 0.0 {
+            return true;
+        }
+        self.since(completed_at).as_secs_f64() >= delay && self.celebration.is_none()
+    }
+
     pub fn format_time(duration: Duration) -> String {
         let total_secs = duration.as_secs();
         let mins = total_secs / 60;
@@ -422,3 +1933,987 @@ impl App {
         &self.particle_system.particles
     }
 }
+
+/// Focus score points for finishing the planned cycles
+const SCORE_COMPLETION: f64 = 60.0;
+/// Focus score points for not pausing
+const SCORE_STEADINESS: f64 = 25.0;
+/// Focus score points for practice time, in full from `SCORE_FULL_MINUTES`
+const SCORE_TIME: f64 = 15.0;
+/// Minutes of practice that earn all of `SCORE_TIME`
+const SCORE_FULL_MINUTES: f64 = 5.0;
+/// Steadiness points lost per pause
+const SCORE_PAUSE_PENALTY: f64 = 10.0;
+
+/// A 0-100 focus score for a session: mostly how much of the plan was
+/// breathed, then how steadily, then for how long
+pub fn session_score(app: &App) -> u8 {
+    let planned: u32 = if app.segments.is_empty() {
+        app.cycles_target
+    } else {
+        app.segments.iter().filter(|s| !s.retention).map(|s| s.cycles).sum()
+    };
+    // An endless session has no plan to fall short of
+    let completion = if planned == 0 {
+        1.0
+    } else {
+        (app.total_cycles() as f64 / planned as f64).min(1.0)
+    };
+    let steadiness = (SCORE_STEADINESS - app.pauses as f64 * SCORE_PAUSE_PENALTY).max(0.0);
+    let minutes = app.session_elapsed().as_secs_f64() / 60.0;
+    let time = (minutes / SCORE_FULL_MINUTES).min(1.0) * SCORE_TIME;
+    (completion * SCORE_COMPLETION + steadiness + time).round().clamp(0.0, 100.0) as u8
+}
+
+/// A word for a focus score
+pub fn score_label(score: u8) -> &'static str {
+    match score {
+        90.. => "Excellent",
+        75..=89 => "Good",
+        50..=74 => "Steady",
+        _ => "Keep practicing",
+    }
+}
+
+/// Whole cycles of a technique at `pace` that best fill `minutes` (at least one)
+pub fn cycles_for_duration(technique: &Technique, minutes: u64, pace: f64) -> u32 {
+    let cycle_secs = technique.cycle_duration() * pace;
+    if cycle_secs <= 0.0 {
+        return 1;
+    }
+    ((minutes as f64 * 60.0) / cycle_secs).round().max(1.0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::techniques::get_technique;
+
+    /// Run the app for `secs` in 50ms frames, as the main loop would
+    fn run_for(app: &mut App, clock: &ManualClock, secs: f64) {
+        let frames = (secs / 0.05).round() as u32;
+        for _ in 0..frames {
+            clock.advance(Duration::from_millis(50));
+            app.tick(0.05);
+        }
+    }
+
+    #[test]
+    fn test_clock_drives_a_full_session() {
+        // Box: four 4s phases, so two cycles take 32s
+        let clock = ManualClock::new();
+        let mut app =
+            App::new_with_technique(get_technique("box").unwrap(), 2).with_clock(clock.clone());
+        app.countdown_secs = 0;
+        app.start();
+
+        let mut phases = vec![app.current_phase_index];
+        for _ in 0..(33.0 / 0.05) as u32 {
+            run_for(&mut app, &clock, 0.05);
+            if app.state == AppState::Breathing && phases.last() != Some(&app.current_phase_index) {
+                phases.push(app.current_phase_index);
+            }
+            if app.state == AppState::Complete {
+                break;
+            }
+        }
+        assert_eq!(phases, [0, 1, 2, 3, 0, 1, 2, 3]);
+        assert_eq!(app.state, AppState::Complete);
+        assert_eq!(app.cycles_completed, 2);
+        assert_eq!(app.session_elapsed().as_secs(), 32);
+    }
+
+    #[test]
+    fn test_last_cycle_completes_on_time_and_is_never_overcounted() {
+        // 4-0-4-0: the holds are empty, leaving an 8s cycle
+        let base = get_technique("coherent").unwrap();
+        let technique = Technique {
+            pattern: "4-4".into(),
+            phases: vec![
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "In".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 4.0, instruction: "Out".into() },
+            ],
+            ..base
+        };
+        let clock = ManualClock::new();
+        let mut app = App::new_with_technique(technique, 2).with_clock(clock.clone());
+        app.countdown_secs = 0;
+        app.start();
+
+        for _ in 0..319 {
+            run_for(&mut app, &clock, 0.05);
+            assert_eq!(app.state, AppState::Breathing);
+            assert!(app.current_cycle() <= app.cycles_target);
+        }
+        assert_eq!(app.current_cycle(), 2);
+        run_for(&mut app, &clock, 0.05);
+        assert_eq!(app.state, AppState::Complete);
+        assert_eq!(app.cycles_completed, 2);
+        assert_eq!(app.current_cycle(), 2);
+    }
+
+    #[test]
+    fn test_clock_times_the_countdown_and_pauses() {
+        let clock = ManualClock::new();
+        let mut app =
+            App::new_with_technique(get_technique("box").unwrap(), 2).with_clock(clock.clone());
+        app.countdown_secs = 3;
+        app.start();
+        run_for(&mut app, &clock, 2.9);
+        assert_eq!(app.state, AppState::Countdown);
+        run_for(&mut app, &clock, 0.2);
+        assert_eq!(app.state, AppState::Breathing);
+
+        // Time spent paused doesn't count toward the phase
+        run_for(&mut app, &clock, 1.0);
+        let before = app.phase_elapsed();
+        app.toggle_pause();
+        run_for(&mut app, &clock, 60.0);
+        app.toggle_pause();
+        assert_eq!(app.phase_elapsed(), before);
+        assert_eq!(app.current_phase_index, 0);
+    }
+
+    #[test]
+    fn test_colour_blend_is_frozen_across_a_pause() {
+        let clock = ManualClock::new();
+        let mut app =
+            App::new_with_technique(get_technique("box").unwrap(), 2).with_clock(clock.clone());
+        app.countdown_secs = 0;
+        app.start();
+        app.skip_phase();
+        run_for(&mut app, &clock, 0.1);
+        let progress = app.phase_transition_progress;
+        assert!(progress > 0.0 && progress < 1.0, "{}", progress);
+
+        app.toggle_pause();
+        // Slow idle frames while paused
+        for _ in 0..8 {
+            clock.advance(Duration::from_millis(250));
+            app.tick(0.25);
+        }
+        assert_eq!(app.phase_transition_progress, progress);
+
+        app.toggle_pause();
+        app.tick(0.25);
+        assert_eq!(app.phase_transition_progress, progress);
+        app.tick(0.05);
+        assert!(app.phase_transition_progress > progress);
+    }
+
+    #[test]
+    fn test_battery_saver_lowers_the_frame_rate_and_particles() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        assert_eq!(app.tick_rate, fps_interval(DEFAULT_FPS));
+
+        app.enable_battery_saver();
+        assert_eq!(app.tick_rate, fps_interval(15));
+        assert_eq!(app.particle_system.max_particles, BATTERY_SAVER_MAX_PARTICLES);
+
+        // An explicit rate still wins, within bounds
+        app.set_fps(1000);
+        assert_eq!(app.tick_rate, fps_interval(MAX_FPS));
+    }
+
+    #[test]
+    fn test_particle_cap_scales_with_terminal_area() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.fit_particles(120, 40);
+        assert_eq!(app.particle_system.max_particles, DEFAULT_MAX_PARTICLES);
+        app.fit_particles(60, 40);
+        assert_eq!(app.particle_system.max_particles, DEFAULT_MAX_PARTICLES / 2);
+        assert!((app.particle_system.density - 0.5).abs() < 0.01);
+        app.fit_particles(20, 5);
+        assert_eq!(app.particle_system.max_particles, MIN_SCALED_PARTICLES);
+        app.fit_particles(400, 120);
+        assert_eq!(app.particle_system.max_particles, MAX_SCALED_PARTICLES);
+
+        // Night mode scales from its own smaller cap; --particles ignores size
+        app.enable_night_mode();
+        app.fit_particles(60, 40);
+        assert_eq!(app.particle_system.max_particles, NIGHT_MAX_PARTICLES / 2);
+        app.set_particle_override(500);
+        assert_eq!(app.particle_system.max_particles, 500);
+    }
+
+    #[test]
+    fn test_reduced_motion_stops_particles_but_keeps_breathing() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.enable_reduced_motion();
+        app.start();
+
+        let start_scale = app.breath_scale();
+        app.phase_start_time = Instant::now() - Duration::from_secs(2);
+        for _ in 0..20 {
+            app.tick(0.05);
+        }
+        assert!(app.particle_system.particles.is_empty());
+        assert!(app.breath_scale() > start_scale);
+    }
+
+    #[test]
+    fn test_volume_steps_and_clamps() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.volume = 0.15;
+        app.adjust_volume(VOLUME_STEP);
+        assert!((app.volume - 0.2).abs() < 1e-6);
+        for _ in 0..30 {
+            app.adjust_volume(VOLUME_STEP);
+        }
+        assert_eq!(app.volume, 1.0);
+        for _ in 0..30 {
+            app.adjust_volume(-VOLUME_STEP);
+        }
+        assert_eq!(app.volume, 0.0);
+    }
+
+    #[test]
+    fn test_pace_scales_phases_and_keeps_progress() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.start();
+        app.phase_start_time = Instant::now() - Duration::from_secs(2);
+        let progress = app.phase_progress();
+
+        app.adjust_pace(PACE_STEP * 10.0);
+        assert_eq!(app.pace, PACE_MAX);
+        assert_eq!(app.effective_phase_duration(), 8.0);
+        assert!((app.phase_progress() - progress).abs() < 0.01);
+
+        for _ in 0..30 {
+            app.adjust_pace(-PACE_STEP);
+        }
+        assert_eq!(app.pace, PACE_MIN);
+        app.adjust_pace(PACE_STEP);
+        assert!((app.pace - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_previous_phase_follows_what_was_on_screen() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.start();
+
+        // Wrapping into a new cycle blends from the last phase
+        for _ in 0..4 {
+            app.skip_phase();
+        }
+        assert_eq!(app.current_phase().name, PhaseName::Inhale);
+        assert_eq!(app.previous_phase(), Some(PhaseName::HoldAfterExhale));
+        let blended = app.get_blended_phase_colors();
+        assert_eq!(blended.primary, app.theme.phase_colors.hold_empty.primary);
+
+        // Stepping back blends from the phase left, not the one before it
+        app.skip_phase();
+        app.skip_phase();
+        app.prev_phase();
+        assert_eq!(app.current_phase().name, PhaseName::Hold);
+        assert_eq!(app.previous_phase(), Some(PhaseName::Exhale));
+    }
+
+    #[test]
+    fn test_circle_eases_in_at_session_start() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        for _ in 0..2 {
+            app.start();
+            // Inhale starts empty, but the circle begins at its resting size
+            assert!((app.breath_scale() - START_RAMP_SCALE).abs() < 0.01);
+
+            for _ in 0..30 {
+                app.tick(1.0 / 60.0);
+            }
+            assert!(app.breath_scale() < START_RAMP_SCALE);
+
+            for _ in 0..60 {
+                app.tick(1.0 / 60.0);
+            }
+            assert!(app.start_ramp > 0.99);
+            app.reset();
+        }
+    }
+
+    #[test]
+    fn test_pause_timeout_ends_the_session() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.pause_timeout = Some(Duration::from_secs(60));
+        app.start();
+        app.toggle_pause();
+        app.tick(0.016);
+        assert_eq!(app.state, AppState::Paused);
+
+        // Nothing done yet: back to the start screen
+        app.paused_at = Some(Instant::now() - Duration::from_secs(61));
+        app.tick(0.016);
+        assert_eq!(app.state, AppState::Ready);
+
+        // A cycle in: straight to the summary
+        app.start();
+        app.cycles_completed = 1;
+        app.toggle_pause();
+        app.paused_at = Some(Instant::now() - Duration::from_secs(61));
+        app.tick(0.016);
+        assert_eq!(app.state, AppState::Complete);
+    }
+
+    #[test]
+    fn test_cycle_limit_is_about_an_hour() {
+        // Wim Hof's 3s cycle allows far more than NSDR's 16s one
+        let fast = App::new_with_technique(get_technique("wim-hof").unwrap(), 30);
+        assert_eq!(fast.cycle_limit(), 1200);
+        let mut slow = App::new_with_technique(get_technique("nsdr").unwrap(), 10);
+        assert_eq!(slow.cycle_limit(), 225);
+        slow.adjust_cycles(1000);
+        assert_eq!(slow.cycles_target, 225);
+
+        slow.max_cycles = Some(20);
+        assert_eq!(slow.cycle_limit(), 20);
+        slow.adjust_cycles(1);
+        assert_eq!(slow.cycles_target, 225);
+        slow.adjust_cycles(-1);
+        assert_eq!(slow.cycles_target, 224);
+    }
+
+    #[test]
+    fn test_large_cycle_steps_stay_within_limits() {
+        let mut app = App::new_with_technique(get_technique("wim-hof").unwrap(), 30);
+        app.max_cycles = Some(99);
+        app.adjust_cycles(10);
+        app.adjust_cycles(-5);
+        assert_eq!(app.cycles_target, 35);
+        for _ in 0..10 {
+            app.adjust_cycles(10);
+        }
+        assert_eq!(app.cycles_target, 99);
+        for _ in 0..20 {
+            app.adjust_cycles(-5);
+        }
+        assert_eq!(app.cycles_target, 1);
+    }
+
+    #[test]
+    fn test_estimated_duration_follows_cycles_and_pace() {
+        // Box: 16s per cycle
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 10);
+        assert_eq!(app.estimated_duration(), Some(Duration::from_secs(160)));
+        app.adjust_cycles(1);
+        assert_eq!(app.estimated_duration(), Some(Duration::from_secs(176)));
+        app.pace = 1.5;
+        assert_eq!(app.estimated_duration(), Some(Duration::from_secs(264)));
+        app.cycles_target = 0;
+        assert_eq!(app.estimated_duration(), None);
+    }
+
+    #[test]
+    fn test_advanced_safety_note_must_be_acknowledged() {
+        let mut app = App::new_with_technique(get_technique("wim-hof").unwrap(), 3);
+        assert!(app.safety_note().is_some());
+        app.start();
+        assert_eq!(app.state, AppState::Ready);
+
+        app.acknowledge_safety();
+        app.start();
+        assert_ne!(app.state, AppState::Ready);
+
+        // Techniques below Advanced (energize is Intermediate) show their note
+        // but don't wait on it
+        let mut app = App::new_with_technique(get_technique("energize").unwrap(), 3);
+        assert!(app.safety_note().is_some());
+        assert!(!app.needs_safety_ack());
+        app.start();
+        assert_ne!(app.state, AppState::Ready);
+    }
+
+    #[test]
+    fn test_breathe_again_skips_the_ready_screen() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.countdown_secs = 0;
+        app.breathe_again();
+        assert_eq!(app.state, AppState::Ready);
+
+        app.start();
+        for _ in 0..4 {
+            app.skip_phase();
+        }
+        assert_eq!(app.state, AppState::Complete);
+
+        app.breathe_again();
+        assert_eq!(app.state, AppState::Breathing);
+        assert_eq!(app.cycles_completed, 0);
+        assert_eq!(app.cycles_target, 1);
+    }
+
+    #[test]
+    fn test_breathing_again_logs_each_session() {
+        let dir = std::env::temp_dir().join(format!("breathe-app-again-{}", std::process::id()));
+        let path = dir.join("history.jsonl");
+
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.countdown_secs = 0;
+        app.history_path = Some(path.clone());
+        app.start();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                app.skip_phase();
+            }
+            assert_eq!(app.state, AppState::Complete);
+            app.breathe_again();
+        }
+
+        assert_eq!(history::read_sessions(&path).unwrap().len(), 2);
+        assert_eq!(app.streak(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sigh_counts_two_breaths_per_cycle() {
+        let mut app = App::new_with_technique(get_technique("sigh").unwrap(), 5);
+        app.start();
+        app.cycles_completed = 3;
+        assert_eq!(app.total_breaths(), 6);
+    }
+
+    #[test]
+    fn test_session_progress_counts_partial_cycles() {
+        // Box: four 4s phases, 32s over two cycles
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 2);
+        app.start();
+        assert!(app.session_progress().unwrap() < 0.01);
+
+        app.cycles_completed = 1;
+        app.current_phase_index = 2;
+        app.phase_start_time = Instant::now() - Duration::from_secs(2);
+        let progress = app.session_progress().unwrap();
+        assert!((progress - 26.0 / 32.0).abs() < 0.01, "{}", progress);
+
+        app.cycles_target = 0;
+        assert_eq!(app.session_progress(), None);
+    }
+
+    #[test]
+    fn test_metronome_peaks_on_whole_seconds() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.start();
+        app.phase_start_time = Instant::now() - Duration::from_secs(2);
+        assert!(app.metronome_pulse() > 0.9);
+
+        app.phase_start_time = Instant::now() - Duration::from_millis(2500);
+        app.toggle_pause();
+        assert!(app.metronome_pulse() < 0.1);
+    }
+
+    #[test]
+    fn test_drone_only_plays_while_breathing() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.toggle_drone();
+        assert!(!app.drone_playing());
+        app.start();
+        assert!(app.drone_playing());
+        app.toggle_pause();
+        assert!(!app.drone_playing());
+        app.toggle_pause();
+        app.toggle_audio();
+        assert!(!app.drone_playing());
+    }
+
+    #[test]
+    fn test_natural_jitter_stays_within_bounds() {
+        for seed in 0..200 {
+            let mut app = App::new_with_technique(get_technique("478").unwrap(), 10);
+            app.natural = true;
+            app.seed = seed;
+            app.start();
+
+            for cycle in 0..10 {
+                app.cycles_completed = cycle;
+                for index in 0..app.current_technique().phases.len() {
+                    app.current_phase_index = index;
+                    let base = app.current_phase().duration_secs;
+                    let duration = app.effective_phase_duration();
+                    assert!(duration >= base * (1.0 - NATURAL_JITTER));
+                    assert!(duration <= base * (1.0 + NATURAL_JITTER));
+                    assert_eq!(duration, app.effective_phase_duration());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_natural_mode_keeps_precise_techniques_exact() {
+        let mut app = App::new_with_technique(get_technique("coherent").unwrap(), 4);
+        app.natural = true;
+        app.start();
+        assert_eq!(app.effective_phase_duration(), app.current_phase().duration_secs);
+    }
+
+    #[test]
+    fn test_manual_taps_are_scored_against_tolerance() {
+        assert_eq!(Pace::judge(1.0, 0.15), Pace::OnTarget);
+        assert_eq!(Pace::judge(0.86, 0.15), Pace::OnTarget);
+        assert_eq!(Pace::judge(0.8, 0.15), Pace::Fast);
+        assert_eq!(Pace::judge(1.2, 0.15), Pace::Slow);
+
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.manual = true;
+        app.start();
+
+        // Tapping immediately is far too fast
+        app.skip_phase();
+        assert_eq!(app.current_phase_index, 1);
+        assert_eq!(app.pace_feedback(), Some(Pace::Fast));
+        assert_eq!(app.pace_score(), Some((0, 1)));
+
+        // Manual mode never advances on its own
+        app.phase_start_time = Instant::now() - Duration::from_secs(10);
+        app.tick(0.016);
+        assert_eq!(app.current_phase_index, 1);
+    }
+
+    #[test]
+    fn test_skip_phase_completes_the_last_cycle() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.start();
+        for _ in 0..4 {
+            app.skip_phase();
+        }
+        assert_eq!(app.cycles_completed, 1);
+        assert_eq!(app.state, AppState::Complete);
+        assert_eq!(app.pace_score(), None);
+    }
+
+    #[test]
+    fn test_search_filters_and_keeps_selection_valid() {
+        let mut app = App::new_interactive();
+        app.start_search();
+        for c in "RES".chars() {
+            app.search_input(c);
+        }
+        let visible = app.filtered_indices();
+        assert!(!visible.is_empty());
+        assert!(visible.iter().all(|&i| {
+            let t = &app.techniques[i];
+            t.name.to_lowercase().contains("res") || t.id.contains("res")
+        }));
+        assert!(visible.contains(&app.selected_index));
+        assert_eq!(app.list_state.selected(), Some(1));
+
+        app.search_input('z');
+        app.search_input('z');
+        assert!(app.filtered_indices().is_empty());
+        assert_eq!(app.list_state.selected(), None);
+        app.confirm_selection();
+        assert_eq!(app.state, AppState::Selecting);
+
+        app.clear_search();
+        assert_eq!(app.filtered_indices().len(), app.techniques.len());
+        let row = app.list_state.selected().unwrap();
+        assert_eq!(app.selector_rows()[row], SelectorRow::Technique(app.selected_index));
+    }
+
+    #[test]
+    fn test_selection_stays_in_view_and_pages() {
+        let mut app = App::new_interactive();
+        let height = 5;
+        app.hit_areas.borrow_mut().list = Rect::new(0, 0, 40, height);
+        let rows = app.selector_rows().len();
+
+        for _ in 0..app.techniques.len() - 1 {
+            app.select_next();
+            app.scroll_selection_into_view(height as usize);
+            let selected = app.list_state.selected().unwrap();
+            let offset = app.list_state.offset();
+            assert!((offset..offset + height as usize).contains(&selected));
+        }
+        assert_eq!(app.list_state.offset(), rows - height as usize);
+
+        // Back at the top, the first heading scrolls into view too
+        app.select_next();
+        app.scroll_selection_into_view(height as usize);
+        assert_eq!(app.list_state.offset(), 0);
+
+        let visible = app.filtered_indices();
+        app.select_page(1);
+        assert_eq!(app.selected_index, visible[height as usize - 1]);
+        app.select_page(1);
+        app.select_page(1);
+        app.select_page(1);
+        app.select_page(1);
+        assert_eq!(app.selected_index, *visible.last().unwrap());
+        app.select_page(-1);
+        assert_eq!(app.selected_index, visible[visible.len() - height as usize]);
+    }
+
+    #[test]
+    fn test_selector_groups_by_category_and_skips_headers() {
+        let mut app = App::new_interactive();
+        let rows = app.selector_rows();
+        assert!(matches!(rows[0], SelectorRow::Header(Category::Focus)));
+        let headers = rows.iter().filter(|r| matches!(r, SelectorRow::Header(_))).count();
+        assert_eq!(headers, all_categories().len());
+
+        // Walking the whole list lands only on techniques and wraps around
+        for _ in 0..app.techniques.len() {
+            app.select_next();
+            let row = app.list_state.selected().unwrap();
+            assert_eq!(rows[row], SelectorRow::Technique(app.selected_index));
+        }
+        assert_eq!(app.selected_index, 0);
+        app.select_prev();
+        assert_eq!(app.selected_index, app.techniques.len() - 1);
+    }
+
+    #[test]
+    fn test_favorites_are_pinned_to_the_top() {
+        let mut app = App::new_interactive();
+        let last = app.filtered_indices()[app.techniques.len() - 1];
+        app.selected_index = last;
+        app.list_state.select(app.selected_row());
+        app.toggle_favorite();
+        assert!(app.is_favorite(last));
+
+        let rows = app.selector_rows();
+        assert_eq!(rows[0], SelectorRow::Favorites);
+        assert_eq!(rows[1], SelectorRow::Technique(last));
+        assert_eq!(rows.iter().filter(|r| **r == SelectorRow::Technique(last)).count(), 1);
+        assert_eq!(app.list_state.selected(), Some(1));
+
+        app.toggle_favorite();
+        assert!(!app.is_favorite(last));
+        assert!(matches!(app.selector_rows()[0], SelectorRow::Header(_)));
+    }
+
+    #[test]
+    fn test_intro_selects_box_and_is_shown_once() {
+        let dir = std::env::temp_dir().join(format!("breathe-app-intro-{}", std::process::id()));
+        let path = dir.join("prefs.toml");
+
+        let mut app = App::new_interactive();
+        app.use_prefs(path.clone());
+        app.select_next();
+        assert!(app.is_first_run());
+        app.begin_intro();
+        assert_eq!(app.selected_technique().id, "box");
+        let row = app.list_state.selected().unwrap();
+        assert_eq!(app.selector_rows()[row], SelectorRow::Technique(app.selected_index));
+        app.dismiss_intro();
+        assert!(!app.show_intro);
+
+        let mut next_run = App::new_interactive();
+        next_run.use_prefs(path);
+        assert!(!next_run.is_first_run());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_session_score_rewards_finishing_steadily() {
+        let clock = ManualClock::new();
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 20)
+            .with_clock(clock.clone());
+        app.countdown_secs = 0;
+        app.start();
+        run_for(&mut app, &clock, 20.0 * 16.0 + 1.0);
+        assert_eq!(app.state, AppState::Complete);
+        assert_eq!(session_score(&app), 100);
+        assert_eq!(score_label(session_score(&app)), "Excellent");
+
+        // Paused twice and stopped at two of five cycles a minute in
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 5)
+            .with_clock(clock.clone());
+        app.countdown_secs = 0;
+        app.start();
+        for _ in 0..2 {
+            app.toggle_pause();
+            app.toggle_pause();
+        }
+        run_for(&mut app, &clock, 2.0 * 16.0 + 1.0);
+        app.finish();
+        // 24 for completion, 5 for steadiness, 2 for the 33 seconds
+        assert_eq!(app.pauses, 2);
+        assert_eq!(session_score(&app), 31);
+        assert_eq!(score_label(31), "Keep practicing");
+    }
+
+    #[test]
+    fn test_endless_runs_until_finished() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.cycles_target = 0;
+        app.adjust_cycles(1);
+        assert!(app.is_endless());
+
+        app.start();
+        for _ in 0..4 * 50 {
+            app.skip_phase();
+        }
+        assert_eq!(app.state, AppState::Breathing);
+        assert_eq!(app.cycles_completed, 50);
+
+        app.skip_phase();
+        app.finish();
+        assert_eq!(app.state, AppState::Complete);
+        assert_eq!(app.total_cycles(), 50);
+        assert!(app.celebration.is_some());
+    }
+
+    #[test]
+    fn test_countdown_holds_the_session_until_it_ends() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.countdown_secs = 3;
+        app.start();
+        assert_eq!(app.state, AppState::Countdown);
+        assert_eq!(app.session_elapsed(), Duration::ZERO);
+        app.tick(0.016);
+        assert_eq!(app.state, AppState::Countdown);
+
+        assert_eq!(app.countdown_number(), Some(3));
+        app.countdown_started = Instant::now() - Duration::from_millis(2500);
+        assert_eq!(app.countdown_number(), None);
+
+        app.countdown_started = Instant::now() - Duration::from_secs(3);
+        app.tick(0.016);
+        assert_eq!(app.state, AppState::Breathing);
+        assert!(app.session_elapsed() < Duration::from_secs(1));
+        assert_eq!(app.current_phase_index, 0);
+    }
+
+    #[test]
+    fn test_prev_phase_steps_back_across_cycles() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 3);
+        app.start();
+
+        // Nothing before the first phase: it just starts over
+        app.prev_phase();
+        assert_eq!((app.cycles_completed, app.current_phase_index), (0, 0));
+
+        for _ in 0..5 {
+            app.skip_phase();
+        }
+        assert_eq!((app.cycles_completed, app.current_phase_index), (1, 1));
+        app.prev_phase();
+        assert_eq!((app.cycles_completed, app.current_phase_index), (1, 0));
+        app.prev_phase();
+        assert_eq!((app.cycles_completed, app.current_phase_index), (0, 3));
+
+        app.phase_start_time = Instant::now() - Duration::from_secs(3);
+        app.restart_phase();
+        assert!(app.phase_elapsed() < 1.0);
+        assert_eq!(app.current_phase_index, 3);
+    }
+
+    #[test]
+    fn test_note_prompt_edits_and_trims() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.start();
+        app.start_note();
+        assert!(!app.editing_note, "notes are only taken on the complete screen");
+
+        for _ in 0..4 {
+            app.skip_phase();
+        }
+        app.start_note();
+        for c in " felt calmerr".chars() {
+            app.note_input(c);
+        }
+        app.note_backspace();
+        app.finish_note();
+        assert_eq!(app.note, "felt calmer");
+
+        app.start_note();
+        app.note_input('!');
+        app.cancel_note();
+        assert!(app.note.is_empty());
+    }
+
+    #[test]
+    fn test_intervals_run_every_segment_for_each_round() {
+        let plan = vec![
+            (get_technique("energize").unwrap(), 2),
+            (get_technique("coherent").unwrap(), 1),
+        ];
+        let mut app = App::new_with_segments(plan, 2);
+        app.start();
+
+        let mut transitions = 0;
+        while app.state != AppState::Complete {
+            if app.state == AppState::Transition {
+                transitions += 1;
+                app.begin_next_segment();
+                continue;
+            }
+            if app.segment_index == 2 {
+                assert_eq!(app.current_round(), 2);
+            }
+            app.skip_phase();
+        }
+
+        assert_eq!(transitions, 3);
+        assert_eq!(app.total_cycles(), 6);
+        let totals = app.technique_totals();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].1, 4);
+        assert_eq!(totals[1].1, 2);
+    }
+
+    #[test]
+    fn test_transition_card_is_not_session_time() {
+        // Two single-cycle box segments (16s each) with the card between
+        let plan = vec![(get_technique("box").unwrap(), 1), (get_technique("box").unwrap(), 1)];
+        let clock = ManualClock::new();
+        let mut app = App::new_with_segments(plan, 1).with_clock(clock.clone());
+        app.countdown_secs = 0;
+        app.start();
+
+        for _ in 0..(40.0 / 0.05) as u32 {
+            run_for(&mut app, &clock, 0.05);
+            if app.state == AppState::Complete {
+                break;
+            }
+        }
+        assert_eq!(app.state, AppState::Complete);
+        assert_eq!(app.session_elapsed().as_secs(), 32);
+    }
+
+    #[test]
+    fn test_sequence_lists_every_segment() {
+        let plan = vec![
+            (get_technique("box").unwrap(), 2),
+            (get_technique("coherent").unwrap(), 1),
+            (get_technique("box").unwrap(), 1),
+        ];
+        let mut app = App::new_with_segments(plan, 1);
+        app.start();
+        while app.state != AppState::Complete {
+            if app.state == AppState::Transition {
+                app.begin_next_segment();
+            }
+            app.skip_phase();
+        }
+
+        let rows: Vec<(&str, u32)> = app.technique_totals().iter().map(|r| (r.0, r.1)).collect();
+        assert_eq!(rows, [("Box Breathing", 2), ("Coherent Breathing", 1), ("Box Breathing", 1)]);
+    }
+
+    #[test]
+    fn test_rounds_end_with_retention_and_rest_between() {
+        let mut app = App::new_with_rounds(get_technique("wim-hof").unwrap(), 3, 3, Some(60.0));
+        app.acknowledge_safety();
+        app.start();
+
+        let mut rests = 0;
+        let mut retentions = 0;
+        while app.state != AppState::Complete {
+            if app.state == AppState::Transition {
+                rests += 1;
+                app.begin_next_segment();
+                assert_eq!(app.current_round(), rests + 1);
+                continue;
+            }
+            let before = app.segment_index;
+            app.skip_phase();
+            // Retention starts straight after the breathing, no rest card
+            if app.segment_index != before && app.segments[app.segment_index].retention {
+                retentions += 1;
+                assert_eq!(app.state, AppState::Breathing);
+                assert_eq!(app.current_phase().name, PhaseName::HoldAfterExhale);
+            }
+        }
+
+        assert_eq!(rests, 2);
+        assert_eq!(retentions, 3);
+        // Retention doesn't inflate the breath count
+        assert_eq!(app.total_cycles(), 9);
+    }
+
+    #[test]
+    fn test_warmup_wraps_the_rounds() {
+        let app = App::new_with_rounds(get_technique("energize").unwrap(), 10, 2, None).with_warmup();
+        let ids: Vec<&str> = app.segments.iter().map(|s| s.technique.id.as_ref()).collect();
+        assert_eq!(ids, ["coherent", "energize", "energize", "recovery"]);
+
+        // The bookends belong to the first and last rounds
+        assert_eq!(app.round_of(0), 1);
+        assert_eq!(app.round_of(1), 1);
+        assert_eq!(app.round_of(2), 2);
+        assert_eq!(app.round_of(3), 2);
+
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 3).with_warmup();
+        app.start();
+        assert_eq!(app.current_technique().id, "coherent");
+        while app.state != AppState::Complete {
+            if app.state == AppState::Transition {
+                app.begin_next_segment();
+            }
+            app.skip_phase();
+        }
+        let totals: Vec<u32> = app.segment_results.iter().map(|r| r.cycles).collect();
+        assert_eq!(totals, [5, 3, 4]);
+    }
+
+    #[test]
+    fn test_duration_rounds_to_whole_cycles() {
+        // Box breathing is 16s per cycle: 5 minutes is 18.75 cycles
+        assert_eq!(cycles_for_duration(&get_technique("box").unwrap(), 5, 1.0), 19);
+        // Never less than one cycle
+        assert_eq!(cycles_for_duration(&get_technique("nsdr").unwrap(), 0, 1.0), 1);
+        assert_eq!(cycles_for_duration(&get_technique("box").unwrap(), 1, 1.0), 4);
+        assert_eq!(cycles_for_duration(&get_technique("box").unwrap(), 4, 2.0), 8);
+    }
+
+    #[test]
+    fn test_holds_are_tracked() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.start();
+
+        // Inhale, then a three-second hold
+        app.skip_phase();
+        app.phase_start_time = Instant::now() - Duration::from_secs(3);
+        app.skip_phase();
+        app.skip_phase();
+        app.skip_phase();
+
+        let (longest, name) = app.longest_hold.clone().unwrap();
+        assert!((3.0..3.5).contains(&longest));
+        assert_eq!(name, "Box Breathing");
+        assert!(app.total_hold_secs >= longest);
+    }
+
+    #[test]
+    fn test_audio_toggle_is_remembered() {
+        let dir = std::env::temp_dir().join(format!("breathe-app-prefs-{}", std::process::id()));
+        let path = dir.join("prefs.toml");
+
+        let mut app = App::new_interactive();
+        app.use_prefs(path.clone());
+        assert!(app.audio_enabled);
+        app.toggle_audio();
+
+        let mut next_run = App::new_interactive();
+        next_run.use_prefs(path);
+        assert!(!next_run.audio_enabled);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_enabling_unavailable_audio_shows_a_notice() {
+        let mut app = App::new_interactive();
+        app.set_audio_status(AudioStatus::Unavailable);
+        assert!(!app.audio_unavailable_notice());
+        app.toggle_audio();
+        assert!(app.audio_enabled);
+        assert!(app.audio_unavailable_notice());
+        app.toggle_audio();
+        assert!(!app.audio_unavailable_notice());
+    }
+
+    #[test]
+    fn test_unavailable_audio_mutes_until_it_turns_up() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 1);
+        app.set_audio_status(AudioStatus::Unavailable);
+        assert!(!app.audio_enabled);
+        app.set_audio_status(AudioStatus::Ready);
+        assert!(app.audio_enabled);
+
+        // A user's explicit choice is left alone
+        app.toggle_audio();
+        app.set_audio_status(AudioStatus::Unavailable);
+        app.set_audio_status(AudioStatus::Ready);
+        assert!(!app.audio_enabled);
+    }
+}
@@ -1,45 +1,192 @@
+use crate::techniques::Category;
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
 use rodio::{OutputStream, Sink, Source};
-use std::sync::mpsc::{self, Sender};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the audio device before treating audio as unavailable
+const AUDIO_INIT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Drone loudness relative to the cue volume
+const DRONE_LEVEL: f32 = 0.3;
+
+/// Metronome tick loudness relative to the cue volume
+const TICK_LEVEL: f32 = 0.3;
+
+/// Tone frequencies (Hz) and loudness for the phase cues
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AudioConfig {
+    pub inhale_hz: f32,
+    pub hold_hz: f32,
+    pub exhale_hz: f32,
+    pub hold_empty_hz: f32,
+    pub start_hz: f32,
+    pub complete_hz: f32,
+    /// Pitch of the optional background drone
+    pub drone_hz: f32,
+    /// Output gain, 0.0 to 1.0
+    pub amplitude: f32,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            inhale_hz: 440.0,      // A4 - start breathing in
+            hold_hz: 523.25,       // C5 - hold
+            exhale_hz: 349.23,     // F4 - breathe out
+            hold_empty_hz: 293.66, // D4 - hold empty
+            start_hz: 523.25,      // C5 - session start
+            complete_hz: 659.25,   // E5 - session complete
+            drone_hz: 110.0,       // A2 - low background hum
+            amplitude: 0.15,       // Quiet, subtle tone
+        }
+    }
+}
+
+/// How a technique's mood colors the cue tones: a pitch shift applied to
+/// the configured frequencies and a loudness relative to the cue volume
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneSet {
+    pub pitch: f32,
+    pub level: f32,
+}
+
+impl ToneSet {
+    /// Lower and softer for winding down, brighter for waking up
+    pub fn for_category(category: Category) -> Self {
+        match category {
+            Category::Focus => Self { pitch: 1.0, level: 1.0 },
+            // Down a minor third
+            Category::Calm => Self { pitch: 0.841, level: 0.85 },
+            // Down a fourth
+            Category::Sleep => Self { pitch: 0.749, level: 0.7 },
+            // Up a major third
+            Category::Energy => Self { pitch: 1.26, level: 1.0 },
+            // Down a whole tone
+            Category::Recovery => Self { pitch: 0.891, level: 0.85 },
+        }
+    }
+}
+
+impl Default for ToneSet {
+    fn default() -> Self {
+        Self { pitch: 1.0, level: 1.0 }
+    }
+}
 
 /// Audio player for breathing cues
 pub struct AudioPlayer {
     sender: Option<Sender<AudioCommand>>,
+    config: AudioConfig,
+    stereo: bool,
+    /// Whether the drone was last told to play
+    drone_on: Cell<bool>,
+    /// Reports once whether the output stream could be opened
+    ready: Receiver<bool>,
+    status: Cell<AudioStatus>,
+    created_at: Instant,
+}
+
+/// Whether the audio output is usable yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioStatus {
+    /// The audio thread is still opening the output device
+    Initializing,
+    Ready,
+    /// No device, or it didn't open within AUDIO_INIT_TIMEOUT
+    Unavailable,
 }
 
 enum AudioCommand {
-    PlayTone { frequency: f32, duration_ms: u64 },
+    /// Notes as (frequency, ms) played back to back. `gain` scales the cue
+    /// volume for these notes only; `pan` spans the whole sequence (-1.0
+    /// left, 1.0 right).
+    PlayNotes { notes: Vec<(f32, u64)>, gain: f32, pan: Option<(f32, f32)> },
+    /// Gain for subsequent tones, 0.0 to 1.0
+    SetVolume(f32),
+    /// Loop a quiet continuous tone until StopDrone
+    StartDrone { frequency: f32 },
+    StopDrone,
     Stop,
 }
 
 impl AudioPlayer {
-    /// Create a new audio player
-    pub fn new() -> Self {
+    /// Create a new audio player with the given tones
+    pub fn new(config: AudioConfig) -> Self {
+        let mut amplitude = config.amplitude;
         let (sender, receiver) = mpsc::channel::<AudioCommand>();
+        let (ready_sender, ready) = mpsc::channel::<bool>();
 
         // Spawn audio thread
         thread::spawn(move || {
             // Try to get audio output
+            // This can block for seconds on misconfigured sound servers, so
+            // the UI learns about readiness through a channel instead
             let output = match OutputStream::try_default() {
                 Ok((stream, handle)) => Some((stream, handle)),
                 Err(_) => None,
             };
+            let _ = ready_sender.send(output.is_some());
 
             if let Some((_stream, handle)) = output {
+                // Panning needs two channels; mono devices get the plain tone
+                let stereo_output = output_channels().is_none_or(|channels| channels >= 2);
+                // The drone plays on its own sink alongside the cue tones
+                let mut drone: Option<Sink> = None;
+
                 while let Ok(cmd) = receiver.recv() {
                     match cmd {
-                        AudioCommand::PlayTone { frequency, duration_ms } => {
+                        AudioCommand::PlayNotes { notes, gain, pan } => {
                             if let Ok(sink) = Sink::try_new(&handle) {
-                                let source = SineWave::new(frequency)
-                                    .take_duration(Duration::from_millis(duration_ms))
-                                    .amplify(0.15)  // Quiet, subtle tone
-                                    .fade_in(Duration::from_millis(20))
-                                    .buffered();
-                                sink.append(source);
+                                let total_ms: u64 = notes.iter().map(|(_, ms)| ms).sum::<u64>().max(1);
+                                let mut start_ms = 0;
+                                for (frequency, duration_ms) in notes {
+                                    let wave = match pan {
+                                        Some((from, to)) if stereo_output => {
+                                            // Each note carries on the sweep where the last left off
+                                            let at = |ms: u64| from + (to - from) * ms as f32 / total_ms as f32;
+                                            let end_ms = start_ms + duration_ms;
+                                            SineWave::panned(frequency, at(start_ms), at(end_ms), duration_ms)
+                                        }
+                                        _ => SineWave::new(frequency),
+                                    };
+                                    sink.append(
+                                        wave.take_duration(Duration::from_millis(duration_ms))
+                                            .amplify(amplitude * gain)
+                                            .fade_in(Duration::from_millis(20)),
+                                    );
+                                    start_ms += duration_ms;
+                                }
                                 sink.sleep_until_end();
                             }
                         }
+                        AudioCommand::SetVolume(volume) => {
+                            amplitude = volume;
+                            if let Some(ref sink) = drone {
+                                sink.set_volume(volume);
+                            }
+                        }
+                        AudioCommand::StartDrone { frequency } => {
+                            if let Ok(sink) = Sink::try_new(&handle) {
+                                sink.set_volume(amplitude);
+                                sink.append(
+                                    SineWave::new(frequency)
+                                        .amplify(DRONE_LEVEL)
+                                        .fade_in(Duration::from_millis(800)),
+                                );
+                                // Replacing the old sink drops (and stops) it
+                                drone = Some(sink);
+                            }
+                        }
+                        AudioCommand::StopDrone => {
+                            if let Some(sink) = drone.take() {
+                                sink.stop();
+                            }
+                        }
                         AudioCommand::Stop => break,
                     }
                 }
@@ -48,34 +195,95 @@ impl AudioPlayer {
 
         Self {
             sender: Some(sender),
+            config,
+            stereo: false,
+            drone_on: Cell::new(false),
+            ready,
+            status: Cell::new(AudioStatus::Initializing),
+            created_at: Instant::now(),
         }
     }
 
-    /// Play a tone for phase transitions
-    pub fn play_phase_tone(&self, phase: PhaseTone) {
+    /// Current audio readiness. Cheap enough to call every frame; a device
+    /// that opens after the timeout still becomes Ready.
+    pub fn status(&self) -> AudioStatus {
+        if self.status.get() != AudioStatus::Ready {
+            match self.ready.try_recv() {
+                Ok(true) => self.status.set(AudioStatus::Ready),
+                Ok(false) | Err(TryRecvError::Disconnected) => {
+                    self.status.set(AudioStatus::Unavailable)
+                }
+                Err(TryRecvError::Empty) => {
+                    if self.created_at.elapsed() >= AUDIO_INIT_TIMEOUT {
+                        self.status.set(AudioStatus::Unavailable);
+                    }
+                }
+            }
+        }
+        self.status.get()
+    }
+
+    /// Change the cue volume (0.0 to 1.0) for the tones that follow
+    pub fn set_volume(&self, volume: f32) {
+        if let Some(ref sender) = self.sender {
+            let _ = sender.send(AudioCommand::SetVolume(volume.clamp(0.0, 1.0)));
+        }
+    }
+
+    /// Start or stop the background drone. Only changes are sent, so this
+    /// can be called every frame.
+    pub fn set_drone(&self, on: bool) {
+        if self.drone_on.get() == on {
+            return;
+        }
+        self.drone_on.set(on);
         if let Some(ref sender) = self.sender {
-            let (frequency, duration_ms) = match phase {
-                PhaseTone::Inhale => (440.0, 150),      // A4 - start breathing in
-                PhaseTone::Hold => (523.25, 100),      // C5 - hold
-                PhaseTone::Exhale => (349.23, 150),    // F4 - breathe out
-                PhaseTone::HoldEmpty => (293.66, 100), // D4 - hold empty
-                PhaseTone::Start => (523.25, 200),     // C5 - session start
-                PhaseTone::Complete => (659.25, 300),  // E5 - session complete
+            let command = if on {
+                AudioCommand::StartDrone { frequency: self.config.drone_hz }
+            } else {
+                AudioCommand::StopDrone
             };
-            let _ = sender.send(AudioCommand::PlayTone { frequency, duration_ms });
+            let _ = sender.send(command);
         }
     }
 
-    /// Check if audio is available
+    /// Enable stereo panning of phase cues
+    pub fn set_stereo(&mut self, stereo: bool) {
+        self.stereo = stereo;
+    }
+
+    /// Play a tone for phase transitions, in the technique's tone set
+    pub fn play_phase_tone(&self, phase: PhaseTone, tone_set: ToneSet) {
+        if let Some(ref sender) = self.sender {
+            let notes = phase_cue_pattern(phase, &self.config)
+                .into_iter()
+                .map(|(frequency, ms)| (frequency * tone_set.pitch, ms))
+                .collect();
+            // Inhale drifts toward the left ear, exhale toward the right
+            let pan = self.stereo.then(|| match phase {
+                PhaseTone::Inhale => (0.0, -0.7),
+                PhaseTone::Exhale => (0.0, 0.7),
+                _ => (0.0, 0.0),
+            });
+            let gain = match phase {
+                PhaseTone::Tick => tone_set.level * TICK_LEVEL,
+                _ => tone_set.level,
+            };
+            let _ = sender.send(AudioCommand::PlayNotes { notes, gain, pan });
+        }
+    }
+
+    /// Whether the output device opened, so tones will actually play
     #[allow(dead_code)]
     pub fn is_available(&self) -> bool {
-        self.sender.is_some()
+        self.status() == AudioStatus::Ready
     }
 }
 
 impl Drop for AudioPlayer {
     fn drop(&mut self) {
         if let Some(sender) = self.sender.take() {
+            let _ = sender.send(AudioCommand::StopDrone);
             let _ = sender.send(AudioCommand::Stop);
         }
     }
@@ -83,7 +291,7 @@ impl Drop for AudioPlayer {
 
 impl Default for AudioPlayer {
     fn default() -> Self {
-        Self::new()
+        Self::new(AudioConfig::default())
     }
 }
 
@@ -96,13 +304,48 @@ pub enum PhaseTone {
     HoldEmpty,
     Start,
     Complete,
+    /// The metronome's quiet once-a-second click
+    Tick,
 }
 
-/// Simple sine wave source
+/// Major third and perfect fifth above a note, for the two-note cues
+const THIRD: f32 = 1.26;
+const FIFTH: f32 = 1.5;
+
+/// The beeps for a cue, as (frequency, ms) pairs, so phases can be told
+/// apart by ear: inhale rises, exhale falls, holds are one long note
+pub fn phase_cue_pattern(phase: PhaseTone, tones: &AudioConfig) -> Vec<(f32, u64)> {
+    match phase {
+        PhaseTone::Inhale => vec![(tones.inhale_hz, 110), (tones.inhale_hz * THIRD, 170)],
+        PhaseTone::Exhale => vec![(tones.exhale_hz * THIRD, 110), (tones.exhale_hz, 170)],
+        PhaseTone::Hold => vec![(tones.hold_hz, 320)],
+        PhaseTone::HoldEmpty => vec![(tones.hold_empty_hz, 320)],
+        PhaseTone::Start => vec![(tones.start_hz, 200)],
+        PhaseTone::Tick => vec![(tones.hold_hz * 2.0, 25)],
+        PhaseTone::Complete => vec![
+            (tones.complete_hz, 140),
+            (tones.complete_hz * THIRD, 140),
+            (tones.complete_hz * FIFTH, 300),
+        ],
+    }
+}
+
+/// Channel count of the default output device, if it can be queried
+pub fn output_channels() -> Option<u16> {
+    let device = rodio::cpal::default_host().default_output_device()?;
+    let config = device.default_output_config().ok()?;
+    Some(config.channels())
+}
+
+/// Simple sine wave source, optionally panned across two channels
 struct SineWave {
     frequency: f32,
     sample_rate: u32,
     sample_index: u64,
+    channels: u16,
+    pan_from: f32,
+    pan_to: f32,
+    pan_frames: u64,
 }
 
 impl SineWave {
@@ -111,18 +354,47 @@ impl SineWave {
             frequency,
             sample_rate: 44100,
             sample_index: 0,
+            channels: 1,
+            pan_from: 0.0,
+            pan_to: 0.0,
+            pan_frames: 1,
         }
     }
+
+    /// Stereo tone whose position sweeps from `from` to `to` over `duration_ms`
+    fn panned(frequency: f32, from: f32, to: f32, duration_ms: u64) -> Self {
+        let mut wave = Self::new(frequency);
+        wave.channels = 2;
+        wave.pan_from = from.clamp(-1.0, 1.0);
+        wave.pan_to = to.clamp(-1.0, 1.0);
+        wave.pan_frames = (wave.sample_rate as u64 * duration_ms / 1000).max(1);
+        wave
+    }
 }
 
 impl Iterator for SineWave {
     type Item = f32;
 
     fn next(&mut self) -> Option<f32> {
-        let t = self.sample_index as f32 / self.sample_rate as f32;
-        let sample = (t * self.frequency * 2.0 * std::f32::consts::PI).sin();
+        let channels = self.channels as u64;
+        let frame = self.sample_index / channels;
+        let channel = self.sample_index % channels;
         self.sample_index += 1;
-        Some(sample)
+
+        // Phase in f64 so long-running tones (the drone) stay clean
+        let cycles = (frame as f64 * self.frequency as f64 / self.sample_rate as f64).fract();
+        let sample = (cycles * std::f64::consts::TAU).sin() as f32;
+
+        if self.channels == 1 {
+            return Some(sample);
+        }
+
+        // Equal-power pan law keeps loudness constant as the tone moves
+        let progress = (frame as f32 / self.pan_frames as f32).min(1.0);
+        let pan = self.pan_from + (self.pan_to - self.pan_from) * progress;
+        let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4;
+        let gain = if channel == 0 { angle.cos() } else { angle.sin() };
+        Some(sample * gain)
     }
 }
 
@@ -132,7 +404,7 @@ impl Source for SineWave {
     }
 
     fn channels(&self) -> u16 {
-        1
+        self.channels
     }
 
     fn sample_rate(&self) -> u32 {
@@ -143,3 +415,21 @@ impl Source for SineWave {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cue_patterns_are_told_apart_by_shape() {
+        let tones = AudioConfig::default();
+        let inhale = phase_cue_pattern(PhaseTone::Inhale, &tones);
+        let exhale = phase_cue_pattern(PhaseTone::Exhale, &tones);
+        let hold = phase_cue_pattern(PhaseTone::Hold, &tones);
+
+        assert!(inhale.len() == 2 && inhale[1].0 > inhale[0].0);
+        assert!(exhale.len() == 2 && exhale[1].0 < exhale[0].0);
+        assert_eq!(hold.len(), 1);
+        assert!(hold[0].1 > inhale.iter().map(|(_, ms)| *ms).max().unwrap());
+    }
+}
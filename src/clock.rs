@@ -0,0 +1,52 @@
+//! Where the session gets the time from
+//!
+//! `App` reads the clock instead of calling `Instant::now()` directly, so
+//! tests can step a session through its phases without sleeping.
+
+use std::time::Instant;
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// The real monotonic clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[cfg(test)]
+pub use manual::ManualClock;
+
+#[cfg(test)]
+mod manual {
+    use super::Clock;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::{Duration, Instant};
+
+    /// A clock that only moves when advanced; clones share the same time
+    #[derive(Clone)]
+    pub struct ManualClock {
+        now: Rc<Cell<Instant>>,
+    }
+
+    impl ManualClock {
+        pub fn new() -> Self {
+            Self { now: Rc::new(Cell::new(Instant::now())) }
+        }
+
+        pub fn advance(&self, by: Duration) {
+            self.now.set(self.now.get() + by);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            self.now.get()
+        }
+    }
+}
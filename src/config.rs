@@ -0,0 +1,144 @@
+//! User preferences from `config.toml` in the breathe config directory
+//!
+//! Every section and key is optional; anything missing keeps its default.
+
+use crate::app::{MAX_FPS, MIN_FPS};
+use crate::audio::AudioConfig;
+use crate::keys::KeyBindings;
+use crate::theme::{PaletteName, ThemeName};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub audio: AudioConfig,
+    pub cues: CueConfig,
+    pub display: DisplayConfig,
+    pub keys: KeyBindings,
+}
+
+/// Phase cues other than the tones
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CueConfig {
+    /// Ring the terminal bell on every phase change (same as --bell)
+    pub bell: bool,
+}
+
+/// Look of the interface
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
+    /// Built-in color theme (same as --theme)
+    pub theme: Option<ThemeName>,
+    /// Phase colors, e.g. a colorblind-friendly palette (same as --palette)
+    pub palette: Option<PaletteName>,
+    /// Frames per second while animating (same as --fps)
+    pub fps: Option<u32>,
+    /// Fewer frames and particles to save power (same as --battery-saver)
+    pub battery_saver: bool,
+}
+
+/// breathe's config directory: `$XDG_CONFIG_HOME/breathe`, falling back
+/// to `~/.config/breathe` on every platform, macOS included
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(base.join("breathe"))
+}
+
+/// Default location of the config file
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+impl Config {
+    /// Parse and validate config TOML
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let config: Config = toml::from_str(text)?;
+        config.audio.validate()?;
+        if let Some(fps) = config.display.fps {
+            if !(MIN_FPS..=MAX_FPS).contains(&fps) {
+                bail!("display.fps must be between {} and {}, got {}", MIN_FPS, MAX_FPS, fps);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load a config file
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("couldn't read {}", path.display()))?;
+        Self::from_toml(&text).with_context(|| format!("invalid config in {}", path.display()))
+    }
+
+    /// The user's config, or defaults when there is none. A broken file is
+    /// reported and ignored rather than stopping the session.
+    pub fn load_or_default() -> Self {
+        let Some(path) = config_path().filter(|path| path.exists()) else {
+            return Self::default();
+        };
+        match Self::load(&path) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("Warning: {:#}. Using the default settings.", err);
+                Self::default()
+            }
+        }
+    }
+}
+
+impl AudioConfig {
+    fn validate(&self) -> Result<()> {
+        let tones = [
+            ("inhale_hz", self.inhale_hz),
+            ("hold_hz", self.hold_hz),
+            ("exhale_hz", self.exhale_hz),
+            ("hold_empty_hz", self.hold_empty_hz),
+            ("start_hz", self.start_hz),
+            ("complete_hz", self.complete_hz),
+            ("drone_hz", self.drone_hz),
+        ];
+        for (name, hz) in tones {
+            if !(20.0..=20_000.0).contains(&hz) {
+                bail!("audio.{} must be between 20 and 20000 Hz, got {}", name, hz);
+            }
+        }
+        if !(0.0..=1.0).contains(&self.amplitude) {
+            bail!("audio.amplitude must be between 0.0 and 1.0, got {}", self.amplitude);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let config = Config::from_toml("[audio]\ninhale_hz = 220.0\namplitude = 0.3\n").unwrap();
+        assert_eq!(config.audio.inhale_hz, 220.0);
+        assert_eq!(config.audio.amplitude, 0.3);
+        assert_eq!(config.audio.exhale_hz, AudioConfig::default().exhale_hz);
+
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+        assert!(Config::from_toml("[audio]\namplitude = 2.0\n").is_err());
+        assert!(Config::from_toml("[audio]\nhold_hz = 0.0\n").is_err());
+        assert!(Config::from_toml("[audoi]\n").is_err());
+        assert!(Config::from_toml("[cues]\nbell = true\n").unwrap().cues.bell);
+        let display = Config::from_toml("[display]\ntheme = \"light\"\n").unwrap().display;
+        assert_eq!(display.theme, Some(ThemeName::Light));
+        assert!(Config::from_toml("[display]\ntheme = \"sepia\"\n").is_err());
+        let display = Config::from_toml("[display]\nfps = 20\nbattery_saver = true\n").unwrap().display;
+        assert_eq!(display.fps, Some(20));
+        assert!(display.battery_saver);
+        assert!(Config::from_toml("[display]\nfps = 500\n").is_err());
+        let display = Config::from_toml("[display]\npalette = \"protanopia\"\n").unwrap().display;
+        assert_eq!(display.palette, Some(PaletteName::Protanopia));
+    }
+}
@@ -0,0 +1,178 @@
+//! Runtime and build diagnostics for bug reports
+//!
+//! Every probe is best-effort: anything that can't be detected is reported
+//! as missing rather than failing the whole report.
+
+use crate::audio;
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+use std::env;
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostics {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub target: Target,
+    pub terminal: TerminalInfo,
+    pub audio: AudioInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Target {
+    pub arch: &'static str,
+    pub os: &'static str,
+    pub family: &'static str,
+    pub profile: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TerminalInfo {
+    pub term: Option<String>,
+    pub program: Option<String>,
+    pub size: Option<(u16, u16)>,
+    pub color: ColorSupport,
+    /// Whether the terminal can be trusted with braille and other symbols
+    pub unicode: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorSupport {
+    /// 24-bit RGB, which the visualizer is designed for
+    Truecolor,
+    /// 256 or fewer colors; RGB gets approximated
+    Limited,
+    /// NO_COLOR is set
+    Disabled,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AudioInfo {
+    pub available: bool,
+    pub host: Option<String>,
+    pub device: Option<String>,
+    pub channels: Option<u16>,
+}
+
+/// Collect everything in one go
+pub fn collect() -> Diagnostics {
+    Diagnostics {
+        name: env!("CARGO_PKG_NAME"),
+        version: env!("CARGO_PKG_VERSION"),
+        target: Target {
+            arch: env::consts::ARCH,
+            os: env::consts::OS,
+            family: env::consts::FAMILY,
+            profile: if cfg!(debug_assertions) { "debug" } else { "release" },
+        },
+        terminal: detect_terminal(),
+        audio: detect_audio(),
+    }
+}
+
+fn non_empty_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+pub fn detect_terminal() -> TerminalInfo {
+    TerminalInfo {
+        term: non_empty_var("TERM"),
+        program: non_empty_var("TERM_PROGRAM"),
+        size: crossterm::terminal::size().ok(),
+        color: detect_color_support(),
+        unicode: detect_unicode_support(),
+    }
+}
+
+/// Best guess at whether the terminal renders Unicode symbols. The Linux
+/// console and old VT terminals don't, and neither does a non-UTF-8 locale.
+pub fn detect_unicode_support() -> bool {
+    if matches!(non_empty_var("TERM").as_deref(), Some("linux" | "dumb" | "vt100" | "vt102" | "vt220")) {
+        return false;
+    }
+    // The first locale variable that is set wins, as in setlocale(3)
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter().find_map(|name| non_empty_var(name));
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    }
+}
+
+pub fn detect_color_support() -> ColorSupport {
+    if non_empty_var("NO_COLOR").is_some() {
+        return ColorSupport::Disabled;
+    }
+    match non_empty_var("COLORTERM").as_deref() {
+        Some("truecolor") | Some("24bit") => ColorSupport::Truecolor,
+        _ => ColorSupport::Limited,
+    }
+}
+
+/// Probe the default output device. Audio backends can panic on broken
+/// setups, so the probe is isolated (with the panic message silenced) and
+/// reports "unavailable" instead.
+pub fn detect_audio() -> AudioInfo {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let probe = std::panic::catch_unwind(|| {
+        let host = rodio::cpal::default_host();
+        let device = host.default_output_device();
+        AudioInfo {
+            available: device.is_some(),
+            host: Some(host.id().name().to_string()),
+            device: device.as_ref().and_then(|d| d.name().ok()),
+            channels: audio::output_channels(),
+        }
+    });
+    std::panic::set_hook(hook);
+
+    probe.unwrap_or(AudioInfo {
+        available: false,
+        host: None,
+        device: None,
+        channels: None,
+    })
+}
+
+/// Human-readable report
+pub fn print_report(diagnostics: &Diagnostics) {
+    let or_unknown = |value: &Option<String>| value.clone().unwrap_or_else(|| "unknown".to_string());
+
+    println!("{} {}", diagnostics.name, diagnostics.version);
+    println!(
+        "  target    {}-{} ({})",
+        diagnostics.target.arch, diagnostics.target.os, diagnostics.target.profile
+    );
+    println!("  terminal  {}", or_unknown(&diagnostics.terminal.term));
+    if let Some((cols, rows)) = diagnostics.terminal.size {
+        println!("  size      {}x{}", cols, rows);
+    }
+    println!("  color     {:?}", diagnostics.terminal.color);
+    println!("  unicode   {}", if diagnostics.terminal.unicode { "yes" } else { "no" });
+    println!(
+        "  audio     {}",
+        if diagnostics.audio.available {
+            or_unknown(&diagnostics.audio.device)
+        } else {
+            "unavailable".to_string()
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_serializes_every_section() {
+        let json = serde_json::to_value(collect()).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        for key in ["target", "terminal", "audio"] {
+            assert!(json.get(key).is_some(), "missing {key}");
+        }
+        assert!(json["audio"]["available"].is_boolean());
+    }
+}
@@ -0,0 +1,555 @@
+//! Session history, one JSON line per completed session
+
+use crate::app::{session_score, App};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A completed session as stored in the history log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Technique id, or "intervals" for an interval session
+    pub technique_id: String,
+    pub cycles_completed: u32,
+    pub duration_secs: u64,
+    pub timestamp: DateTime<Local>,
+    /// Journal note typed on the complete screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Longest breath hold in the session, in seconds, and where it was held
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longest_hold_secs: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longest_hold_technique: Option<String>,
+    #[serde(default)]
+    pub total_hold_secs: f64,
+    /// Focus score, 0-100, from the complete screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<u8>,
+}
+
+impl SessionRecord {
+    /// Record for the session the app just completed
+    pub fn from_app(app: &App) -> Self {
+        let technique_id = if app.segments.is_empty() {
+            app.current_technique().id.to_string()
+        } else {
+            "intervals".to_string()
+        };
+
+        Self {
+            technique_id,
+            cycles_completed: app.total_cycles(),
+            duration_secs: app.session_elapsed().as_secs(),
+            timestamp: Local::now(),
+            note: Some(app.note.clone()).filter(|note| !note.is_empty()),
+            longest_hold_secs: app.longest_hold.as_ref().map(|(secs, _)| *secs),
+            longest_hold_technique: app.longest_hold.as_ref().map(|(_, name)| name.to_string()),
+            total_hold_secs: app.total_hold_secs,
+            score: Some(session_score(app)),
+        }
+    }
+}
+
+/// Default location of the history log
+pub fn history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("breathe").join("history.jsonl"))
+}
+
+/// Append a session to a history log, creating it (and its directory) if needed
+pub fn append_session_to(path: &Path, record: &SessionRecord) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Read every record from a history log, skipping lines that don't parse
+pub fn read_sessions(path: &Path) -> Result<Vec<SessionRecord>> {
+    let file = fs::File::open(path)?;
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(record) = serde_json::from_str(&line?) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+/// File format for `breathe export`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        match text.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!("unknown export format '{}' (expected csv or json)", text),
+        }
+    }
+}
+
+/// Every record in `format`: CSV with a header row, or a JSON array
+pub fn export(records: &[SessionRecord], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(records)? + "\n"),
+        ExportFormat::Csv => {
+            let mut csv = String::from(
+                "timestamp,technique_id,cycles_completed,duration_secs,longest_hold_secs,longest_hold_technique,total_hold_secs,score,note\n",
+            );
+            for r in records {
+                let fields = [
+                    r.timestamp.to_rfc3339(),
+                    r.technique_id.clone(),
+                    r.cycles_completed.to_string(),
+                    r.duration_secs.to_string(),
+                    r.longest_hold_secs.map(|s| s.to_string()).unwrap_or_default(),
+                    r.longest_hold_technique.clone().unwrap_or_default(),
+                    r.total_hold_secs.to_string(),
+                    r.score.map(|s| s.to_string()).unwrap_or_default(),
+                    r.note.clone().unwrap_or_default(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                csv.push_str(&row.join(","));
+                csv.push('\n');
+            }
+            Ok(csv)
+        }
+    }
+}
+
+/// Quote a CSV field if it holds a comma, quote or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Outcome of `breathe import`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub added: usize,
+    /// Already in the history, so left alone
+    pub duplicates: usize,
+    /// Lines or rows that weren't a valid session
+    pub skipped: usize,
+}
+
+/// Sessions from exported or copied history: a JSON array, JSON lines, or
+/// CSV with the export's header row. Also returns how many entries were
+/// malformed.
+pub fn parse_import(text: &str) -> (Vec<SessionRecord>, usize) {
+    let trimmed = text.trim_start();
+    let candidates: Vec<Option<SessionRecord>> = if trimmed.starts_with('[') {
+        match serde_json::from_str::<Vec<serde_json::Value>>(trimmed) {
+            Ok(values) => values.into_iter().map(|v| serde_json::from_value(v).ok()).collect(),
+            Err(_) => vec![None],
+        }
+    } else if trimmed.starts_with("timestamp,") {
+        let mut rows = parse_csv(trimmed).into_iter();
+        let header = rows.next().unwrap_or_default();
+        rows.map(|row| record_from_csv(&header, &row)).collect()
+    } else {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).ok())
+            .collect()
+    };
+
+    let mut records = Vec::new();
+    let mut skipped = 0;
+    for candidate in candidates {
+        match candidate.filter(is_valid) {
+            Some(record) => records.push(record),
+            None => skipped += 1,
+        }
+    }
+    (records, skipped)
+}
+
+/// Append imported sessions to the history log at `path`, skipping any
+/// already there (same time and technique) so importing twice is harmless
+pub fn import_into(path: &Path, text: &str) -> Result<ImportReport> {
+    let existing = if path.exists() { read_sessions(path)? } else { Vec::new() };
+    let mut seen: HashSet<(DateTime<Local>, String)> = existing
+        .into_iter()
+        .map(|r| (r.timestamp, r.technique_id))
+        .collect();
+
+    let (records, skipped) = parse_import(text);
+    let mut report = ImportReport { skipped, ..Default::default() };
+    for record in records {
+        if seen.insert((record.timestamp, record.technique_id.clone())) {
+            append_session_to(path, &record)?;
+            report.added += 1;
+        } else {
+            report.duplicates += 1;
+        }
+    }
+    Ok(report)
+}
+
+fn is_valid(record: &SessionRecord) -> bool {
+    !record.technique_id.trim().is_empty()
+        && record.total_hold_secs.is_finite()
+        && record.total_hold_secs >= 0.0
+        && record.longest_hold_secs.is_none_or(|secs| secs.is_finite() && secs >= 0.0)
+}
+
+/// Rows of CSV fields, honoring quoted fields with commas, doubled quotes
+/// and line breaks inside them
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// A session from a CSV row, looking fields up by the header's names
+fn record_from_csv(header: &[String], row: &[String]) -> Option<SessionRecord> {
+    if row.len() != header.len() {
+        return None;
+    }
+    let field = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .map(|i| row[i].as_str())
+            .filter(|value| !value.is_empty())
+    };
+    Some(SessionRecord {
+        timestamp: DateTime::parse_from_rfc3339(field("timestamp")?).ok()?.with_timezone(&Local),
+        technique_id: field("technique_id")?.to_string(),
+        cycles_completed: field("cycles_completed")?.parse().ok()?,
+        duration_secs: field("duration_secs")?.parse().ok()?,
+        longest_hold_secs: field("longest_hold_secs").map(str::parse).transpose().ok()?,
+        longest_hold_technique: field("longest_hold_technique").map(str::to_string),
+        total_hold_secs: field("total_hold_secs").map_or(Ok(0.0), str::parse).ok()?,
+        score: field("score").map(str::parse).transpose().ok()?,
+        note: field("note").map(str::to_string),
+    })
+}
+
+/// Local dates with at least one completed session
+pub fn practice_days(records: &[SessionRecord]) -> BTreeSet<NaiveDate> {
+    records.iter().map(|r| r.timestamp.date_naive()).collect()
+}
+
+/// Consecutive days of practice counting back from `today`. A streak that
+/// ran through yesterday stays alive until today ends without a session.
+pub fn streak(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> u32 {
+    let mut day = if days.contains(&today) { Some(today) } else { today.pred_opt() };
+    let mut count = 0;
+    while let Some(d) = day.filter(|d| days.contains(d)) {
+        count += 1;
+        day = d.pred_opt();
+    }
+    count
+}
+
+/// Today's streak from the history log at the default location
+pub fn current_streak() -> u32 {
+    let records = history_path()
+        .and_then(|path| read_sessions(&path).ok())
+        .unwrap_or_default();
+    streak(&practice_days(&records), Local::now().date_naive())
+}
+
+/// Minutes breathed in the week (Monday to Sunday) containing `today`
+pub fn minutes_in_week(records: &[SessionRecord], today: NaiveDate) -> u32 {
+    let monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    let secs: u64 = records
+        .iter()
+        .filter(|r| (monday..=today).contains(&r.timestamp.date_naive()))
+        .map(|r| r.duration_secs)
+        .sum();
+    (secs / 60) as u32
+}
+
+/// This week's minutes from the history log at the default location
+pub fn minutes_this_week() -> u32 {
+    let records = history_path()
+        .and_then(|path| read_sessions(&path).ok())
+        .unwrap_or_default();
+    minutes_in_week(&records, Local::now().date_naive())
+}
+
+/// Time and sessions for one technique
+#[derive(Debug, Clone, PartialEq)]
+pub struct TechniqueStats {
+    pub technique_id: String,
+    pub sessions: u32,
+    pub total_secs: u64,
+}
+
+/// Totals across the whole history
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub sessions: u32,
+    pub total_secs: u64,
+    /// Per technique, most time first
+    pub by_technique: Vec<TechniqueStats>,
+    /// Technique practiced in the most sessions
+    pub most_used: Option<String>,
+    /// Longest hold ever practiced, and the technique it was held in
+    pub longest_hold: Option<(f64, String)>,
+    pub total_hold_secs: f64,
+    /// Most recent notes first, with when they were written
+    pub recent_notes: Vec<(DateTime<Local>, String)>,
+}
+
+/// How many notes `breathe stats` shows
+const RECENT_NOTES: usize = 3;
+
+pub fn summarize(records: &[SessionRecord]) -> Stats {
+    let mut by_technique: Vec<TechniqueStats> = Vec::new();
+    let mut longest_hold: Option<(f64, String)> = None;
+    let mut total_hold_secs = 0.0;
+
+    for record in records {
+        match by_technique.iter_mut().find(|t| t.technique_id == record.technique_id) {
+            Some(entry) => {
+                entry.sessions += 1;
+                entry.total_secs += record.duration_secs;
+            }
+            None => by_technique.push(TechniqueStats {
+                technique_id: record.technique_id.clone(),
+                sessions: 1,
+                total_secs: record.duration_secs,
+            }),
+        }
+
+        total_hold_secs += record.total_hold_secs;
+        if let Some(secs) = record.longest_hold_secs {
+            if longest_hold.as_ref().is_none_or(|(longest, _)| secs > *longest) {
+                let technique = record
+                    .longest_hold_technique
+                    .clone()
+                    .unwrap_or_else(|| record.technique_id.clone());
+                longest_hold = Some((secs, technique));
+            }
+        }
+    }
+
+    // Ties go to whichever technique was practiced first
+    let most_used = by_technique
+        .iter()
+        .rev()
+        .max_by_key(|t| t.sessions)
+        .map(|t| t.technique_id.clone());
+    by_technique.sort_by_key(|t| std::cmp::Reverse(t.total_secs));
+
+    let mut notes: Vec<(DateTime<Local>, String)> = records
+        .iter()
+        .filter_map(|r| r.note.clone().map(|note| (r.timestamp, note)))
+        .collect();
+    notes.sort_by_key(|(timestamp, _)| std::cmp::Reverse(*timestamp));
+    notes.truncate(RECENT_NOTES);
+
+    Stats {
+        sessions: records.len() as u32,
+        total_secs: records.iter().map(|r| r.duration_secs).sum(),
+        by_technique,
+        most_used,
+        longest_hold,
+        total_hold_secs,
+        recent_notes: notes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_append_as_json_lines() {
+        let dir = std::env::temp_dir().join(format!("breathe-history-{}", std::process::id()));
+        let path = dir.join("nested").join("history.jsonl");
+
+        let record = SessionRecord {
+            technique_id: "box".to_string(),
+            cycles_completed: 4,
+            duration_secs: 64,
+            timestamp: Local::now(),
+            note: None,
+            longest_hold_secs: Some(4.0),
+            longest_hold_technique: Some("Box Breathing".to_string()),
+            total_hold_secs: 32.0,
+            score: Some(88),
+        };
+        append_session_to(&path, &record).unwrap();
+        append_session_to(&path, &record).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: SessionRecord = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed, record);
+        assert!(!lines[0].contains("note"));
+
+        assert_eq!(read_sessions(&path).unwrap().len(), 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn record(technique_id: &str, duration_secs: u64, hold: Option<f64>) -> SessionRecord {
+        SessionRecord {
+            technique_id: technique_id.to_string(),
+            cycles_completed: 1,
+            duration_secs,
+            timestamp: Local::now(),
+            note: None,
+            longest_hold_secs: hold,
+            longest_hold_technique: hold.map(|_| technique_id.to_string()),
+            total_hold_secs: hold.unwrap_or(0.0),
+            score: None,
+        }
+    }
+
+    #[test]
+    fn test_stats_group_and_sort_by_time() {
+        let mut noted = record("box", 60, Some(4.0));
+        noted.note = Some("calmer".to_string());
+        let records = vec![
+            noted,
+            record("box", 60, Some(4.0)),
+            record("sere", 300, Some(7.0)),
+        ];
+
+        let stats = summarize(&records);
+        assert_eq!(stats.sessions, 3);
+        assert_eq!(stats.total_secs, 420);
+        assert_eq!(stats.most_used.as_deref(), Some("box"));
+        assert_eq!(stats.by_technique[0].technique_id, "sere");
+        assert_eq!(stats.by_technique[1].sessions, 2);
+        assert_eq!(stats.longest_hold, Some((7.0, "sere".to_string())));
+        assert_eq!(stats.total_hold_secs, 15.0);
+        assert_eq!(stats.recent_notes.len(), 1);
+    }
+
+    #[test]
+    fn test_streak_counts_back_from_today_or_yesterday() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let days: BTreeSet<NaiveDate> = [day(1), day(3), day(4), day(5)].into_iter().collect();
+
+        assert_eq!(streak(&days, day(5)), 3);
+        assert_eq!(streak(&days, day(6)), 3);
+        assert_eq!(streak(&days, day(7)), 0);
+        assert_eq!(streak(&days, day(2)), 1);
+        assert_eq!(streak(&BTreeSet::new(), day(5)), 0);
+
+        let mut morning = record("box", 60, None);
+        morning.timestamp = Local::now();
+        let mut evening = morning.clone();
+        evening.technique_id = "478".to_string();
+        assert_eq!(practice_days(&[morning, evening]).len(), 1);
+    }
+
+    #[test]
+    fn test_export_as_csv_and_json() {
+        let mut noted = record("box", 60, Some(4.0));
+        noted.note = Some("slow, \"easy\"".to_string());
+        let records = vec![noted, record("478", 90, None)];
+
+        let csv = export(&records, ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("timestamp,technique_id"));
+        assert!(lines[1].ends_with(",box,1,60,4,box,4,,\"slow, \"\"easy\"\"\""));
+        assert!(lines[2].contains(",478,1,90,,,0,,"));
+
+        let json: Vec<SessionRecord> =
+            serde_json::from_str(&export(&records, ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json, records);
+
+        assert_eq!(export(&[], ExportFormat::Csv).unwrap().lines().count(), 1);
+        assert_eq!(export(&[], ExportFormat::Json).unwrap().trim(), "[]");
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_import_reads_every_export_format_once() {
+        let dir = std::env::temp_dir().join(format!("breathe-import-{}", std::process::id()));
+        let path = dir.join("history.jsonl");
+        let mut noted = record("box", 60, Some(4.0));
+        noted.note = Some("line one,\n\"two\"".to_string());
+        let records = vec![noted, record("478", 90, None)];
+
+        for format in [ExportFormat::Csv, ExportFormat::Json] {
+            let (parsed, skipped) = parse_import(&export(&records, format).unwrap());
+            assert_eq!((parsed, skipped), (records.clone(), 0));
+        }
+
+        let csv = export(&records, ExportFormat::Csv).unwrap();
+        let report = import_into(&path, &csv).unwrap();
+        assert_eq!(report, ImportReport { added: 2, duplicates: 0, skipped: 0 });
+
+        // Re-importing the same sessions as JSON lines adds nothing new
+        let mut lines = String::new();
+        for r in &records {
+            lines.push_str(&serde_json::to_string(r).unwrap());
+            lines.push('\n');
+        }
+        lines.push_str("not a session\n{\"technique_id\": \"\"}\n");
+        let report = import_into(&path, &lines).unwrap();
+        assert_eq!(report, ImportReport { added: 0, duplicates: 2, skipped: 2 });
+        assert_eq!(read_sessions(&path).unwrap(), records);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_week_minutes_start_on_monday() {
+        let on = |date: &str, secs: u64| {
+            let mut r = record("box", secs, None);
+            r.timestamp = format!("{}T12:00:00", date)
+                .parse::<chrono::NaiveDateTime>()
+                .unwrap()
+                .and_local_timezone(Local)
+                .unwrap();
+            r
+        };
+        // 2026-03-02 is a Monday
+        let records = [on("2026-03-01", 600), on("2026-03-02", 300), on("2026-03-04", 150)];
+        let wednesday = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        assert_eq!(minutes_in_week(&records, wednesday), 7);
+        let sunday = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(minutes_in_week(&records, sunday), 10);
+    }
+}
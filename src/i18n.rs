@@ -0,0 +1,228 @@
+//! Interface language: English, plus Spanish as the first translation
+//!
+//! Strings are looked up by their English text, so anything without a
+//! translation yet simply shows in English. Technique names, descriptions
+//! and instructions come from the technique data and aren't translated here.
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+impl Lang {
+    /// Language from a locale like "es_ES.UTF-8", if it's one we speak
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.split(['_', '.', '-', '@']).next()?.to_lowercase();
+        match code.as_str() {
+            "en" => Some(Self::En),
+            "es" => Some(Self::Es),
+            _ => None,
+        }
+    }
+
+    /// Language from the environment, checked in the usual POSIX order
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_locale(&value))
+    }
+}
+
+impl FromStr for Lang {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Self> {
+        Self::from_locale(text)
+            .ok_or_else(|| anyhow::anyhow!("unknown language '{}' (expected en or es)", text))
+    }
+}
+
+/// `key` (English text) in `lang`, or `key` itself when there's no
+/// translation
+pub fn t(key: &'static str, lang: Lang) -> &'static str {
+    translate(key, lang).unwrap_or(key)
+}
+
+/// Translation of English `text`, for strings built at runtime
+pub fn translate(text: &str, lang: Lang) -> Option<&'static str> {
+    match lang {
+        Lang::En => None,
+        Lang::Es => spanish(text),
+    }
+}
+
+fn spanish(text: &str) -> Option<&'static str> {
+    Some(match text {
+        // Phases
+        "INHALE" => "INHALA",
+        "HOLD" => "RETÉN",
+        "EXHALE" => "EXHALA",
+        "REST" => "DESCANSO",
+
+        // Selector
+        "Select a technique" => "Elige una técnica",
+        "No techniques match" => "Ninguna técnica coincide",
+        "FAVORITES" => "FAVORITAS",
+        "This week " => "Esta semana ",
+        "Weekly goal reached" => "Meta semanal cumplida",
+        "day streak" => "días seguidos",
+
+        // Ready screen
+        "Press " => "Pulsa ",
+        " to begin" => " para empezar",
+        " to acknowledge" => " para aceptar",
+        "Not medical advice" => "No es consejo médico",
+
+        // Session and pause
+        "B E G I N" => "E M P I E Z A",
+        "First:" => "Primero:",
+        "Cycle " => "Ciclo ",
+        "PAUSED" => "EN PAUSA",
+        "resume" => "continuar",
+        "restart" => "reiniciar",
+        "back to menu" => "volver al menú",
+        "Round" => "Ronda",
+        "of" => "de",
+        "Retention" => "Retención",
+
+        // Between segments
+        "UP NEXT" => "A CONTINUACIÓN",
+        "Starting in " => "Empieza en ",
+
+        // Complete screen
+        "Session Complete" => "Sesión completada",
+        "Technique" => "Técnica",
+        "Cycles" => "Ciclos",
+        "Breaths" => "Respiraciones",
+        "Duration" => "Duración",
+        "Focus" => "Enfoque",
+        "Excellent" => "Excelente",
+        "Good" => "Bien",
+        "Steady" => "Constante",
+        "Keep practicing" => "Sigue practicando",
+        " to go again" => " para repetir",
+        "Note: " => "Nota: ",
+        "On pace" => "A ritmo",
+
+        // Footer hints
+        "navigate" => "navegar",
+        "select" => "elegir",
+        "clear" => "borrar",
+        "search" => "buscar",
+        "favorite" => "favorita",
+        "guide" => "guía",
+        "help" => "ayuda",
+        "quit" => "salir",
+        "finish" => "terminar",
+        "acknowledge" => "aceptar",
+        "start" => "empezar",
+        "cycles" => "ciclos",
+        "back" => "atrás",
+        "start now" => "empezar ya",
+        "next phase" => "siguiente fase",
+        "pause" => "pausa",
+        "save note" => "guardar nota",
+        "discard" => "descartar",
+        "again" => "otra vez",
+        "techniques" => "técnicas",
+        "note" => "nota",
+        "skip" => "saltar",
+        "previous" => "anterior",
+        "page" => "página",
+        "pace" => "ritmo",
+        "volume" => "volumen",
+        "drone" => "zumbido",
+        "metronome" => "metrónomo",
+        "audio unavailable" => "sin audio",
+        "initializing audio…" => "iniciando audio…",
+
+        // Help overlay
+        "Keyboard Shortcuts" => "Atajos de teclado",
+        "Previous technique" => "Técnica anterior",
+        "Next technique" => "Técnica siguiente",
+        "Select technique" => "Elegir técnica",
+        "Search (ESC clears)" => "Buscar (ESC borra)",
+        "Quit" => "Salir",
+        "Start / Pause / Resume" => "Empezar / Pausa / Continuar",
+        "Shorter / longer phases" => "Fases más cortas / más largas",
+        "Skip to next phase" => "Saltar a la siguiente fase",
+        "Back one phase" => "Volver una fase",
+        "Adjust cycles" => "Ajustar ciclos",
+        "by 10" => "de 10 en 10",
+        "by 5" => "de 5 en 5",
+        "Cue volume" => "Volumen de las señales",
+        "Background drone" => "Zumbido de fondo",
+        "Metronome" => "Metrónomo",
+        "Restart phase" => "Reiniciar fase",
+        "Restart session" => "Reiniciar sesión",
+        "Back to techniques" => "Volver a las técnicas",
+        "Next phase (manual pacing)" => "Siguiente fase (ritmo manual)",
+        "Press any key to close" => "Pulsa cualquier tecla para cerrar",
+
+        // Disclaimer
+        "Before You Begin" => "Antes de empezar",
+        "Not Medical Advice" => "No es consejo médico",
+        "BREATHE is a wellness tool, not medical advice. Technique descriptions and their \
+         cited sources are provided for general information only." => {
+            "BREATHE es una herramienta de bienestar, no un consejo médico. Las descripciones \
+             de las técnicas y sus fuentes se ofrecen solo como información general."
+        }
+        "Stop if you feel dizzy, lightheaded or unwell. Never practice breath holds or \
+         rapid breathing while driving, standing, or in or near water." => {
+            "Para si te mareas o te encuentras mal. Nunca practiques retenciones ni \
+             respiración rápida mientras conduces, de pie, o dentro o cerca del agua."
+        }
+        "If you are pregnant or have a heart, lung or blood-pressure condition, talk to a \
+         doctor before practicing." => {
+            "Si estás embarazada o tienes una afección cardíaca, pulmonar o de tensión \
+             arterial, consulta a un médico antes de practicar."
+        }
+        "Press any key to continue" => "Pulsa cualquier tecla para continuar",
+
+        // First-run intro
+        "Welcome" => "Bienvenida",
+        "Welcome to BREATHE" => "Te damos la bienvenida a BREATHE",
+        "Follow the circle: it grows as you breathe in and shrinks as you breathe out. \
+         Box breathing is selected, a calm place to start: in, hold, out, hold, four seconds each." => {
+            "Sigue el círculo: crece al inhalar y se encoge al exhalar. La respiración \
+             cuadrada está elegida, un comienzo tranquilo: inhala, retén, exhala, retén, \
+             cuatro segundos cada una."
+        }
+        "choose a technique" => "elegir una técnica",
+        "start it" => "empezarla",
+        "pause and resume" => "pausar y continuar",
+        "sound on or off" => "activar o quitar el sonido",
+        "every key" => "todas las teclas",
+        "Press any key to begin" => "Pulsa cualquier tecla para empezar",
+
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages_come_from_flags_and_locales() {
+        assert_eq!(Lang::from_locale("es_ES.UTF-8"), Some(Lang::Es));
+        assert_eq!(Lang::from_locale("en_US"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("C"), None);
+        assert_eq!("ES".parse::<Lang>().unwrap(), Lang::Es);
+        assert!("klingon".parse::<Lang>().is_err());
+    }
+
+    #[test]
+    fn test_missing_translations_fall_back_to_english() {
+        assert_eq!(t("INHALE", Lang::Es), "INHALA");
+        assert_eq!(t("INHALE", Lang::En), "INHALE");
+        assert_eq!(t("Not in the table", Lang::Es), "Not in the table");
+        assert_eq!(translate("quit", Lang::Es), Some("salir"));
+    }
+}
@@ -0,0 +1,351 @@
+//! Key bindings: the named actions the interface responds to and the keys
+//! that trigger them, remappable from the `[keys]` section of `config.toml`
+//!
+//! Each screen lists the actions it accepts; a key triggers the first of
+//! those actions it is bound to, so the same key can mean different things
+//! on different screens (e.g. `n` is next phase while breathing and note
+//! on the summary).
+
+use crate::app::AppState;
+use anyhow::bail;
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Something a key press can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    /// Leave the current screen (Esc)
+    Back,
+    /// Return to the technique menu (b)
+    Menu,
+    Confirm,
+    Up,
+    Down,
+    Search,
+    Guide,
+    Help,
+    Pause,
+    Next,
+    Prev,
+    Restart,
+    Audio,
+    Drone,
+    Metronome,
+    VolumeUp,
+    VolumeDown,
+    PaceDown,
+    PaceUp,
+    CyclesDown,
+    CyclesUp,
+    /// Five cycles at a time (PageDown / PageUp)
+    CyclesDownMore,
+    CyclesUpMore,
+    Note,
+    /// Accept a technique's safety note before starting
+    Acknowledge,
+    /// Star or unstar the selected technique
+    Favorite,
+    /// A screenful up or down the technique list (PageUp / PageDown)
+    PageUp,
+    PageDown,
+    /// Run the finished session again (Enter on the summary)
+    Again,
+}
+
+/// A single key, written in config as a character ("p") or a name
+/// ("space", "enter", "esc", "backspace", "tab", "up", "down", "left",
+/// "right", "pageup", "pagedown")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key(pub KeyCode);
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Self> {
+        let code = match text.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "tab" => KeyCode::Tab,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("unknown key '{}' (use one character or a name like space or enter)", text),
+                }
+            }
+        };
+        Ok(Self(code))
+    }
+}
+
+impl Key {
+    /// How the key is shown on screen, e.g. "SPACE", "↑" or "n"
+    pub fn label(&self) -> String {
+        match self.0 {
+            KeyCode::Char(' ') => "SPACE".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "ENTER".to_string(),
+            KeyCode::Esc => "ESC".to_string(),
+            KeyCode::Backspace => "BKSP".to_string(),
+            KeyCode::Tab => "TAB".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PGUP".to_string(),
+            KeyCode::PageDown => "PGDN".to_string(),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = anyhow::Error;
+
+    fn try_from(text: String) -> anyhow::Result<Self> {
+        text.parse()
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        key.to_string()
+    }
+}
+
+/// Keys for every action; any action left out of the config keeps its
+/// default keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub quit: Vec<Key>,
+    pub back: Vec<Key>,
+    pub menu: Vec<Key>,
+    pub confirm: Vec<Key>,
+    pub up: Vec<Key>,
+    pub down: Vec<Key>,
+    pub search: Vec<Key>,
+    pub guide: Vec<Key>,
+    pub help: Vec<Key>,
+    pub pause: Vec<Key>,
+    pub next: Vec<Key>,
+    pub prev: Vec<Key>,
+    pub restart: Vec<Key>,
+    pub audio: Vec<Key>,
+    pub drone: Vec<Key>,
+    pub metronome: Vec<Key>,
+    pub volume_up: Vec<Key>,
+    pub volume_down: Vec<Key>,
+    pub pace_down: Vec<Key>,
+    pub pace_up: Vec<Key>,
+    pub cycles_down: Vec<Key>,
+    pub cycles_up: Vec<Key>,
+    pub cycles_down_more: Vec<Key>,
+    pub cycles_up_more: Vec<Key>,
+    pub note: Vec<Key>,
+    pub acknowledge: Vec<Key>,
+    pub favorite: Vec<Key>,
+    pub page_up: Vec<Key>,
+    pub page_down: Vec<Key>,
+    pub again: Vec<Key>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = |codes: &[KeyCode]| codes.iter().map(|&code| Key(code)).collect();
+        Self {
+            quit: keys(&[KeyCode::Char('q')]),
+            back: keys(&[KeyCode::Esc]),
+            menu: keys(&[KeyCode::Char('b')]),
+            confirm: keys(&[KeyCode::Enter, KeyCode::Char(' ')]),
+            up: keys(&[KeyCode::Up, KeyCode::Char('k')]),
+            down: keys(&[KeyCode::Down, KeyCode::Char('j')]),
+            search: keys(&[KeyCode::Char('/')]),
+            guide: keys(&[KeyCode::Char('g')]),
+            help: keys(&[KeyCode::Char('?')]),
+            pause: keys(&[KeyCode::Char(' ')]),
+            next: keys(&[KeyCode::Char('n'), KeyCode::Char('.')]),
+            prev: keys(&[KeyCode::Char(',')]),
+            restart: keys(&[KeyCode::Char('r')]),
+            audio: keys(&[KeyCode::Char('a')]),
+            drone: keys(&[KeyCode::Char('d')]),
+            metronome: keys(&[KeyCode::Char('m')]),
+            volume_up: keys(&[KeyCode::Char('+'), KeyCode::Char('=')]),
+            volume_down: keys(&[KeyCode::Char('-')]),
+            pace_down: keys(&[KeyCode::Char('[')]),
+            pace_up: keys(&[KeyCode::Char(']')]),
+            cycles_down: keys(&[KeyCode::Left]),
+            cycles_up: keys(&[KeyCode::Right]),
+            cycles_down_more: keys(&[KeyCode::PageDown]),
+            cycles_up_more: keys(&[KeyCode::PageUp]),
+            note: keys(&[KeyCode::Char('n')]),
+            acknowledge: keys(&[KeyCode::Char('y')]),
+            favorite: keys(&[KeyCode::Char('f')]),
+            page_up: keys(&[KeyCode::PageUp]),
+            page_down: keys(&[KeyCode::PageDown]),
+            again: keys(&[KeyCode::Enter]),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Keys bound to an action
+    pub fn keys(&self, action: Action) -> &[Key] {
+        match action {
+            Action::Quit => &self.quit,
+            Action::Back => &self.back,
+            Action::Menu => &self.menu,
+            Action::Confirm => &self.confirm,
+            Action::Up => &self.up,
+            Action::Down => &self.down,
+            Action::Search => &self.search,
+            Action::Guide => &self.guide,
+            Action::Help => &self.help,
+            Action::Pause => &self.pause,
+            Action::Next => &self.next,
+            Action::Prev => &self.prev,
+            Action::Restart => &self.restart,
+            Action::Audio => &self.audio,
+            Action::Drone => &self.drone,
+            Action::Metronome => &self.metronome,
+            Action::VolumeUp => &self.volume_up,
+            Action::VolumeDown => &self.volume_down,
+            Action::PaceDown => &self.pace_down,
+            Action::PaceUp => &self.pace_up,
+            Action::CyclesDown => &self.cycles_down,
+            Action::CyclesUp => &self.cycles_up,
+            Action::CyclesDownMore => &self.cycles_down_more,
+            Action::CyclesUpMore => &self.cycles_up_more,
+            Action::Note => &self.note,
+            Action::Acknowledge => &self.acknowledge,
+            Action::Favorite => &self.favorite,
+            Action::PageUp => &self.page_up,
+            Action::PageDown => &self.page_down,
+            Action::Again => &self.again,
+        }
+    }
+
+    /// The keys that reach `action` on a screen answering `actions`, as
+    /// shown on screen (e.g. "n/."), or None when no key does
+    pub fn label(&self, action: Action, actions: &[Action]) -> Option<String> {
+        let labels: Vec<String> = self
+            .keys(action)
+            .iter()
+            .filter(|key| self.action(key.0, actions) == Some(action))
+            .map(Key::label)
+            .collect();
+        (!labels.is_empty()).then(|| labels.join("/"))
+    }
+
+    /// The first of `actions` that `code` is bound to
+    pub fn action(&self, code: KeyCode, actions: &[Action]) -> Option<Action> {
+        actions
+            .iter()
+            .copied()
+            .find(|&action| self.keys(action).contains(&Key(code)))
+    }
+}
+
+/// Actions each screen responds to, in priority order
+pub fn state_actions(state: AppState) -> &'static [Action] {
+    use Action::*;
+    match state {
+        AppState::Selecting => &[
+            Quit, Back, Up, Down, PageUp, PageDown, Confirm, Search, Favorite, Guide, Help,
+        ],
+        AppState::Ready => &[
+            Quit, Back, Menu, Confirm, Acknowledge, CyclesDown, CyclesUp,
+            CyclesDownMore, CyclesUpMore, Up, Down, PaceDown, PaceUp, Guide, Audio,
+            VolumeUp, VolumeDown, Help,
+        ],
+        AppState::Countdown => &[Quit, Back, Menu, Confirm],
+        AppState::Breathing => &[
+            Quit, Back, Pause, Confirm, Next, Prev, PaceDown, PaceUp, Restart,
+            Audio, Drone, Metronome, VolumeUp, VolumeDown, Help,
+        ],
+        AppState::Paused => &[Quit, Back, Menu, Pause, Restart, Help],
+        AppState::Transition => &[Quit, Back, Menu, Confirm, Audio, Help],
+        AppState::Complete => &[Quit, Back, Again, Restart, Menu, Note, Help],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_parse_by_character_or_name() {
+        assert_eq!("p".parse::<Key>().unwrap(), Key(KeyCode::Char('p')));
+        assert_eq!("Space".parse::<Key>().unwrap(), Key(KeyCode::Char(' ')));
+        assert_eq!("esc".parse::<Key>().unwrap(), Key(KeyCode::Esc));
+        assert_eq!("PageUp".parse::<Key>().unwrap(), Key(KeyCode::PageUp));
+        assert!("pp".parse::<Key>().is_err());
+        assert!("".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn test_first_listed_action_wins() {
+        let keys = KeyBindings::default();
+        let breathing = [Action::Pause, Action::Confirm, Action::Next];
+        assert_eq!(keys.action(KeyCode::Char(' '), &breathing), Some(Action::Pause));
+        assert_eq!(keys.action(KeyCode::Enter, &breathing), Some(Action::Confirm));
+        assert_eq!(keys.action(KeyCode::Char('n'), &[Action::Note]), Some(Action::Note));
+        assert_eq!(keys.action(KeyCode::Char('z'), &breathing), None);
+    }
+
+    #[test]
+    fn test_labels_follow_the_bindings() {
+        let breathing = state_actions(AppState::Breathing);
+        let keys = KeyBindings::default();
+        assert_eq!(keys.label(Action::Next, breathing).as_deref(), Some("n/."));
+        assert_eq!(keys.label(Action::Pause, breathing).as_deref(), Some("SPACE"));
+        // SPACE pauses before it can confirm
+        assert_eq!(keys.label(Action::Confirm, breathing).as_deref(), Some("ENTER"));
+
+        let keys: KeyBindings = toml::from_str("pause = [\"p\"]\nguide = []\n").unwrap();
+        assert_eq!(keys.label(Action::Pause, breathing).as_deref(), Some("p"));
+        assert_eq!(keys.label(Action::Confirm, breathing).as_deref(), Some("ENTER/SPACE"));
+        assert_eq!(keys.label(Action::Guide, state_actions(AppState::Selecting)), None);
+    }
+
+    #[test]
+    fn test_remapped_keys_replace_the_defaults() {
+        let keys: KeyBindings = toml::from_str("pause = [\"p\"]\n").unwrap();
+        assert_eq!(keys.action(KeyCode::Char('p'), &[Action::Pause]), Some(Action::Pause));
+        assert_eq!(keys.action(KeyCode::Char(' '), &[Action::Pause]), None);
+        assert_eq!(keys.quit, KeyBindings::default().quit);
+        assert!(toml::from_str::<KeyBindings>("pasue = [\"p\"]\n").is_err());
+    }
+}
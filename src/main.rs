@@ -1,24 +1,39 @@
 mod animation;
 mod app;
 mod audio;
+mod clock;
+mod config;
+mod diagnostics;
+mod history;
+mod i18n;
+mod keys;
 mod particles;
+mod prefs;
+mod rng;
 mod techniques;
 mod theme;
 mod ui;
 
-use anyhow::Result;
-use app::{App, AppState};
-use audio::{AudioPlayer, PhaseTone};
-use clap::{Parser, Subcommand};
+use anyhow::{Context, Result};
+use app::{App, AppState, SelectorRow, PACE_STEP, VOLUME_STEP};
+use audio::{AudioPlayer, PhaseTone, ToneSet};
+use clap::{Args, Parser, Subcommand};
+use keys::{state_actions, Action, KeyBindings};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
+    style::Print,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use ratatui::{backend::CrosstermBackend, layout::Size, Terminal};
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use techniques::{all_techniques, get_technique, PhaseName};
+use techniques::{all_techniques, format_secs, get_technique, parse_technique_list, PhaseName};
+use theme::{PaletteName, PhaseColorScheme, Theme, ThemeName};
 
 /// BREATHE - Military-grade breathing techniques in your terminal
 #[derive(Parser)]
@@ -39,6 +54,138 @@ use techniques::{all_techniques, get_technique, PhaseName};
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    #[command(flatten)]
+    options: GlobalOptions,
+}
+
+/// Options that apply to every session, interactive or direct
+#[derive(Args, Clone)]
+struct GlobalOptions {
+    /// Pan cue tones between ears: left on inhale, right on exhale
+    #[arg(long, global = true)]
+    stereo: bool,
+
+    /// Exit after the completion screen has shown for SECS (default 3, 0 = immediately)
+    #[arg(long, global = true, value_name = "SECS", num_args = 0..=1, default_missing_value = "3")]
+    auto_quit: Option<f64>,
+
+    /// End a session left paused for MINUTES: to the summary if any cycles
+    /// were done, otherwise back to the start screen
+    #[arg(long, global = true, value_name = "MINUTES",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    pause_timeout: Option<u64>,
+
+    /// Show the safety disclaimer before starting
+    #[arg(long, global = true)]
+    about: bool,
+
+    /// Replay the intro to the controls shown on first run
+    #[arg(long, global = true)]
+    intro: bool,
+
+    /// Interface language: en or es (default: from $LANG)
+    #[arg(long, global = true, value_name = "LANG")]
+    lang: Option<i18n::Lang>,
+
+    /// Hide the nose/mouth hint next to the phase name
+    #[arg(long, global = true)]
+    no_route_hint: bool,
+
+    /// Vary each phase by up to ±10% so the pacing feels less mechanical
+    #[arg(long, global = true)]
+    natural: bool,
+
+    /// Built-in color theme: dark or light
+    #[arg(long, global = true, value_name = "NAME")]
+    theme: Option<ThemeName>,
+
+    /// Phase colors: default, or deuteranopia, protanopia or tritanopia for
+    /// colorblind-friendly ones
+    #[arg(long, global = true, value_name = "NAME")]
+    palette: Option<PaletteName>,
+
+    /// Load colors from a theme TOML file (see `breathe export-theme`)
+    #[arg(long, global = true, value_name = "PATH")]
+    theme_file: Option<PathBuf>,
+
+    /// No TUI: print a line per phase ("12.0s  INHALE (4s)  Breathe In"),
+    /// for screen readers, logs and scripts
+    #[arg(long, global = true)]
+    text: bool,
+
+    /// No TUI: stream JSON lines for each phase and a final summary, for
+    /// driving other programs (lights, biofeedback) in real time
+    #[arg(long, global = true, conflicts_with = "text")]
+    events: bool,
+
+    /// Compact session layout without the visualizer (automatic below 20 rows)
+    #[arg(long, global = true)]
+    compact: bool,
+
+    /// Keep the display to a fixed COLSxROWS region centered in the terminal
+    #[arg(long, global = true, value_name = "COLSxROWS", value_parser = parse_size)]
+    size: Option<(u16, u16)>,
+
+    /// Dim, red-shifted colors and fewer particles for a dark room
+    #[arg(long, global = true)]
+    night: bool,
+
+    /// Advance each phase yourself with ENTER and get feedback on your pace
+    #[arg(long, global = true)]
+    manual: bool,
+
+    /// How far (in percent) a manual phase may be off target and still count as on pace
+    #[arg(long, global = true, value_name = "PCT", default_value_t = 15.0)]
+    tolerance: f64,
+
+    /// Count in for SECS before the first breath (0 starts immediately)
+    #[arg(long, global = true, value_name = "SECS", default_value_t = 3)]
+    countdown: u32,
+
+    /// Stretch (above 1.0) or shorten every phase, 0.5 to 2.0; adjust with [ and ]
+    #[arg(long, global = true, default_value_t = 1.0)]
+    pace: f64,
+
+    /// Seed for particles and natural-mode timing, for reproducible visuals
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+
+    /// Ring the terminal bell on each phase change (works over SSH without audio)
+    #[arg(long, global = true)]
+    bell: bool,
+
+    /// Play a soft continuous drone while breathing (toggle with d)
+    #[arg(long, global = true)]
+    drone: bool,
+
+    /// Pulse a dot every second, with a quiet tick when audio is on (toggle with m)
+    #[arg(long, global = true)]
+    metronome: bool,
+
+    /// Reduce motion: no particles or ambient animation, just the breathing circle
+    #[arg(long, visible_alias = "reduce-motion", global = true)]
+    calm: bool,
+
+    /// Draw with plain ASCII (automatic on terminals without Unicode)
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Frames per second while animating (default 60)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(app::MIN_FPS as i64..=app::MAX_FPS as i64))]
+    fps: Option<u32>,
+
+    /// Save power on long sessions: about 15fps, fewer particles, slower redraws during holds
+    #[arg(long, global = true)]
+    battery_saver: bool,
+
+    /// Highest cycle count the ready screen allows (default: about an hour's worth)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    max_cycles: Option<u32>,
+
+    /// Most particles on screen at once (default: scaled to the terminal size)
+    #[arg(long, global = true, value_name = "N")]
+    particles: Option<usize>,
 }
 
 #[derive(Subcommand)]
@@ -47,169 +194,543 @@ enum Commands {
     /// Box breathing - Navy SEAL technique (4-4-4-4)
     #[command(visible_alias = "b")]
     Box {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Gateway Process - CIA declassified technique
     Gateway {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Operative Protocol - Field agent standard
     Operative {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// SERE Breathing - Survival training technique
     Sere {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     // === STRESS & CALM ===
     /// Combat breathing - Rapid calm-down
     Combat {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Physiological sigh - Instant calm reset
     #[command(visible_alias = "sigh")]
     PhysiologicalSigh {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Coherent breathing - Heart-brain sync
     Coherent {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Resonant breathing - Vagal tone builder
     Resonant {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     // === SLEEP & RELAXATION ===
     /// Military sleep method - 2-minute sleep technique
     #[command(name = "military-sleep", visible_alias = "sleep")]
     MilitarySleep {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// 4-7-8 breathing - Natural tranquilizer
     #[command(name = "478")]
     FourSevenEight {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Sleep exhale - Extended exhale for sleep
     #[command(name = "sleep-exhale")]
     SleepExhale {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     // === ENERGY & ACTIVATION ===
     /// Energizing breath - Natural energy surge
     Energize {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Power breathing - Pre-mission activation
     Power {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// Wim Hof Method - The Iceman protocol
     #[command(name = "wim-hof", visible_alias = "wh")]
     WimHof {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     // === RECOVERY & HEALING ===
     /// Recovery breathing - Post-stress recovery
     Recovery {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// NSDR breathing - Non-sleep deep rest
     Nsdr {
-        #[arg(short, long)]
-        cycles: Option<u32>,
+        #[command(flatten)]
+        args: SessionArgs,
     },
 
     /// List all available breathing techniques
     #[command(visible_alias = "ls")]
-    List,
+    List {
+        /// Print the full catalog as JSON instead
+        #[arg(long)]
+        json: bool,
+
+        /// Only techniques in this category (focus, calm, sleep, energy or recovery)
+        #[arg(long)]
+        category: Option<String>,
+    },
+
+    /// Run any technique by id, including your own from techniques.toml
+    Run {
+        /// Technique id (see `breathe list`)
+        id: String,
+
+        #[command(flatten)]
+        args: SessionArgs,
+    },
+
+    /// Let breathe pick a technique for you (--seed makes it repeatable)
+    #[command(visible_alias = "rand")]
+    Random {
+        /// Only pick from this category (focus, calm, sleep, energy or recovery)
+        #[arg(long)]
+        category: Option<String>,
+
+        #[command(flatten)]
+        args: SessionArgs,
+    },
+
+    /// Today's technique: one a day, the same for everyone
+    Daily {
+        #[command(flatten)]
+        args: SessionArgs,
+    },
+
+    /// Your favorite technique: the starred one you practised last
+    Fav {
+        #[command(flatten)]
+        args: SessionArgs,
+    },
+
+    /// Print a technique's phase timeline without starting a session
+    Preview {
+        /// Technique id (see `breathe list`)
+        id: String,
+    },
+
+    /// Interval training: alternate techniques for several rounds
+    #[command(after_help = "EXAMPLE:\n    breathe intervals \"energize:10,coherent:5\" --rounds 3")]
+    Intervals {
+        /// Comma-separated technique:cycles list (cycles default to each technique's own)
+        plan: String,
+
+        /// Number of times to repeat the list
+        #[arg(short, long, default_value_t = 1)]
+        rounds: u32,
+    },
+
+    /// Run several techniques back to back, e.g. focus, then calm, then sleep
+    #[command(after_help = "EXAMPLE:\n    breathe sequence box,coherent,478 -c 3")]
+    Sequence {
+        /// Comma-separated technique list (an id:cycles entry keeps its own count)
+        techniques: String,
+
+        /// Cycles of each technique (default: each technique's own)
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+        cycles: Option<u32>,
+    },
+
+    /// Check every technique, including your own, for mistakes
+    #[command(visible_alias = "doctor")]
+    Validate,
+
+    /// Summarize your past sessions
+    Stats,
+
+    /// Export your session history for spreadsheets or scripts
+    #[command(after_help = "EXAMPLE:\n    breathe export --format csv --out breathe.csv")]
+    Export {
+        /// csv or json
+        #[arg(long, default_value = "csv")]
+        format: history::ExportFormat,
+
+        /// Write to this file instead of standard output
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+
+    /// Add sessions from an exported history (CSV, JSON or JSON lines) to yours
+    Import {
+        /// File written by `breathe export`, or another machine's history.jsonl
+        file: PathBuf,
+    },
+
+    /// Set a weekly goal in minutes (0 clears it), or show this week's progress
+    #[command(after_help = "EXAMPLE:\n    breathe goal 70")]
+    Goal {
+        minutes: Option<u32>,
+    },
+
+    /// Show version and build/runtime diagnostics for bug reports
+    Version {
+        /// Print the diagnostics as a JSON object
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the default theme as TOML, a starting point for --theme-file
+    ExportTheme,
+}
+
+/// Parse a `COLSxROWS` size such as `80x24`
+fn parse_size(text: &str) -> std::result::Result<(u16, u16), String> {
+    let (cols, rows) = text
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected COLSxROWS (e.g. 80x24), got '{}'", text))?;
+    let parse = |value: &str| match value.trim().parse::<u16>() {
+        Ok(n) if n >= 10 => Ok(n),
+        _ => Err(format!("invalid size '{}': each dimension must be a number of at least 10", text)),
+    };
+    Ok((parse(cols)?, parse(rows)?))
+}
+
+/// Options shared by every technique subcommand
+#[derive(Args, Clone)]
+struct SessionArgs {
+    /// Number of breathing cycles
+    #[arg(short, long)]
+    cycles: Option<u32>,
+
+    /// Breathe for this many minutes instead of a cycle count
+    #[arg(short, long, value_name = "MINUTES", conflicts_with = "cycles",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    duration: Option<u64>,
+
+    /// Keep breathing until you stop with q or ESC
+    #[arg(long, conflicts_with_all = ["cycles", "duration", "rounds", "retention"])]
+    endless: bool,
+
+    /// Override the phase durations, e.g. 5-5-5-5 for a slower box
+    #[arg(long, value_name = "SECS-SECS-...")]
+    pattern: Option<String>,
+
+    /// Begin the first cycle at this phase (1-based) to drill the hard part
+    #[arg(long, value_name = "N")]
+    from_phase: Option<usize>,
+
+    /// Practice only this phase of the technique (inhale, hold, exhale or
+    /// hold-after-exhale), e.g. just the long exhales
+    #[arg(long, value_name = "PHASE", conflicts_with = "from_phase")]
+    only: Option<techniques::PhaseName>,
+
+    /// Repeat the cycles this many times, resting between rounds
+    #[arg(short, long, default_value_t = 1)]
+    rounds: u32,
+
+    /// Retention hold at the end of each round, in seconds (0 = none;
+    /// Wim Hof defaults to 60 when practicing in rounds)
+    #[arg(long, value_name = "SECS")]
+    retention: Option<f64>,
+
+    /// Ease in with a few calm cycles first and recover with a few after
+    #[arg(long, conflicts_with_all = ["endless", "from_phase"])]
+    warmup: bool,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // User techniques join the built-in ones everywhere, so load them first
+    if let Some(path) = techniques::user_techniques_path().filter(|path| path.exists()) {
+        techniques::install_user_techniques(techniques::load_user_techniques(&path)?);
+    }
+
     match cli.command {
-        Some(Commands::List) => {
-            print_techniques_list();
+        Some(Commands::List { json, category }) => {
+            let techniques = match category {
+                Some(name) => techniques::get_techniques_by_category(name.parse()?),
+                None => all_techniques().iter().collect(),
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&techniques)?);
+            } else {
+                print_techniques_list(&techniques);
+            }
+            Ok(())
+        }
+        Some(Commands::Intervals { plan, rounds }) => {
+            let plan = parse_technique_list(&plan, None).map_err(anyhow::Error::msg)?;
+            if rounds == 0 {
+                anyhow::bail!("--rounds must be at least 1");
+            }
+            run_session(App::new_with_segments(plan, rounds), &cli.options)
+        }
+        Some(Commands::Sequence { techniques, cycles }) => {
+            let plan = parse_technique_list(&techniques, cycles).map_err(anyhow::Error::msg)?;
+            run_session(App::new_with_segments(plan, 1), &cli.options)
+        }
+        Some(Commands::Run { id, args }) => {
+            let technique = get_technique(&id)
+                .ok_or_else(|| anyhow::anyhow!("unknown technique '{}' (see `breathe list`)", id))?;
+            run_technique_command(technique, &args, &cli.options)
+        }
+        Some(Commands::Random { category, args }) => {
+            let category = category.map(|name| name.parse()).transpose()?;
+            let seed = cli.options.seed.unwrap_or_else(rng::time_seed);
+            let technique = techniques::random_technique(category, seed)
+                .ok_or_else(|| anyhow::anyhow!("no techniques to pick from"))?;
+            run_technique_command(technique, &args, &cli.options)
+        }
+        Some(Commands::Daily { args }) => {
+            let technique = techniques::daily_technique(chrono::Local::now().date_naive());
+            println!("Technique of the day: {} - {}", technique.name, technique.tagline);
+            run_technique_command(technique, &args, &cli.options)
+        }
+        Some(Commands::Fav { args }) => {
+            let prefs = prefs::prefs_path()
+                .map(|path| prefs::Prefs::load_from(&path))
+                .unwrap_or_default();
+            let records = history::history_path()
+                .and_then(|path| history::read_sessions(&path).ok())
+                .unwrap_or_default();
+            let id = prefs
+                .latest_favorite(&records)
+                .ok_or_else(|| anyhow::anyhow!("no favorites yet (press f in the technique list to star one)"))?;
+            let technique = get_technique(id)
+                .ok_or_else(|| anyhow::anyhow!("favorite '{}' is no longer a technique (see `breathe list`)", id))?;
+            run_technique_command(technique, &args, &cli.options)
+        }
+        Some(Commands::Preview { id }) => {
+            let technique = get_technique(&id)
+                .ok_or_else(|| anyhow::anyhow!("unknown technique '{}' (see `breathe list`)", id))?;
+            print_preview(&technique);
+            Ok(())
+        }
+        Some(Commands::Validate) => {
+            let results = techniques::validate_techniques(all_techniques());
+            print_validation(&results);
+            let failed = results.iter().filter(|(_, problems)| !problems.is_empty()).count();
+            if failed > 0 {
+                anyhow::bail!("{} of {} techniques have problems", failed, results.len());
+            }
+            Ok(())
+        }
+        Some(Commands::Stats) => {
+            print_stats();
+            Ok(())
+        }
+        Some(Commands::Export { format, out }) => {
+            let records = history::history_path()
+                .and_then(|path| history::read_sessions(&path).ok())
+                .unwrap_or_default();
+            if records.is_empty() {
+                eprintln!("No sessions recorded yet; exporting an empty history.");
+            }
+            let text = history::export(&records, format)?;
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, text)?;
+                    eprintln!("Exported {} sessions to {}", records.len(), path.display());
+                }
+                None => print!("{}", text),
+            }
+            Ok(())
+        }
+        Some(Commands::Import { file }) => {
+            let text = std::fs::read_to_string(&file)
+                .with_context(|| format!("couldn't read {}", file.display()))?;
+            let path = history::history_path().ok_or_else(|| anyhow::anyhow!("no data directory"))?;
+            let report = history::import_into(&path, &text)?;
+            println!(
+                "Imported {} sessions ({} already in your history)",
+                report.added, report.duplicates
+            );
+            if report.skipped > 0 {
+                eprintln!("warning: skipped {} malformed entries", report.skipped);
+            }
+            Ok(())
+        }
+        Some(Commands::Goal { minutes }) => {
+            let path = prefs::prefs_path().ok_or_else(|| anyhow::anyhow!("no config directory"))?;
+            let mut prefs = prefs::Prefs::load_from(&path);
+            if let Some(minutes) = minutes {
+                prefs.weekly_goal_minutes = Some(minutes).filter(|&m| m > 0);
+                prefs.save_to(&path)?;
+            }
+            print_goal(prefs.weekly_goal_minutes, history::minutes_this_week());
+            Ok(())
+        }
+        Some(Commands::Version { json }) => {
+            let report = diagnostics::collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                diagnostics::print_report(&report);
+            }
+            Ok(())
+        }
+        Some(Commands::ExportTheme) => {
+            print!("{}", Theme::dark().to_toml());
             Ok(())
         }
         Some(cmd) => {
-            let (technique_id, cycles) = match cmd {
+            let (technique_id, args) = match cmd {
                 // Focus & Performance
-                Commands::Box { cycles } => ("box", cycles),
-                Commands::Gateway { cycles } => ("gateway", cycles),
-                Commands::Operative { cycles } => ("operative", cycles),
-                Commands::Sere { cycles } => ("sere", cycles),
+                Commands::Box { args } => ("box", args),
+                Commands::Gateway { args } => ("gateway", args),
+                Commands::Operative { args } => ("operative", args),
+                Commands::Sere { args } => ("sere", args),
                 // Stress & Calm
-                Commands::Combat { cycles } => ("combat", cycles),
-                Commands::PhysiologicalSigh { cycles } => ("physiological-sigh", cycles),
-                Commands::Coherent { cycles } => ("coherent", cycles),
-                Commands::Resonant { cycles } => ("resonant", cycles),
+                Commands::Combat { args } => ("combat", args),
+                Commands::PhysiologicalSigh { args } => ("sigh", args),
+                Commands::Coherent { args } => ("coherent", args),
+                Commands::Resonant { args } => ("resonant", args),
                 // Sleep & Relaxation
-                Commands::MilitarySleep { cycles } => ("military-sleep", cycles),
-                Commands::FourSevenEight { cycles } => ("478", cycles),
-                Commands::SleepExhale { cycles } => ("sleep-exhale", cycles),
+                Commands::MilitarySleep { args } => ("military-sleep", args),
+                Commands::FourSevenEight { args } => ("478", args),
+                Commands::SleepExhale { args } => ("sleep-exhale", args),
                 // Energy & Activation
-                Commands::Energize { cycles } => ("energize", cycles),
-                Commands::Power { cycles } => ("power", cycles),
-                Commands::WimHof { cycles } => ("wim-hof", cycles),
+                Commands::Energize { args } => ("energize", args),
+                Commands::Power { args } => ("power", args),
+                Commands::WimHof { args } => ("wim-hof", args),
                 // Recovery & Healing
-                Commands::Recovery { cycles } => ("recovery", cycles),
-                Commands::Nsdr { cycles } => ("nsdr", cycles),
-                Commands::List => unreachable!(),
+                Commands::Recovery { args } => ("recovery", args),
+                Commands::Nsdr { args } => ("nsdr", args),
+                Commands::List { .. }
+                | Commands::Run { .. }
+                | Commands::Random { .. }
+                | Commands::Daily { .. }
+                | Commands::Fav { .. }
+                | Commands::Preview { .. }
+                | Commands::Intervals { .. }
+                | Commands::Sequence { .. }
+                | Commands::Validate
+                | Commands::Stats
+                | Commands::Goal { .. }
+                | Commands::Export { .. }
+                | Commands::Import { .. }
+                | Commands::Version { .. }
+                | Commands::ExportTheme => unreachable!(),
             };
 
             let technique = get_technique(technique_id)
                 .expect("Unknown technique");
-            let cycle_count = cycles.unwrap_or(technique.default_cycles);
-
-            run_with_technique(technique, cycle_count)
+            run_technique_command(technique, &args, &cli.options)
         }
         None => {
             // Interactive mode - show technique selector
-            run_interactive()
+            run_interactive(&cli.options)
         }
     }
 }
 
-fn print_techniques_list() {
+/// Validate a technique's session options and run it
+fn run_technique_command(
+    technique: techniques::Technique,
+    args: &SessionArgs,
+    options: &GlobalOptions,
+) -> Result<()> {
+    let technique = match &args.pattern {
+        Some(pattern) => technique.with_pattern(pattern)?,
+        None => technique,
+    };
+    let technique = match args.only {
+        Some(phase) => technique.only_phase(phase)?,
+        None => technique,
+    };
+
+    let cycle_count = match (args.cycles, args.duration) {
+        (_, Some(minutes)) => app::cycles_for_duration(&technique, minutes, options.pace),
+        (Some(cycles), None) => cycles,
+        (None, None) => technique.default_cycles,
+    };
+
+    // --from-phase is 1-based to match the numbering in the guide
+    let start_phase = match args.from_phase {
+        Some(n) if n == 0 || n > technique.phases.len() => {
+            anyhow::bail!(
+                "--from-phase must be between 1 and {} for {}",
+                technique.phases.len(),
+                technique.name
+            );
+        }
+        Some(n) => n - 1,
+        None => 0,
+    };
+
+    if args.rounds == 0 {
+        anyhow::bail!("--rounds must be at least 1");
+    }
+    let retention = match args.retention {
+        Some(secs) if !secs.is_finite() || secs < 0.0 => {
+            anyhow::bail!("--retention must be zero or more seconds");
+        }
+        Some(secs) => Some(secs).filter(|secs| *secs > 0.0),
+        None if args.rounds > 1 => technique.default_retention(),
+        None => None,
+    };
+
+    if args.endless {
+        let mut app = App::new_with_technique(technique, cycle_count);
+        app.cycles_target = 0;
+        app.start_phase_index = start_phase;
+        return run_session(app, options);
+    }
+    if args.rounds > 1 || retention.is_some() || args.warmup {
+        let mut app = App::new_with_rounds(technique, cycle_count, args.rounds, retention);
+        if args.warmup {
+            app = app.with_warmup();
+        }
+        app.start_phase_index = start_phase;
+        return run_session(app, options);
+    }
+    run_with_technique(technique, cycle_count, start_phase, options)
+}
+
+fn print_techniques_list(techniques: &[&techniques::Technique]) {
     println!();
     println!("  \x1b[1;38;5;75m◉ BREATHE\x1b[0m - Available Techniques");
     println!("  \x1b[38;5;240m─────────────────────────────────────────\x1b[0m");
     println!();
 
-    for technique in all_techniques() {
+    let builtin = techniques::builtin_techniques();
+    for technique in techniques {
         let tc = technique.color;
+        // User techniques have no subcommand of their own
+        let command = if builtin.iter().any(|t| t.id == technique.id) {
+            format!("breathe {}", technique.id)
+        } else {
+            format!("breathe run {}", technique.id)
+        };
         println!(
             "  \x1b[38;2;{};{};{}m●\x1b[0m \x1b[1m{:<20}\x1b[0m \x1b[38;5;245m{}\x1b[0m",
             tc.r, tc.g, tc.b,
@@ -220,10 +741,7 @@ fn print_techniques_list() {
             "    \x1b[38;5;240m{}\x1b[0m",
             technique.description
         );
-        println!(
-            "    \x1b[38;5;75mbreathe {}\x1b[0m",
-            technique.id
-        );
+        println!("    \x1b[38;5;75m{}\x1b[0m", command);
         println!();
     }
 
@@ -233,43 +751,294 @@ fn print_techniques_list() {
     println!();
 }
 
-fn run_interactive() -> Result<()> {
-    // Initialize audio
-    let audio = AudioPlayer::new();
+/// Display name for a technique id from the history log
+fn technique_display_name(id: &str) -> String {
+    match id {
+        "intervals" => "Interval training".to_string(),
+        _ => get_technique(id).map_or_else(|| id.to_string(), |t| t.name.to_string()),
+    }
+}
 
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+fn print_preview(technique: &techniques::Technique) {
+    let tc = technique.color;
+    println!();
+    println!(
+        "  \x1b[38;2;{};{};{}m●\x1b[0m \x1b[1m{}\x1b[0m \x1b[38;5;245m{}\x1b[0m",
+        tc.r, tc.g, tc.b, technique.name, technique.pattern
+    );
+    println!();
+    for (start, end, phase) in technique.timeline() {
+        let span = format!("{}–{}s", format_secs(start), format_secs(end));
+        println!(
+            "    {:>10}  \x1b[1m{:<8}\x1b[0m \x1b[38;5;245m{}\x1b[0m",
+            span,
+            phase.name.display(),
+            phase.instruction
+        );
+    }
+    println!();
+    let cycle = technique.cycle_duration();
+    println!(
+        "  One cycle: {}s · {:.1} breaths per minute",
+        format_secs(cycle),
+        60.0 / cycle
+    );
+    println!();
+}
 
-    // Create app in interactive mode
-    let mut app = App::new_interactive();
+fn print_validation(results: &[(&str, Vec<String>)]) {
+    println!();
+    for (id, problems) in results {
+        if problems.is_empty() {
+            println!("  \x1b[38;5;82m✓\x1b[0m {}", id);
+        } else {
+            println!("  \x1b[38;5;203m✗\x1b[0m \x1b[1m{}\x1b[0m", id);
+            for problem in problems {
+                println!("      \x1b[38;5;245m{}\x1b[0m", problem);
+            }
+        }
+    }
+    println!();
+}
 
-    // Run the main loop
-    let result = run_loop(&mut terminal, &mut app, &audio);
+fn print_goal(goal: Option<u32>, minutes: u32) {
+    println!();
+    match goal {
+        None => println!(
+            "  {} minutes breathed this week · \x1b[38;5;245mno weekly goal (set one with `breathe goal 70`)\x1b[0m",
+            minutes
+        ),
+        Some(goal) if minutes >= goal => println!(
+            "  \x1b[1;38;5;114m✓ Weekly goal reached:\x1b[0m {} of {} minutes",
+            minutes, goal
+        ),
+        Some(goal) => println!(
+            "  \x1b[1m{}\x1b[0m of {} minutes this week · \x1b[38;5;245m{} to go\x1b[0m",
+            minutes,
+            goal,
+            goal - minutes
+        ),
+    }
+    println!();
+}
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+fn print_stats() {
+    let records = history::history_path()
+        .and_then(|path| history::read_sessions(&path).ok())
+        .unwrap_or_default();
+    if records.is_empty() {
+        println!();
+        println!("  \x1b[38;5;245mNo sessions recorded yet.\x1b[0m");
+        println!();
+        return;
+    }
+    let stats = history::summarize(&records);
 
-    // Print session summary if completed
-    if app.state == AppState::Complete {
-        print_session_summary(&app);
+    println!();
+    println!("  \x1b[1;38;5;75m◉ BREATHE\x1b[0m - Your Practice");
+    println!("  \x1b[38;5;240m─────────────────────────────────────────\x1b[0m");
+    println!();
+    println!(
+        "  \x1b[1m{}\x1b[0m sessions · \x1b[1m{}\x1b[0m minutes breathed",
+        stats.sessions,
+        stats.total_secs / 60
+    );
+    if let Some(id) = &stats.most_used {
+        println!("  \x1b[38;5;245mMost used:\x1b[0m {}", technique_display_name(id));
+    }
+    if let Some((secs, technique)) = &stats.longest_hold {
+        println!(
+            "  \x1b[38;5;245mYour longest practiced hold:\x1b[0m {:.0}s in {} · {:.0}s held in total",
+            secs, technique, stats.total_hold_secs
+        );
     }
+    println!();
 
-    result
+    for entry in &stats.by_technique {
+        let color = get_technique(&entry.technique_id).map_or((100, 116, 139), |t| {
+            (t.color.r, t.color.g, t.color.b)
+        });
+        println!(
+            "  \x1b[38;2;{};{};{}m●\x1b[0m \x1b[1m{:<22}\x1b[0m \x1b[38;5;245m{} session{} · {}\x1b[0m",
+            color.0, color.1, color.2,
+            technique_display_name(&entry.technique_id),
+            entry.sessions,
+            if entry.sessions == 1 { "" } else { "s" },
+            App::format_time(Duration::from_secs(entry.total_secs))
+        );
+    }
+
+    if !stats.recent_notes.is_empty() {
+        println!();
+        println!("  \x1b[38;5;245mRecent notes:\x1b[0m");
+        for (timestamp, note) in &stats.recent_notes {
+            println!(
+                "    \x1b[38;5;240m{}\x1b[0m  {}",
+                timestamp.format("%b %e"),
+                note
+            );
+        }
+    }
+
+    println!();
+    println!("  \x1b[38;5;240m─────────────────────────────────────────\x1b[0m");
+    println!();
+}
+
+fn run_interactive(options: &GlobalOptions) -> Result<()> {
+    // Create app in interactive mode
+    let app = App::new_interactive();
+
+    run_session(app, options)
+}
+
+fn run_with_technique(
+    technique: techniques::Technique,
+    cycles: u32,
+    start_phase: usize,
+    options: &GlobalOptions,
+) -> Result<()> {
+    // Create app with specific technique
+    let mut app = App::new_with_technique(technique, cycles);
+    app.start_phase_index = start_phase;
+
+    run_session(app, options)
 }
 
-fn run_with_technique(technique: techniques::Technique, cycles: u32) -> Result<()> {
+/// Set up audio and the terminal, run the main loop, then restore the terminal
+fn run_session(mut app: App, options: &GlobalOptions) -> Result<()> {
+    app.auto_quit_after = options.auto_quit;
+    app.pause_timeout = options.pause_timeout.map(|mins| Duration::from_secs(mins * 60));
+    app.show_disclaimer = options.about || first_run_disclaimer();
+    app.show_route_hint = !options.no_route_hint;
+    app.lang = options.lang.or_else(i18n::Lang::from_env).unwrap_or_default();
+    app.natural = options.natural;
+    app.manual = options.manual;
+
+    if !(options.tolerance > 0.0 && options.tolerance < 100.0) {
+        anyhow::bail!("--tolerance must be between 0 and 100 (got {})", options.tolerance);
+    }
+    app.pace_tolerance = options.tolerance / 100.0;
+
+    if !(app::PACE_MIN..=app::PACE_MAX).contains(&options.pace) {
+        anyhow::bail!(
+            "--pace must be between {} and {} (got {})",
+            app::PACE_MIN,
+            app::PACE_MAX,
+            options.pace
+        );
+    }
+    app.pace = options.pace;
+    app.countdown_secs = options.countdown;
+    app.max_cycles = options.max_cycles;
+
+    if let Some((cols, rows)) = options.size {
+        if let Ok((width, height)) = crossterm::terminal::size() {
+            if cols > width || rows > height {
+                eprintln!(
+                    "Warning: --size {}x{} doesn't fit the {}x{} terminal; it will be clamped.",
+                    cols, rows, width, height
+                );
+            }
+        }
+        app.fixed_size = Some((cols, rows));
+    }
+
+    // Config is read early since it can pick the theme; NO_COLOR keeps the
+    // monochrome one regardless
+    let config = config::Config::load_or_default();
+    if let Some(name) = options.theme.or(config.display.theme) {
+        if !theme::no_color() {
+            app.theme = Theme::named(name);
+        }
+    }
+
+    if let Some(path) = &options.theme_file {
+        match Theme::load(path) {
+            Ok(theme) => app.theme = theme,
+            Err(err) => eprintln!("Warning: {:#}. Using the default theme.", err),
+        }
+    }
+    if let Some(palette) = options.palette.or(config.display.palette) {
+        if !theme::no_color() && palette != PaletteName::Default {
+            app.theme.phase_colors = PhaseColorScheme::named(palette);
+        }
+    }
+
+    if options.natural {
+        if let Some(technique) = app.technique.as_ref().filter(|t| t.precise_timing()) {
+            eprintln!(
+                "Note: --natural is ignored for {}, which relies on exact pacing.",
+                technique.name
+            );
+        }
+    }
+
+    if options.night {
+        app.enable_night_mode();
+    }
+    if options.calm {
+        app.enable_reduced_motion();
+    }
+    if options.battery_saver || config.display.battery_saver {
+        app.enable_battery_saver();
+    }
+    if let Some(fps) = options.fps.or(config.display.fps) {
+        app.set_fps(fps);
+    }
+    if let Some(count) = options.particles {
+        app.set_particle_override(count);
+    }
+    if let Some(seed) = options.seed {
+        app.set_seed(seed);
+    }
+
+    app.use_ascii = options.ascii || !diagnostics::detect_unicode_support();
+    if let Some(path) = prefs::prefs_path() {
+        app.use_prefs(path);
+    }
+    app.history_path = history::history_path();
+    let records = app
+        .history_path
+        .as_ref()
+        .and_then(|path| history::read_sessions(path).ok())
+        .unwrap_or_default();
+    app.practice_days = history::practice_days(&records);
+    app.week_minutes = history::minutes_in_week(&records, chrono::Local::now().date_naive());
+    if options.intro || app.is_first_run() {
+        app.begin_intro();
+    }
+
     // Initialize audio
-    let audio = AudioPlayer::new();
+    let mut audio = AudioPlayer::new(config.audio);
+    app.volume = config.audio.amplitude;
+    app.drone = options.drone;
+    app.metronome = options.metronome;
+    app.compact = options.compact;
+    app.bell = options.bell || config.cues.bell;
+    audio.set_stereo(options.stereo);
+
+    if options.text || options.events {
+        let output = if options.events { TextOutput::Events } else { TextOutput::Lines };
+        run_text_session(&mut app, &audio, output)?;
+        if app.state == AppState::Complete {
+            app.log_session();
+            match output {
+                TextOutput::Lines => print_session_summary(&app),
+                TextOutput::Events => {
+                    emit_event(serde_json::json!({
+                        "t": round_tenths(app.session_elapsed().as_secs_f64()),
+                        "event": "complete",
+                        "technique": app.current_technique().id,
+                        "cycles": app.total_cycles(),
+                        "breaths": app.total_breaths(),
+                        "duration": round_tenths(app.session_elapsed().as_secs_f64()),
+                    }))?;
+                }
+            }
+        }
+        return Ok(());
+    }
 
     // Setup terminal
     enable_raw_mode()?;
@@ -278,11 +1047,8 @@ fn run_with_technique(technique: techniques::Technique, cycles: u32) -> Result<(
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app with specific technique
-    let mut app = App::new_with_technique(technique, cycles);
-
     // Run the main loop
-    let result = run_loop(&mut terminal, &mut app, &audio);
+    let result = run_loop(&mut terminal, &mut app, &audio, &config.keys);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -295,120 +1061,466 @@ fn run_with_technique(technique: techniques::Technique, cycles: u32) -> Result<(
 
     // Print session summary if completed
     if app.state == AppState::Complete {
+        app.log_session();
         print_session_summary(&app);
     }
 
     result
 }
 
+/// How often text mode checks for a phase change
+const TEXT_TICK: Duration = Duration::from_millis(20);
+
+/// What the headless modes print
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextOutput {
+    /// A readable line per phase (--text)
+    Lines,
+    /// A JSON object per phase (--events)
+    Events,
+}
+
+/// Headless mode: drive the session without the TUI, printing each phase as
+/// it starts. Cues and cycle/duration targets work as usual.
+fn run_text_session(app: &mut App, audio: &AudioPlayer, output: TextOutput) -> Result<()> {
+    if app.state == AppState::Selecting {
+        let flag = match output {
+            TextOutput::Lines => "--text",
+            TextOutput::Events => "--events",
+        };
+        anyhow::bail!("{} needs a technique, e.g. `breathe box {}`", flag, flag);
+    }
+
+    // There's no prompt to answer here, so the note is printed instead
+    if let Some(note) = app.safety_note() {
+        eprintln!("⚠ {}", note);
+        app.acknowledge_safety();
+    }
+    app.countdown_secs = 0;
+    app.start();
+    if app.audio_enabled {
+        audio.play_phase_tone(PhaseTone::Start, tone_set(app));
+    }
+    print_phase(app, output)?;
+
+    let mut prev_phase = app.phase_serial;
+    let mut prev_state = app.state;
+    let mut last_tick = Instant::now();
+    while app.state != AppState::Complete {
+        std::thread::sleep(TEXT_TICK);
+        app.tick(last_tick.elapsed().as_secs_f64());
+        last_tick = Instant::now();
+
+        if app.state == AppState::Transition && prev_state != AppState::Transition {
+            if let Some(next) = app.next_segment() {
+                match output {
+                    TextOutput::Lines => println!("-- next: {}", next.technique.name),
+                    TextOutput::Events => emit_event(serde_json::json!({
+                        "t": round_tenths(app.session_elapsed().as_secs_f64()),
+                        "event": "next",
+                        "technique": next.technique.id,
+                    }))?,
+                }
+            }
+        } else if app.state == AppState::Breathing && app.phase_serial != prev_phase {
+            // The bell character would break a JSON stream
+            if app.bell && output == TextOutput::Lines {
+                print!("\x07");
+            }
+            if app.audio_enabled {
+                audio.play_phase_tone(phase_tone(app.current_phase().name), tone_set(app));
+            }
+            print_phase(app, output)?;
+        }
+        prev_phase = app.phase_serial;
+        prev_state = app.state;
+    }
+
+    if app.audio_enabled {
+        audio.play_phase_tone(PhaseTone::Complete, tone_set(app));
+        // Let the completion cue finish before the process exits
+        std::thread::sleep(Duration::from_millis(600));
+    }
+    Ok(())
+}
+
+/// Announce the phase that just started: time into the session, phase,
+/// its length and the instruction (plus the cycle, for events)
+fn print_phase(app: &App, output: TextOutput) -> Result<()> {
+    let phase = app.current_phase();
+    let t = app.session_elapsed().as_secs_f64();
+    let duration = round_tenths(app.effective_phase_duration());
+    match output {
+        TextOutput::Lines => println!(
+            "{:>6.1}s  {} ({}s)  {}",
+            t,
+            phase.name.display(),
+            format_secs(duration),
+            phase.instruction
+        ),
+        TextOutput::Events => emit_event(serde_json::json!({
+            "t": round_tenths(t),
+            "event": "phase",
+            "phase": phase.name,
+            "cycle": app.current_cycle(),
+            "duration": duration,
+            "instruction": phase.instruction,
+        }))?,
+    }
+    Ok(())
+}
+
+/// Write one JSON line and flush it, so readers see it as it happens
+fn emit_event(event: serde_json::Value) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", event)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn round_tenths(secs: f64) -> f64 {
+    (secs * 10.0).round() / 10.0
+}
+
+/// True the first time breathe runs on this machine; a marker file in the
+/// config directory records that the disclaimer has been shown
+fn first_run_disclaimer() -> bool {
+    let Some(dir) = config::config_dir() else {
+        return false;
+    };
+    let marker = dir.join("disclaimer-seen");
+    if marker.exists() {
+        return false;
+    }
+    let _ = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&marker, ""));
+    true
+}
+
+/// Close the topmost overlay, if one is showing
+fn close_overlay(app: &mut App) -> bool {
+    if app.show_disclaimer {
+        app.show_disclaimer = false;
+    } else if app.show_intro {
+        app.dismiss_intro();
+    } else if app.show_help {
+        app.show_help = false;
+    } else if app.show_guide {
+        app.show_guide = false;
+    } else {
+        return false;
+    }
+    true
+}
+
+/// Clicks pick techniques and press footer hints, and the wheel scrolls the
+/// technique list; true when a click means leaving breathe
+fn handle_mouse(app: &mut App, audio: &AudioPlayer, mouse: MouseEvent) -> bool {
+    match mouse.kind {
+        MouseEventKind::ScrollDown => app.select_next(),
+        MouseEventKind::ScrollUp => app.select_prev(),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if close_overlay(app) {
+                return false;
+            }
+            if let Some(SelectorRow::Technique(index)) = app.row_at(mouse.column, mouse.row) {
+                app.click_technique(index);
+                return false;
+            }
+            // Typing prompts give keys other meanings, so their hints aren't
+            // clickable
+            if app.searching || app.editing_note {
+                return false;
+            }
+            if let Some(action) = app.hint_at(mouse.column, mouse.row) {
+                return apply_action(app, audio, action);
+            }
+        }
+        _ => {}
+    }
+    false
+}
+
+/// Carry out an action from a key press or a clicked footer hint; true
+/// when it means leaving breathe
+fn apply_action(app: &mut App, audio: &AudioPlayer, action: Action) -> bool {
+    use Action::*;
+    match (app.state, action) {
+        // Quitting an endless session wraps it up instead
+        (AppState::Breathing | AppState::Paused, Quit | Back) if app.is_endless() => {
+            app.finish()
+        }
+        (AppState::Selecting | AppState::Complete, Quit | Back) => return true,
+        (_, Quit) => return true,
+        (AppState::Breathing, Back) => {}
+        (_, Back | Menu) => app.back_to_selection(),
+
+        (AppState::Selecting, Up) => app.select_prev(),
+        (AppState::Selecting, Down) => app.select_next(),
+        (AppState::Selecting, Confirm) => app.confirm_selection(),
+        (AppState::Selecting, Search) => app.start_search(),
+        // Up and down step the cycle count by ten before starting
+        (AppState::Ready, Up) => app.adjust_cycles(10),
+        (AppState::Ready, Down) => app.adjust_cycles(-10),
+        (AppState::Ready, Confirm) => app.start(),
+        (AppState::Countdown, Confirm) => app.begin_breathing(),
+        (AppState::Transition, Confirm) => app.begin_next_segment(),
+        (AppState::Complete, Again) => app.breathe_again(),
+        // ENTER taps the phases along in manual mode
+        (AppState::Breathing, Confirm) if app.manual => app.skip_phase(),
+
+        (_, Pause) => app.toggle_pause(),
+        (_, Next) => app.skip_phase(),
+        (_, Prev) => app.prev_phase(),
+        (AppState::Breathing, Restart) => app.restart_phase(),
+        (_, Restart) => app.reset(),
+        (_, CyclesDown) => app.adjust_cycles(-1),
+        (_, CyclesUp) => app.adjust_cycles(1),
+        (_, CyclesDownMore) => app.adjust_cycles(-5),
+        (_, CyclesUpMore) => app.adjust_cycles(5),
+        (_, PaceDown) => app.adjust_pace(-PACE_STEP),
+        (_, PaceUp) => app.adjust_pace(PACE_STEP),
+        (_, Guide) => app.toggle_guide(),
+        (_, Help) => app.toggle_help(),
+        (_, Audio) => app.toggle_audio(),
+        (_, Drone) => app.toggle_drone(),
+        (_, Metronome) => app.toggle_metronome(),
+        (_, VolumeUp) => {
+            app.adjust_volume(VOLUME_STEP);
+            audio.set_volume(app.volume);
+        }
+        (_, VolumeDown) => {
+            app.adjust_volume(-VOLUME_STEP);
+            audio.set_volume(app.volume);
+        }
+        (_, Note) => app.start_note(),
+        (_, Acknowledge) => app.acknowledge_safety(),
+        (_, Favorite) => app.toggle_favorite(),
+        (_, PageUp) => app.select_page(-1),
+        (_, PageDown) => app.select_page(1),
+        (_, Up | Down | Search | Confirm | Again) => {}
+    }
+    false
+}
+
+/// Poll interval while nothing on screen is animating
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often the current screen needs redrawing without input, or None when
+/// it is static. The Ready prompt pulses slowly, so it gets a lower rate.
+fn redraw_interval(app: &App) -> Option<Duration> {
+    let tick_rate = app.tick_rate;
+    match app.state {
+        // Holds barely change on screen, so battery saver draws them at half rate
+        AppState::Breathing if app.battery_saver && app.current_phase().name.is_hold() => {
+            Some(tick_rate * 2)
+        }
+        AppState::Breathing => Some(tick_rate),
+        AppState::Complete if app.celebration.is_some() => Some(tick_rate),
+        AppState::Ready | AppState::Countdown | AppState::Transition => {
+            Some(Duration::from_millis(100))
+        }
+        AppState::Selecting | AppState::Paused | AppState::Complete => None,
+    }
+}
+
+/// Scale the particle cap to the drawing area: the terminal, or the
+/// --size box within it
+fn fit_particles(app: &mut App, size: Size) {
+    let (width, height) = match app.fixed_size {
+        Some((cols, rows)) => (cols.min(size.width), rows.min(size.height)),
+        None => (size.width, size.height),
+    };
+    app.fit_particles(width, height);
+}
+
 fn run_loop<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
     audio: &AudioPlayer,
+    keys: &KeyBindings,
 ) -> Result<()> {
-    let tick_rate = Duration::from_millis(16); // ~60 FPS
     let mut last_tick = Instant::now();
+    let mut last_draw = Instant::now();
+    let mut needs_redraw = true;
+    fit_particles(app, terminal.size()?);
+
+    // Phase and state as of the last cue check; phases can change from a
+    // manual tap as well as from the timer
+    let mut prev_phase = app.phase_serial;
+    let mut prev_state = app.state;
+    // Whole seconds into the phase at the last metronome check
+    let mut prev_second = 0;
 
     loop {
-        // Render
-        terminal.draw(|frame| ui::render(frame, app))?;
+        // Pick up audio readiness from the audio thread
+        let audio_status = audio.status();
+        if audio_status != app.audio_status {
+            app.set_audio_status(audio_status);
+            needs_redraw = true;
+        }
+        audio.set_drone(app.drone_playing());
+
+        // Render: animated screens redraw every frame, static ones only
+        // after input or a state change
+        let frame_interval = redraw_interval(app);
+        let frame_due = frame_interval.is_some_and(|interval| last_draw.elapsed() >= interval);
+        if needs_redraw || frame_due {
+            terminal.draw(|frame| ui::render(frame, app, keys))?;
+            last_draw = Instant::now();
+            needs_redraw = false;
+        }
 
         // Handle input with timeout
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        let poll_interval = frame_interval.unwrap_or(IDLE_POLL_INTERVAL);
+        let timeout = poll_interval.saturating_sub(last_tick.elapsed());
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            needs_redraw = true;
+
+            // Redraw at the new size right away instead of on the next frame,
+            // so static screens don't show a stale layout
+            if let Event::Resize(width, height) = event {
+                fit_particles(app, Size::new(width, height));
+                terminal.autoresize()?;
+                terminal.draw(|frame| ui::render(frame, app, keys))?;
+                last_draw = Instant::now();
+                needs_redraw = false;
+                continue;
+            }
+
+            if let Event::Mouse(mouse) = event {
+                if handle_mouse(app, audio, mouse) {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
-                    // If help or guide is showing, any key closes it
-                    if app.show_help {
-                        app.show_help = false;
+                    // The note prompt captures typing until it is closed
+                    if app.editing_note {
+                        match key.code {
+                            KeyCode::Enter => app.finish_note(),
+                            KeyCode::Esc => app.cancel_note(),
+                            KeyCode::Backspace => app.note_backspace(),
+                            KeyCode::Char(c) => app.note_input(c),
+                            _ => {}
+                        }
                         continue;
                     }
-                    if app.show_guide {
-                        app.show_guide = false;
+
+                    // So does the selector's search box
+                    if app.searching {
+                        match key.code {
+                            KeyCode::Enter => app.confirm_selection(),
+                            KeyCode::Esc => app.clear_search(),
+                            KeyCode::Backspace => app.search_backspace(),
+                            KeyCode::Up => app.select_prev(),
+                            KeyCode::Down => app.select_next(),
+                            KeyCode::PageUp => app.select_page(-1),
+                            KeyCode::PageDown => app.select_page(1),
+                            KeyCode::Char(c) => app.search_input(c),
+                            _ => {}
+                        }
                         continue;
                     }
 
-                    match app.state {
-                        AppState::Selecting => match key.code {
-                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                            KeyCode::Up | KeyCode::Char('k') => app.select_prev(),
-                            KeyCode::Down | KeyCode::Char('j') => app.select_next(),
-                            KeyCode::Enter | KeyCode::Char(' ') => app.confirm_selection(),
-                            KeyCode::Char('g') => app.toggle_guide(),
-                            KeyCode::Char('?') => app.toggle_help(),
-                            _ => {}
-                        },
-                        AppState::Ready => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Esc | KeyCode::Char('b') => app.back_to_selection(),
-                            KeyCode::Char(' ') | KeyCode::Enter => {
-                                app.start();
-                                if app.audio_enabled {
-                                    audio.play_phase_tone(PhaseTone::Start);
-                                }
-                            },
-                            KeyCode::Left => app.adjust_cycles(-1),
-                            KeyCode::Right => app.adjust_cycles(1),
-                            KeyCode::Char('g') => app.toggle_guide(),
-                            KeyCode::Char('a') => app.toggle_audio(),
-                            KeyCode::Char('?') => app.toggle_help(),
-                            _ => {}
-                        },
-                        AppState::Breathing => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Char(' ') => app.toggle_pause(),
-                            KeyCode::Char('a') => app.toggle_audio(),
-                            KeyCode::Char('?') => app.toggle_help(),
-                            _ => {}
-                        },
-                        AppState::Paused => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Esc | KeyCode::Char('b') => app.back_to_selection(),
-                            KeyCode::Char(' ') => app.toggle_pause(),
-                            KeyCode::Char('r') => app.reset(),
-                            KeyCode::Char('?') => app.toggle_help(),
-                            _ => {}
-                        },
-                        AppState::Complete => match key.code {
-                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                            KeyCode::Char('r') => app.reset(),
-                            KeyCode::Char('b') => app.back_to_selection(),
-                            KeyCode::Char('?') => app.toggle_help(),
-                            _ => {}
-                        },
+                    // If an overlay is showing, any key closes it
+                    if close_overlay(app) {
+                        continue;
+                    }
+
+                    let Some(action) = keys.action(key.code, state_actions(app.state)) else {
+                        continue;
+                    };
+                    if apply_action(app, audio, action) {
+                        return Ok(());
                     }
                 }
             }
         }
 
         // Update app state and check for phase changes
-        if last_tick.elapsed() >= tick_rate {
+        if last_tick.elapsed() >= app.tick_rate {
             let dt = last_tick.elapsed().as_secs_f64();
-            let prev_phase = app.current_phase_index;
-            let prev_state = app.state;
+            let state_before_tick = app.state;
+            let was_celebrating = app.celebration.is_some();
             app.tick(dt);
+            if app.state != state_before_tick || app.celebration.is_some() != was_celebrating {
+                needs_redraw = true;
+            }
 
-            // Play sound on phase change
-            if app.audio_enabled && app.state == AppState::Breathing && app.current_phase_index != prev_phase {
-                let tone = match app.current_phase().name {
-                    PhaseName::Inhale => PhaseTone::Inhale,
-                    PhaseName::Hold => PhaseTone::Hold,
-                    PhaseName::Exhale => PhaseTone::Exhale,
-                    PhaseName::HoldAfterExhale => PhaseTone::HoldEmpty,
-                };
-                audio.play_phase_tone(tone);
+            // Play the start sound when breathing begins, after any countdown
+            if matches!(prev_state, AppState::Ready | AppState::Countdown)
+                && app.state == AppState::Breathing
+            {
+                app.mark_cue();
+                if app.audio_enabled {
+                    audio.play_phase_tone(PhaseTone::Start, tone_set(app));
+                }
+                prev_phase = app.phase_serial;
+            }
+
+            // Play sound on phase change, whether from the timer or a manual
+            // tap (the visual cue flashes even when muted)
+            if app.state == AppState::Breathing && app.phase_serial != prev_phase {
+                app.mark_cue();
+                if app.bell {
+                    execute!(io::stdout(), Print("\x07"))?;
+                }
+                if app.audio_enabled {
+                    audio.play_phase_tone(phase_tone(app.current_phase().name), tone_set(app));
+                }
+            }
+
+            // Metronome tick on each whole second after the phase cue; the
+            // phase clock stops while paused, so ticks do too
+            let second = app.phase_elapsed() as u64;
+            if app.phase_serial != prev_phase {
+                prev_second = 0;
+            } else if second != prev_second {
+                prev_second = second;
+                if app.metronome && app.audio_enabled && app.state == AppState::Breathing {
+                    audio.play_phase_tone(PhaseTone::Tick, tone_set(app));
+                }
             }
 
             // Play completion sound
-            if app.audio_enabled && prev_state == AppState::Breathing && app.state == AppState::Complete {
-                audio.play_phase_tone(PhaseTone::Complete);
+            if prev_state == AppState::Breathing && app.state == AppState::Complete {
+                app.mark_cue();
+                if app.audio_enabled {
+                    audio.play_phase_tone(PhaseTone::Complete, tone_set(app));
+                }
             }
 
+            prev_phase = app.phase_serial;
+            prev_state = app.state;
             last_tick = Instant::now();
+
+            if app.auto_quit_due() {
+                return Ok(());
+            }
         }
     }
 }
 
+/// The cue played as a phase starts
+fn phase_tone(phase: PhaseName) -> PhaseTone {
+    match phase {
+        PhaseName::Inhale => PhaseTone::Inhale,
+        PhaseName::Hold => PhaseTone::Hold,
+        PhaseName::Exhale => PhaseTone::Exhale,
+        PhaseName::HoldAfterExhale => PhaseTone::HoldEmpty,
+    }
+}
+
+/// Cue tones matching the mood of the technique being practiced
+fn tone_set(app: &App) -> ToneSet {
+    app.technique
+        .as_ref()
+        .map_or_else(ToneSet::default, |technique| ToneSet::for_category(technique.category))
+}
+
 fn print_session_summary(app: &App) {
     let technique = app.current_technique();
     let tc = technique.color;
@@ -417,13 +1529,65 @@ fn print_session_summary(app: &App) {
     println!();
     println!("  \x1b[1;38;5;82m✓ Session Complete\x1b[0m");
     println!();
+    if app.segment_results.is_empty() {
+        println!(
+            "  \x1b[38;2;{};{};{}m●\x1b[0m {} · {} cycles · {} breaths · {}",
+            tc.r, tc.g, tc.b,
+            technique.name,
+            app.cycles_completed,
+            app.total_breaths(),
+            elapsed
+        );
+    } else {
+        let totals = app.technique_totals();
+        let label = if app.rounds > 1 {
+            format!("{} rounds", app.rounds)
+        } else {
+            format!("{} techniques", totals.len())
+        };
+        println!(
+            "  \x1b[1m{}\x1b[0m · {} cycles · {} breaths · {}",
+            label,
+            app.total_cycles(),
+            app.total_breaths(),
+            elapsed
+        );
+        for (name, cycles, duration) in totals {
+            println!(
+                "    \x1b[38;5;245m{:<22}\x1b[0m {} cycles · {}",
+                name,
+                cycles,
+                App::format_time(duration)
+            );
+        }
+    }
+    if let Some((on_target, total)) = app.pace_score() {
+        println!(
+            "  \x1b[38;5;245mOn pace:\x1b[0m {}/{} phases ({}%)",
+            on_target,
+            total,
+            on_target * 100 / total
+        );
+    }
+    if let Some((longest, _)) = &app.longest_hold {
+        println!(
+            "  \x1b[38;5;245mHolds:\x1b[0m longest {:.0}s · {:.0}s in total",
+            longest, app.total_hold_secs
+        );
+    }
+    let score = app::session_score(app);
     println!(
-        "  \x1b[38;2;{};{};{}m●\x1b[0m {} · {} cycles · {}",
-        tc.r, tc.g, tc.b,
-        technique.name,
-        app.cycles_completed,
-        elapsed
+        "  \x1b[38;5;245mFocus score:\x1b[0m \x1b[1m{}\x1b[0m · {}",
+        score,
+        app::score_label(score)
     );
+    if !app.note.is_empty() {
+        println!("  \x1b[38;5;245mNote:\x1b[0m {}", app.note);
+    }
+    let streak = history::current_streak();
+    if streak > 0 {
+        println!("  \x1b[38;5;214m🔥 {} day streak\x1b[0m", streak);
+    }
     println!();
     println!("  \x1b[38;5;245mTake a moment to notice how you feel.\x1b[0m");
     println!();
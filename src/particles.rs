@@ -2,9 +2,8 @@
 
 #![allow(dead_code)]
 
+use crate::rng::{time_seed, Rng};
 use crate::techniques::PhaseName;
-use std::collections::hash_map::RandomState;
-use std::hash::{BuildHasher, Hasher};
 
 /// Maximum trail length for particles
 const MAX_TRAIL_LENGTH: usize = 8;
@@ -194,14 +193,14 @@ impl Emitter {
     }
 
     /// Emit particles based on elapsed time
-    pub fn emit(&mut self, dt: f64) -> Vec<Particle> {
+    pub fn emit(&mut self, dt: f64, rng: &mut Rng) -> Vec<Particle> {
         self.accumulator += dt;
         let emit_interval = 1.0 / self.rate;
         let mut particles = Vec::new();
 
         while self.accumulator >= emit_interval {
             self.accumulator -= emit_interval;
-            if let Some(p) = self.spawn_particle() {
+            if let Some(p) = self.spawn_particle(rng) {
                 particles.push(p);
             }
         }
@@ -209,14 +208,14 @@ impl Emitter {
         particles
     }
 
-    fn spawn_particle(&self) -> Option<Particle> {
+    fn spawn_particle(&self, rng: &mut Rng) -> Option<Particle> {
         let (x, y, angle) = match self.shape {
             EmitterShape::Point { x, y } => {
-                let angle = rand_f64() * std::f64::consts::TAU;
+                let angle = rng.next_f64() * std::f64::consts::TAU;
                 (x, y, angle)
             }
             EmitterShape::Ring { cx, cy, radius } => {
-                let angle = rand_f64() * std::f64::consts::TAU;
+                let angle = rng.next_f64() * std::f64::consts::TAU;
                 let x = cx + angle.cos() * radius;
                 let y = cy + angle.sin() * radius;
                 // Direction towards center for inward, away for outward
@@ -227,14 +226,14 @@ impl Emitter {
                 (x, y, dir)
             }
             EmitterShape::Cone { x, y, direction, spread } => {
-                let angle = direction + (rand_f64() - 0.5) * spread;
+                let angle = direction + (rng.next_f64() - 0.5) * spread;
                 (x, y, angle)
             }
         };
 
-        let speed = lerp_rand(self.speed_min, self.speed_max);
-        let life = lerp_rand(self.life_min, self.life_max);
-        let size = lerp_rand(self.size_min, self.size_max);
+        let speed = rng.range(self.speed_min, self.speed_max);
+        let life = rng.range(self.life_min, self.life_max);
+        let size = rng.range(self.size_min, self.size_max);
 
         Some(Particle::new_with_size(x, y, angle, speed, life, size, self.particle_type))
     }
@@ -244,7 +243,12 @@ impl Emitter {
 pub struct ParticleSystem {
     pub particles: Vec<Particle>,
     pub max_particles: usize,
+    /// When false, phases configure no emitters (reduced motion)
+    pub emitting: bool,
+    /// Multiplier on emitter rates, so emission keeps up with the cap
+    pub density: f64,
     emitters: Vec<Emitter>,
+    rng: Rng,
     center_x: f64,
     center_y: f64,
 }
@@ -254,12 +258,20 @@ impl ParticleSystem {
         Self {
             particles: Vec::with_capacity(max_particles),
             max_particles,
+            emitting: true,
+            density: 1.0,
             emitters: Vec::new(),
+            rng: Rng::new(time_seed()),
             center_x: 0.0,
             center_y: 0.0,
         }
     }
 
+    /// Restart the random sequence, for reproducible particles
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
     /// Set the center point for particle behaviors
     pub fn set_center(&mut self, x: f64, y: f64) {
         self.center_x = x;
@@ -279,6 +291,9 @@ impl ParticleSystem {
     /// Configure emitters for a specific breathing phase
     pub fn configure_for_phase(&mut self, phase: PhaseName, scale: f64) {
         self.clear_emitters();
+        if !self.emitting {
+            return;
+        }
 
         match phase {
             PhaseName::Inhale => {
@@ -290,7 +305,7 @@ impl ParticleSystem {
                         cy: self.center_y,
                         radius: outer_radius,
                     },
-                    30.0, // particles per second
+                    30.0 * self.density, // particles per second
                     ParticleType::Inward,
                 )
                 .with_speed(8.0, 15.0)
@@ -305,7 +320,7 @@ impl ParticleSystem {
                         x: self.center_x,
                         y: self.center_y,
                     },
-                    25.0,
+                    25.0 * self.density,
                     ParticleType::Outward,
                 )
                 .with_speed(5.0, 12.0)
@@ -322,7 +337,7 @@ impl ParticleSystem {
                         cy: self.center_y,
                         radius: orbit_radius,
                     },
-                    15.0,
+                    15.0 * self.density,
                     ParticleType::Orbital,
                 )
                 .with_speed(1.0, 2.0)
@@ -338,7 +353,7 @@ impl ParticleSystem {
                         cy: self.center_y,
                         radius: 15.0,
                     },
-                    5.0,
+                    5.0 * self.density,
                     ParticleType::Ambient,
                 )
                 .with_speed(0.5, 1.5)
@@ -360,7 +375,7 @@ impl ParticleSystem {
         // Emit new particles from emitters
         for emitter in &mut self.emitters {
             if self.particles.len() < self.max_particles {
-                let new_particles = emitter.emit(dt);
+                let new_particles = emitter.emit(dt, &mut self.rng);
                 let remaining_capacity = self.max_particles - self.particles.len();
                 self.particles.extend(new_particles.into_iter().take(remaining_capacity));
             }
@@ -374,10 +389,10 @@ impl ParticleSystem {
                 break;
             }
 
-            let angle = rand_f64() * std::f64::consts::TAU;
-            let speed = 10.0 + rand_f64() * 20.0;
-            let life = 1.5 + rand_f64() * 2.0;
-            let size = 0.8 + rand_f64() * 0.8;
+            let angle = self.rng.next_f64() * std::f64::consts::TAU;
+            let speed = self.rng.range(10.0, 30.0);
+            let life = self.rng.range(1.5, 3.5);
+            let size = self.rng.range(0.8, 1.6);
 
             self.particles.push(Particle::new_with_size(
                 x, y, angle, speed, life, size, particle_type,
@@ -401,23 +416,41 @@ impl ParticleSystem {
     }
 }
 
-// ============================================================================
-// RANDOM NUMBER UTILITIES
-// ============================================================================
-
-/// Simple pseudo-random for particles (no external crate needed)
-fn rand_f64() -> f64 {
-    let mut hasher = RandomState::new().build_hasher();
-    hasher.write_u64(
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64,
-    );
-    (hasher.finish() as f64) / (u64::MAX as f64)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_seeded(seed: u64) -> Vec<(f64, f64)> {
+        let mut system = ParticleSystem::new(150);
+        system.reseed(seed);
+        system.configure_for_phase(PhaseName::Inhale, 0.5);
+        for _ in 0..10 {
+            system.update(0.05);
+        }
+        system.iter().map(|p| (p.x, p.y)).collect()
+    }
 
-/// Random value between min and max
-fn lerp_rand(min: f64, max: f64) -> f64 {
-    min + rand_f64() * (max - min)
+    #[test]
+    fn test_same_seed_same_particles() {
+        let first = run_seeded(1234);
+        assert!(!first.is_empty());
+        assert_eq!(first, run_seeded(1234));
+        assert_ne!(first, run_seeded(4321));
+    }
+
+    #[test]
+    fn test_single_burst_fans_out() {
+        // One emit call covering a whole second spawns every particle in a tight loop
+        let mut emitter = Emitter::new(
+            EmitterShape::Ring { cx: 0.0, cy: 0.0, radius: 10.0 },
+            100.0,
+            ParticleType::Outward,
+        );
+        let mut rng = Rng::new(7);
+        let particles = emitter.emit(1.005, &mut rng);
+        assert_eq!(particles.len(), 100);
+
+        let first = particles[0].vy.atan2(particles[0].vx);
+        assert!(particles.iter().any(|p| (p.vy.atan2(p.vx) - first).abs() > 1e-6));
+    }
 }
@@ -0,0 +1,136 @@
+//! Settings breathe remembers between runs, in `prefs.toml` next to the
+//! config file
+//!
+//! Unlike `config.toml` this file is written by breathe itself. A missing
+//! or unreadable file just means the defaults.
+
+use crate::history::SessionRecord;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Prefs {
+    /// Audio cues on or off, as last toggled with `a`
+    pub audio_enabled: bool,
+    /// Technique ids starred with `f`, oldest first
+    pub favorites: Vec<String>,
+    /// Minutes to breathe each week, set with `breathe goal`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weekly_goal_minutes: Option<u32>,
+    /// Still to see the intro to the controls
+    pub first_run: bool,
+}
+
+impl Default for Prefs {
+    fn default() -> Self {
+        Self {
+            audio_enabled: true,
+            favorites: Vec::new(),
+            weekly_goal_minutes: None,
+            first_run: true,
+        }
+    }
+}
+
+/// Default location of the preferences file
+pub fn prefs_path() -> Option<PathBuf> {
+    crate::config::config_dir().map(|dir| dir.join("prefs.toml"))
+}
+
+impl Prefs {
+    /// Preferences from a file, or the defaults if it's missing or broken
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn is_favorite(&self, id: &str) -> bool {
+        self.favorites.iter().any(|f| f == id)
+    }
+
+    /// Star or unstar a technique; true if it's now a favorite
+    pub fn toggle_favorite(&mut self, id: &str) -> bool {
+        if self.is_favorite(id) {
+            self.favorites.retain(|f| f != id);
+            false
+        } else {
+            self.favorites.push(id.to_string());
+            true
+        }
+    }
+
+    /// The favorite practised most recently in `records`, or the newest
+    /// favorite if none of them has been practised yet
+    pub fn latest_favorite(&self, records: &[SessionRecord]) -> Option<&str> {
+        records
+            .iter()
+            .rev()
+            .find_map(|r| self.favorites.iter().find(|f| **f == r.technique_id))
+            .or_else(|| self.favorites.last())
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefs_round_trip_and_fall_back() {
+        let dir = std::env::temp_dir().join(format!("breathe-prefs-{}", std::process::id()));
+        let path = dir.join("prefs.toml");
+        assert_eq!(Prefs::load_from(&path), Prefs::default());
+
+        let muted = Prefs {
+            audio_enabled: false,
+            favorites: vec!["box".to_string()],
+            weekly_goal_minutes: Some(70),
+            first_run: false,
+        };
+        muted.save_to(&path).unwrap();
+        assert_eq!(Prefs::load_from(&path), muted);
+
+        fs::write(&path, "audio_enabled = \"loud\"").unwrap();
+        assert_eq!(Prefs::load_from(&path), Prefs::default());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_latest_favorite_follows_history() {
+        let mut prefs = Prefs::default();
+        assert_eq!(prefs.latest_favorite(&[]), None);
+        prefs.toggle_favorite("box");
+        prefs.toggle_favorite("4-7-8");
+        assert_eq!(prefs.latest_favorite(&[]), Some("4-7-8"));
+
+        let record = |id: &str| SessionRecord {
+            technique_id: id.to_string(),
+            cycles_completed: 4,
+            duration_secs: 60,
+            timestamp: chrono::Local::now(),
+            note: None,
+            longest_hold_secs: None,
+            longest_hold_technique: None,
+            total_hold_secs: 0.0,
+            score: None,
+        };
+        let records = [record("box"), record("coherent")];
+        assert_eq!(prefs.latest_favorite(&records), Some("box"));
+
+        assert!(!prefs.toggle_favorite("box"));
+        assert_eq!(prefs.latest_favorite(&records), Some("4-7-8"));
+    }
+}
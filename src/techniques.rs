@@ -1,11 +1,18 @@
+use crate::rng::Rng;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
 
 /// A breathing phase with duration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Phase {
     pub name: PhaseName,
     pub duration_secs: f64,
-    pub instruction: &'static str,
+    pub instruction: Cow<'static, str>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,7 +24,11 @@ pub enum PhaseName {
 }
 
 impl PhaseName {
-    #[allow(dead_code)]
+    /// Either hold, after the inhale or after the exhale
+    pub fn is_hold(&self) -> bool {
+        matches!(self, PhaseName::Hold | PhaseName::HoldAfterExhale)
+    }
+
     pub fn display(&self) -> &'static str {
         match self {
             PhaseName::Inhale => "INHALE",
@@ -27,7 +38,6 @@ impl PhaseName {
         }
     }
 
-    #[allow(dead_code)]
     pub fn default_instruction(&self) -> &'static str {
         match self {
             PhaseName::Inhale => "Breathe in slowly through your nose",
@@ -48,7 +58,6 @@ pub enum Category {
 }
 
 impl Category {
-    #[allow(dead_code)]
     pub fn display(&self) -> &'static str {
         match self {
             Category::Focus => "Focus & Performance",
@@ -59,7 +68,6 @@ impl Category {
         }
     }
 
-    #[allow(dead_code)]
     pub fn icon(&self) -> &'static str {
         match self {
             Category::Focus => "◎",
@@ -71,6 +79,28 @@ impl Category {
     }
 }
 
+impl FromStr for PhaseName {
+    type Err = anyhow::Error;
+
+    /// Parse a phase by name, e.g. "exhale" or "hold-after-exhale"
+    fn from_str(text: &str) -> Result<Self> {
+        parse_phase_name(text).with_context(|| {
+            format!("unknown phase '{}' (expected inhale, hold, exhale or hold-after-exhale)", text)
+        })
+    }
+}
+
+impl FromStr for Category {
+    type Err = anyhow::Error;
+
+    /// Parse a category by its short name, e.g. "sleep"
+    fn from_str(text: &str) -> Result<Self> {
+        parse_category(text).with_context(|| {
+            format!("unknown category '{}' (expected focus, calm, sleep, energy or recovery)", text)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Difficulty {
     Beginner,
@@ -79,7 +109,6 @@ pub enum Difficulty {
 }
 
 impl Difficulty {
-    #[allow(dead_code)]
     pub fn display(&self) -> &'static str {
         match self {
             Difficulty::Beginner => "Beginner",
@@ -89,22 +118,43 @@ impl Difficulty {
     }
 }
 
+/// How the breath should travel for a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreathRoute {
+    Nose,
+    Mouth,
+}
+
+impl BreathRoute {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BreathRoute::Nose => "nose",
+            BreathRoute::Mouth => "mouth",
+        }
+    }
+}
+
 /// A complete breathing technique
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Technique {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub tagline: &'static str,
-    pub description: &'static str,
-    pub pattern: &'static str,
+    pub id: Cow<'static, str>,
+    pub name: Cow<'static, str>,
+    pub tagline: Cow<'static, str>,
+    pub description: Cow<'static, str>,
+    pub pattern: Cow<'static, str>,
     pub phases: Vec<Phase>,
-    pub purpose: &'static str,
-    pub use_case: &'static str,
-    pub source: &'static str,
+    pub purpose: Cow<'static, str>,
+    pub use_case: Cow<'static, str>,
+    pub source: Cow<'static, str>,
     pub color: TechniqueColor,
     pub default_cycles: u32,
     pub category: Category,
     pub difficulty: Difficulty,
+    /// Intended route for inhales and exhales (None = either is fine)
+    pub inhale_route: Option<BreathRoute>,
+    pub exhale_route: Option<BreathRoute>,
+    /// Risks to know about before starting, shown on the ready screen
+    pub safety_note: Option<Cow<'static, str>>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -129,352 +179,936 @@ impl TechniqueColor {
     pub const fn rose() -> Self { Self::new(244, 63, 94) }
 }
 
+/// How long the recovery breath is held at the end of a round (seconds)
+pub const RECOVERY_HOLD_SECS: f64 = 15.0;
+
 impl Technique {
-    #[allow(dead_code)]
+    /// Length of one full cycle, in seconds
     pub fn cycle_duration(&self) -> f64 {
         self.phases.iter().map(|p| p.duration_secs).sum()
     }
+
+    /// Breaths taken in one cycle: its inhale phases, so two for the
+    /// physiological sigh's double inhale
+    pub fn inhales_per_cycle(&self) -> u32 {
+        self.phases.iter().filter(|p| p.name == PhaseName::Inhale).count() as u32
+    }
+
+    /// Each phase with the offsets (in seconds) it starts and ends at
+    /// within one cycle
+    pub fn timeline(&self) -> Vec<(f64, f64, &Phase)> {
+        let mut start = 0.0;
+        self.phases
+            .iter()
+            .map(|phase| {
+                let end = start + phase.duration_secs;
+                let entry = (start, end, phase);
+                start = end;
+                entry
+            })
+            .collect()
+    }
+
+    /// Techniques whose benefit depends on an exact breathing rate, so
+    /// natural-mode jitter is never applied to them
+    pub fn precise_timing(&self) -> bool {
+        matches!(self.id.as_ref(), "coherent" | "resonant")
+    }
+
+    /// Retention hold (seconds) that ends each round when practicing in
+    /// rounds, for techniques built around one
+    pub fn default_retention(&self) -> Option<f64> {
+        match self.id.as_ref() {
+            "wim-hof" => Some(60.0),
+            _ => None,
+        }
+    }
+
+    /// The end-of-round sequence for this technique: exhale and hold on
+    /// empty, then one recovery breath held briefly before letting go
+    pub fn retention(&self, hold_secs: f64) -> Technique {
+        Technique {
+            pattern: "hold-recover".into(),
+            phases: vec![
+                Phase { name: PhaseName::HoldAfterExhale, duration_secs: hold_secs, instruction: "Hold on Empty".into() },
+                Phase { name: PhaseName::Inhale, duration_secs: 2.0, instruction: "Recovery Breath".into() },
+                Phase { name: PhaseName::Hold, duration_secs: RECOVERY_HOLD_SECS, instruction: "Hold Full".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 2.0, instruction: "Release".into() },
+            ],
+            ..self.clone()
+        }
+    }
+
+    /// An ad-hoc technique from a dash-separated pattern and the phase each
+    /// duration belongs to, e.g. ("Triangle", "4-4-4", [Inhale, Hold,
+    /// Exhale]). Instructions are the phases' defaults; color, category and
+    /// cycles are the same defaults user techniques get.
+    pub fn from_pattern(name: &str, pattern: &str, phase_names: &[PhaseName]) -> Result<Technique> {
+        let durations = parse_durations(pattern)?;
+        if durations.len() != phase_names.len() {
+            bail!(
+                "invalid pattern '{}': {} durations for {} phases",
+                pattern,
+                durations.len(),
+                phase_names.len()
+            );
+        }
+
+        let phases = phase_names
+            .iter()
+            .zip(durations)
+            .map(|(&name, duration_secs)| Phase {
+                name,
+                duration_secs,
+                instruction: name.default_instruction().into(),
+            })
+            .collect();
+        let id: Vec<String> = name.split_whitespace().map(str::to_lowercase).collect();
+        Ok(Technique {
+            id: id.join("-").into(),
+            name: name.to_string().into(),
+            tagline: "".into(),
+            description: "".into(),
+            pattern: pattern.trim().to_string().into(),
+            phases,
+            purpose: "".into(),
+            use_case: "".into(),
+            source: "".into(),
+            color: TechniqueColor::slate(),
+            default_cycles: 10,
+            category: Category::Calm,
+            difficulty: Difficulty::Beginner,
+            inhale_route: None,
+            exhale_route: None,
+            safety_note: None,
+        })
+    }
+
+    /// This technique with its phase durations replaced by a dash-separated
+    /// pattern like "5-5-5-5", one number per phase, in order
+    pub fn with_pattern(&self, pattern: &str) -> Result<Technique> {
+        let durations = pattern.split('-').count();
+        if durations != self.phases.len() {
+            let names: Vec<&str> = self.phases.iter().map(|p| p.name.display()).collect();
+            bail!(
+                "invalid pattern '{}': {} has {} phases ({}), got {} durations",
+                pattern,
+                self.name,
+                self.phases.len(),
+                names.join("-").to_lowercase(),
+                durations
+            );
+        }
+
+        // Same phases with the new durations, keeping their instructions
+        let phases = self
+            .phases
+            .iter()
+            .zip(parse_durations(pattern)?)
+            .map(|(phase, duration_secs)| Phase { duration_secs, ..phase.clone() })
+            .collect();
+        Ok(Technique {
+            pattern: pattern.trim().to_string().into(),
+            phases,
+            ..self.clone()
+        })
+    }
+
+    /// Just the phases of one kind from this technique, in order, to
+    /// practice that part on its own (e.g. only the long exhale)
+    pub fn only_phase(&self, name: PhaseName) -> Result<Technique> {
+        let phases: Vec<Phase> = self.phases.iter().filter(|p| p.name == name).cloned().collect();
+        if phases.is_empty() {
+            let names: Vec<&str> = self.phases.iter().map(|p| p.name.display()).collect();
+            bail!(
+                "{} has no {} phase ({})",
+                self.name,
+                name.display().to_lowercase(),
+                names.join("-").to_lowercase()
+            );
+        }
+
+        let durations: Vec<String> = phases.iter().map(|p| p.duration_secs.to_string()).collect();
+        Ok(Technique {
+            pattern: durations.join("-").into(),
+            phases,
+            ..self.clone()
+        })
+    }
+
+    /// Breathing route for a phase, if the technique specifies one
+    pub fn route_for(&self, phase: PhaseName) -> Option<BreathRoute> {
+        match phase {
+            PhaseName::Inhale => self.inhale_route,
+            PhaseName::Exhale => self.exhale_route,
+            PhaseName::Hold | PhaseName::HoldAfterExhale => None,
+        }
+    }
+}
+
+/// Seconds without a trailing ".0" for whole numbers
+pub fn format_secs(secs: f64) -> String {
+    if secs.fract() == 0.0 {
+        format!("{}", secs)
+    } else {
+        format!("{:.1}", secs)
+    }
+}
+
+/// The built-in techniques, built once on first use
+static BUILTIN: OnceLock<Vec<Technique>> = OnceLock::new();
+
+/// The built-in techniques followed by any user-defined ones, fixed the
+/// first time it is read or when user techniques are installed
+static CATALOG: OnceLock<Vec<Technique>> = OnceLock::new();
+
+/// All available breathing techniques: the built-in set followed by any
+/// user-defined ones
+pub fn all_techniques() -> &'static [Technique] {
+    CATALOG.get_or_init(|| builtin_techniques().to_vec())
 }
 
-/// All available breathing techniques
-pub fn all_techniques() -> Vec<Technique> {
+/// Techniques that ship with breathe
+pub fn builtin_techniques() -> &'static [Technique] {
+    BUILTIN.get_or_init(builtin_catalog)
+}
+
+fn builtin_catalog() -> Vec<Technique> {
     vec![
         // ==========================================
         // FOCUS & PERFORMANCE
         // ==========================================
         Technique {
-            id: "box",
-            name: "Box Breathing",
-            tagline: "Navy SEAL Standard",
-            description: "The gold standard of tactical breathing. Equal parts inhale, hold, exhale, and hold create a \"box\" pattern that brings you to a state of alert calm.",
-            pattern: "4-4-4-4",
+            id: "box".into(),
+            name: "Box Breathing".into(),
+            tagline: "Navy SEAL Standard".into(),
+            description: "The gold standard of tactical breathing. Equal parts inhale, hold, exhale, and hold create a \"box\" pattern that brings you to a state of alert calm.".into(),
+            pattern: "4-4-4-4".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Breathe In" },
-                Phase { name: PhaseName::Hold, duration_secs: 4.0, instruction: "Hold" },
-                Phase { name: PhaseName::Exhale, duration_secs: 4.0, instruction: "Breathe Out" },
-                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 4.0, instruction: "Hold Empty" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Breathe In".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 4.0, instruction: "Hold".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 4.0, instruction: "Breathe Out".into() },
+                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 4.0, instruction: "Hold Empty".into() },
             ],
-            purpose: "Alert calm, mental clarity, stress inoculation",
-            use_case: "Pre-performance, daily practice, high-pressure situations",
-            source: "Navy SEAL standard, Mark Divine (SEALFIT)",
+            purpose: "Alert calm, mental clarity, stress inoculation".into(),
+            use_case: "Pre-performance, daily practice, high-pressure situations".into(),
+            source: "Navy SEAL standard, Mark Divine (SEALFIT)".into(),
             color: TechniqueColor::arctic(),
             default_cycles: 5,
             category: Category::Focus,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Nose),
+            safety_note: None,
         },
         Technique {
-            id: "gateway",
-            name: "Gateway Process",
-            tagline: "CIA Declassified",
-            description: "From declassified CIA documents. Developed at the Monroe Institute for intelligence applications. Achieves \"Focus 10\" state—mind awake, body asleep.",
-            pattern: "4-4-8",
+            id: "gateway".into(),
+            name: "Gateway Process".into(),
+            tagline: "CIA Declassified".into(),
+            description: "From declassified CIA documents. Developed at the Monroe Institute for intelligence applications. Achieves \"Focus 10\" state—mind awake, body asleep.".into(),
+            pattern: "4-4-8".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Deep Breath In" },
-                Phase { name: PhaseName::Hold, duration_secs: 4.0, instruction: "Hold & Hum" },
-                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Resonant Exhale" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Deep Breath In".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 4.0, instruction: "Hold & Hum".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Resonant Exhale".into() },
             ],
-            purpose: "Enhanced focus, expanded awareness, mental clarity",
-            use_case: "Deep concentration, meditation, problem-solving",
-            source: "CIA/Monroe Institute, declassified 2003",
+            purpose: "Enhanced focus, expanded awareness, mental clarity".into(),
+            use_case: "Deep concentration, meditation, problem-solving".into(),
+            source: "CIA/Monroe Institute, declassified 2003".into(),
             color: TechniqueColor::slate(),
             default_cycles: 7,
             category: Category::Focus,
             difficulty: Difficulty::Intermediate,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Nose),
+            safety_note: None,
         },
         Technique {
-            id: "operative",
-            name: "Operative Protocol",
-            tagline: "Field Agent Standard",
-            description: "Three-phase technique from declassified CIA training. Emphasizes exhale and post-exhale hold where best mental concentration is achieved.",
-            pattern: "3-6-3",
+            id: "operative".into(),
+            name: "Operative Protocol".into(),
+            tagline: "Field Agent Standard".into(),
+            description: "Three-phase technique from declassified CIA training. Emphasizes exhale and post-exhale hold where best mental concentration is achieved.".into(),
+            pattern: "3-6-3".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 3.0, instruction: "Effortless Inhale" },
-                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Controlled Exhale" },
-                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 3.0, instruction: "Focus Point" },
+                Phase { name: PhaseName::Inhale, duration_secs: 3.0, instruction: "Effortless Inhale".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Controlled Exhale".into() },
+                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 3.0, instruction: "Focus Point".into() },
             ],
-            purpose: "Tactical calmness, mental concentration under pressure",
-            use_case: "High-stakes situations, crisis management",
-            source: "CIA declassified training documents",
+            purpose: "Tactical calmness, mental concentration under pressure".into(),
+            use_case: "High-stakes situations, crisis management".into(),
+            source: "CIA declassified training documents".into(),
             color: TechniqueColor::slate(),
             default_cycles: 8,
             category: Category::Focus,
             difficulty: Difficulty::Intermediate,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "sere",
-            name: "SERE Breathing",
-            tagline: "Survival Training",
-            description: "Core technique from Survival, Evasion, Resistance, and Escape training. Builds stress tolerance through controlled discomfort.",
-            pattern: "4-7-8-4",
+            id: "sere".into(),
+            name: "SERE Breathing".into(),
+            tagline: "Survival Training".into(),
+            description: "Core technique from Survival, Evasion, Resistance, and Escape training. Builds stress tolerance through controlled discomfort.".into(),
+            pattern: "4-7-8-4".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Controlled Inhale" },
-                Phase { name: PhaseName::Hold, duration_secs: 7.0, instruction: "Stress Inoculation" },
-                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Complete Release" },
-                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 4.0, instruction: "Empty Resilience" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Controlled Inhale".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 7.0, instruction: "Stress Inoculation".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Complete Release".into() },
+                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 4.0, instruction: "Empty Resilience".into() },
             ],
-            purpose: "Stress inoculation, psychological resilience",
-            use_case: "Extreme stress preparation, building mental toughness",
-            source: "SERE Training Program, U.S. Military",
+            purpose: "Stress inoculation, psychological resilience".into(),
+            use_case: "Extreme stress preparation, building mental toughness".into(),
+            source: "SERE Training Program, U.S. Military".into(),
             color: TechniqueColor::gold(),
             default_cycles: 6,
             category: Category::Focus,
             difficulty: Difficulty::Advanced,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: Some("Long breath holds can cause light-headedness. Practice seated, and stop if you feel faint.".into()),
         },
 
         // ==========================================
         // STRESS & CALM
         // ==========================================
         Technique {
-            id: "combat",
-            name: "Combat Breathing",
-            tagline: "Rapid Calm-Down",
-            description: "Designed for rapid calm-down in high-stress situations. Extended exhale activates parasympathetic nervous system, dropping heart rate within seconds.",
-            pattern: "4-1-8",
+            id: "combat".into(),
+            name: "Combat Breathing".into(),
+            tagline: "Rapid Calm-Down".into(),
+            description: "Designed for rapid calm-down in high-stress situations. Extended exhale activates parasympathetic nervous system, dropping heart rate within seconds.".into(),
+            pattern: "4-1-8".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Breathe In" },
-                Phase { name: PhaseName::Hold, duration_secs: 1.0, instruction: "Brief Pause" },
-                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Slow Exhale" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Breathe In".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 1.0, instruction: "Brief Pause".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Slow Exhale".into() },
             ],
-            purpose: "Rapid heart rate reduction, combat stress control",
-            use_case: "Acute stress, panic moments, before confrontation",
-            source: "U.S. Military Combat Stress Control",
+            purpose: "Rapid heart rate reduction, combat stress control".into(),
+            use_case: "Acute stress, panic moments, before confrontation".into(),
+            source: "U.S. Military Combat Stress Control".into(),
             color: TechniqueColor::gold(),
             default_cycles: 6,
             category: Category::Calm,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "sigh",
-            name: "Physiological Sigh",
-            tagline: "Instant Calm Reset",
-            description: "The fastest scientifically-proven way to reduce stress in real-time. Double inhale reinflates lung sacs, long exhale offloads CO2, triggering immediate calm.",
-            pattern: "2-1-6",
+            id: "sigh".into(),
+            name: "Physiological Sigh".into(),
+            tagline: "Instant Calm Reset".into(),
+            description: "The fastest scientifically-proven way to reduce stress in real-time. Double inhale reinflates lung sacs, long exhale offloads CO2, triggering immediate calm.".into(),
+            pattern: "2-1-6".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 2.0, instruction: "Inhale (Nose)" },
-                Phase { name: PhaseName::Inhale, duration_secs: 1.0, instruction: "Sip More Air" },
-                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Long Exhale (Mouth)" },
+                Phase { name: PhaseName::Inhale, duration_secs: 2.0, instruction: "Inhale (Nose)".into() },
+                Phase { name: PhaseName::Inhale, duration_secs: 1.0, instruction: "Sip More Air".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Long Exhale (Mouth)".into() },
             ],
-            purpose: "Fastest real-time stress reduction",
-            use_case: "Panic attacks, immediate relief, emotional reset",
-            source: "Dr. Andrew Huberman, Stanford Neuroscience",
+            purpose: "Fastest real-time stress reduction".into(),
+            use_case: "Panic attacks, immediate relief, emotional reset".into(),
+            source: "Dr. Andrew Huberman, Stanford Neuroscience".into(),
             color: TechniqueColor::arctic(),
             default_cycles: 3,
             category: Category::Calm,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "coherent",
-            name: "Coherent Breathing",
-            tagline: "Heart-Brain Sync",
-            description: "Breathing at 5 breaths per minute synchronizes heart rate variability, creating \"coherence\" between heart and brain. Used by elite athletes.",
-            pattern: "6-6",
+            id: "coherent".into(),
+            name: "Coherent Breathing".into(),
+            tagline: "Heart-Brain Sync".into(),
+            description: "Breathing at 5 breaths per minute synchronizes heart rate variability, creating \"coherence\" between heart and brain. Used by elite athletes.".into(),
+            pattern: "6-6".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 6.0, instruction: "Slow Inhale" },
-                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Slow Exhale" },
+                Phase { name: PhaseName::Inhale, duration_secs: 6.0, instruction: "Slow Inhale".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Slow Exhale".into() },
             ],
-            purpose: "Heart-brain coherence, HRV optimization",
-            use_case: "Daily practice, emotional regulation, peak performance",
-            source: "HeartMath Institute, Stephen Elliott",
+            purpose: "Heart-brain coherence, HRV optimization".into(),
+            use_case: "Daily practice, emotional regulation, peak performance".into(),
+            source: "HeartMath Institute, Stephen Elliott".into(),
             color: TechniqueColor::rose(),
             default_cycles: 10,
             category: Category::Calm,
             difficulty: Difficulty::Intermediate,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Nose),
+            safety_note: None,
         },
         Technique {
-            id: "resonant",
-            name: "Resonant Breathing",
-            tagline: "Vagal Tone Builder",
-            description: "Optimizes vagal tone—the strength of your relaxation response. At 5-6 breaths per minute, cardiovascular system enters resonance.",
-            pattern: "5-5",
+            id: "resonant".into(),
+            name: "Resonant Breathing".into(),
+            tagline: "Vagal Tone Builder".into(),
+            description: "Optimizes vagal tone—the strength of your relaxation response. At 5-6 breaths per minute, cardiovascular system enters resonance.".into(),
+            pattern: "5-5".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 5.0, instruction: "Smooth Inhale" },
-                Phase { name: PhaseName::Exhale, duration_secs: 5.0, instruction: "Smooth Exhale" },
+                Phase { name: PhaseName::Inhale, duration_secs: 5.0, instruction: "Smooth Inhale".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 5.0, instruction: "Smooth Exhale".into() },
             ],
-            purpose: "Build long-term stress resilience",
-            use_case: "Daily practice, vagal toning, PTSD recovery",
-            source: "Dr. Richard Brown, Columbia University",
+            purpose: "Build long-term stress resilience".into(),
+            use_case: "Daily practice, vagal toning, PTSD recovery".into(),
+            source: "Dr. Richard Brown, Columbia University".into(),
             color: TechniqueColor::emerald(),
             default_cycles: 12,
             category: Category::Calm,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Nose),
+            safety_note: None,
         },
 
         // ==========================================
         // SLEEP & RELAXATION
         // ==========================================
         Technique {
-            id: "military-sleep",
-            name: "Military Sleep",
-            tagline: "2-Minute Sleep Technique",
-            description: "Developed for fighter pilots to fall asleep in 2 minutes under any conditions. Used by 96% of pilots after 6 weeks of practice.",
-            pattern: "4-7-8",
+            id: "military-sleep".into(),
+            name: "Military Sleep".into(),
+            tagline: "2-Minute Sleep Technique".into(),
+            description: "Developed for fighter pilots to fall asleep in 2 minutes under any conditions. Used by 96% of pilots after 6 weeks of practice.".into(),
+            pattern: "4-7-8".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Deep Breath In" },
-                Phase { name: PhaseName::Hold, duration_secs: 7.0, instruction: "Hold & Relax Face" },
-                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Release Everything" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Deep Breath In".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 7.0, instruction: "Hold & Relax Face".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Release Everything".into() },
             ],
-            purpose: "Fall asleep in under 2 minutes",
-            use_case: "Insomnia, sleeping in difficult conditions, jet lag",
-            source: "U.S. Navy Pre-Flight School, Bud Winter",
+            purpose: "Fall asleep in under 2 minutes".into(),
+            use_case: "Insomnia, sleeping in difficult conditions, jet lag".into(),
+            source: "U.S. Navy Pre-Flight School, Bud Winter".into(),
             color: TechniqueColor::purple(),
             default_cycles: 6,
             category: Category::Sleep,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "478",
-            name: "4-7-8 Breathing",
-            tagline: "Natural Tranquilizer",
-            description: "A powerful relaxation technique that acts as a natural tranquilizer for the nervous system. Long hold and exhale shift body into deep rest mode.",
-            pattern: "4-7-8",
+            id: "478".into(),
+            name: "4-7-8 Breathing".into(),
+            tagline: "Natural Tranquilizer".into(),
+            description: "A powerful relaxation technique that acts as a natural tranquilizer for the nervous system. Long hold and exhale shift body into deep rest mode.".into(),
+            pattern: "4-7-8".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Breathe In" },
-                Phase { name: PhaseName::Hold, duration_secs: 7.0, instruction: "Hold" },
-                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Breathe Out" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Breathe In".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 7.0, instruction: "Hold".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Breathe Out".into() },
             ],
-            purpose: "Deep relaxation, nervous system reset",
-            use_case: "Pre-sleep routine, anxiety relief, wind-down",
-            source: "Dr. Andrew Weil (based on yogic pranayama)",
+            purpose: "Deep relaxation, nervous system reset".into(),
+            use_case: "Pre-sleep routine, anxiety relief, wind-down".into(),
+            source: "Dr. Andrew Weil (based on yogic pranayama)".into(),
             color: TechniqueColor::purple(),
             default_cycles: 4,
             category: Category::Sleep,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "sleep-exhale",
-            name: "Sleep Exhale",
-            tagline: "Extended Exhale Sleep",
-            description: "Emphasizes very long exhale to maximally activate parasympathetic \"rest and digest\" response. 2:1 exhale-to-inhale ratio signals deep safety.",
-            pattern: "4-2-8-2",
+            id: "sleep-exhale".into(),
+            name: "Sleep Exhale".into(),
+            tagline: "Extended Exhale Sleep".into(),
+            description: "Emphasizes very long exhale to maximally activate parasympathetic \"rest and digest\" response. 2:1 exhale-to-inhale ratio signals deep safety.".into(),
+            pattern: "4-2-8-2".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Gentle Inhale" },
-                Phase { name: PhaseName::Hold, duration_secs: 2.0, instruction: "Soft Hold" },
-                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Long Slow Exhale" },
-                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 2.0, instruction: "Rest Empty" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Gentle Inhale".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 2.0, instruction: "Soft Hold".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 8.0, instruction: "Long Slow Exhale".into() },
+                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 2.0, instruction: "Rest Empty".into() },
             ],
-            purpose: "Maximum relaxation, parasympathetic activation",
-            use_case: "Deep insomnia, racing thoughts, nighttime anxiety",
-            source: "Clinical sleep research",
+            purpose: "Maximum relaxation, parasympathetic activation".into(),
+            use_case: "Deep insomnia, racing thoughts, nighttime anxiety".into(),
+            source: "Clinical sleep research".into(),
             color: TechniqueColor::purple(),
             default_cycles: 8,
             category: Category::Sleep,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
 
         // ==========================================
         // ENERGY & ACTIVATION
         // ==========================================
         Technique {
-            id: "energize",
-            name: "Energizing Breath",
-            tagline: "Natural Energy Surge",
-            description: "Controlled hyperventilation that boosts oxygen levels and triggers adrenaline release. Creates natural energy surge without caffeine.",
-            pattern: "1-1",
+            id: "energize".into(),
+            name: "Energizing Breath".into(),
+            tagline: "Natural Energy Surge".into(),
+            description: "Controlled hyperventilation that boosts oxygen levels and triggers adrenaline release. Creates natural energy surge without caffeine.".into(),
+            pattern: "1-1".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 1.0, instruction: "Quick Inhale" },
-                Phase { name: PhaseName::Exhale, duration_secs: 1.0, instruction: "Quick Exhale" },
+                Phase { name: PhaseName::Inhale, duration_secs: 1.0, instruction: "Quick Inhale".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 1.0, instruction: "Quick Exhale".into() },
             ],
-            purpose: "Alertness, energy boost, wake-up",
-            use_case: "Morning activation, pre-workout, afternoon slump",
-            source: "Modified from Wim Hof & Kapalabhati",
+            purpose: "Alertness, energy boost, wake-up".into(),
+            use_case: "Morning activation, pre-workout, afternoon slump".into(),
+            source: "Modified from Wim Hof & Kapalabhati".into(),
             color: TechniqueColor::orange(),
             default_cycles: 30,
             category: Category::Energy,
             difficulty: Difficulty::Intermediate,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Nose),
+            safety_note: Some("Fast, forceful breathing can cause tingling or dizziness. Practice seated, and never in or near water or while driving.".into()),
         },
         Technique {
-            id: "power",
-            name: "Power Breathing",
-            tagline: "Pre-Mission Activation",
-            description: "Used by special operators before missions. Builds energy through breath holds that trigger adrenaline, then channels it with controlled exhales.",
-            pattern: "4-4-4",
+            id: "power".into(),
+            name: "Power Breathing".into(),
+            tagline: "Pre-Mission Activation".into(),
+            description: "Used by special operators before missions. Builds energy through breath holds that trigger adrenaline, then channels it with controlled exhales.".into(),
+            pattern: "4-4-4".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Power Inhale" },
-                Phase { name: PhaseName::Hold, duration_secs: 4.0, instruction: "Build Energy" },
-                Phase { name: PhaseName::Exhale, duration_secs: 4.0, instruction: "Channel Power" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Power Inhale".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 4.0, instruction: "Build Energy".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 4.0, instruction: "Channel Power".into() },
             ],
-            purpose: "Peak activation, mental intensity, pre-performance",
-            use_case: "Before competition, presentations, physical challenges",
-            source: "Special Operations performance protocols",
+            purpose: "Peak activation, mental intensity, pre-performance".into(),
+            use_case: "Before competition, presentations, physical challenges".into(),
+            source: "Special Operations performance protocols".into(),
             color: TechniqueColor::orange(),
             default_cycles: 6,
             category: Category::Energy,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "wim-hof",
-            name: "Wim Hof Method",
-            tagline: "The Iceman Protocol",
-            description: "Famous technique from \"The Iceman.\" 30 power breaths create massive oxygen saturation and controlled stress exposure, building mental resilience.",
-            pattern: "2-1",
+            id: "wim-hof".into(),
+            name: "Wim Hof Method".into(),
+            tagline: "The Iceman Protocol".into(),
+            description: "Famous technique from \"The Iceman.\" 30 power breaths create massive oxygen saturation and controlled stress exposure, building mental resilience.".into(),
+            pattern: "2-1".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 2.0, instruction: "Full Breath In" },
-                Phase { name: PhaseName::Exhale, duration_secs: 1.0, instruction: "Let Go" },
+                Phase { name: PhaseName::Inhale, duration_secs: 2.0, instruction: "Full Breath In".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 1.0, instruction: "Let Go".into() },
             ],
-            purpose: "Immune boost, cold tolerance, mental fortitude",
-            use_case: "Morning practice, cold exposure prep, stress inoculation",
-            source: "Wim Hof, validated by Radboud University",
+            purpose: "Immune boost, cold tolerance, mental fortitude".into(),
+            use_case: "Morning practice, cold exposure prep, stress inoculation".into(),
+            source: "Wim Hof, validated by Radboud University".into(),
             color: TechniqueColor::arctic(),
             default_cycles: 30,
             category: Category::Energy,
             difficulty: Difficulty::Advanced,
+            inhale_route: None,
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: Some("Never practice in or near water, while driving, or standing: the breathing and holds can cause fainting. Not for pregnancy, epilepsy or heart conditions.".into()),
         },
 
         // ==========================================
         // RECOVERY & HEALING
         // ==========================================
         Technique {
-            id: "recovery",
-            name: "Recovery Breathing",
-            tagline: "Post-Stress Recovery",
-            description: "Designed for recovery after intense physical or mental stress. Longer exhales and holds maximize parasympathetic recovery and reduce cortisol.",
-            pattern: "4-2-6-4",
+            id: "recovery".into(),
+            name: "Recovery Breathing".into(),
+            tagline: "Post-Stress Recovery".into(),
+            description: "Designed for recovery after intense physical or mental stress. Longer exhales and holds maximize parasympathetic recovery and reduce cortisol.".into(),
+            pattern: "4-2-6-4".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Recovery Breath" },
-                Phase { name: PhaseName::Hold, duration_secs: 2.0, instruction: "Brief Hold" },
-                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Release Tension" },
-                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 4.0, instruction: "Deep Rest" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Recovery Breath".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 2.0, instruction: "Brief Hold".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Release Tension".into() },
+                Phase { name: PhaseName::HoldAfterExhale, duration_secs: 4.0, instruction: "Deep Rest".into() },
             ],
-            purpose: "Cortisol reduction, nervous system recovery",
-            use_case: "Post-workout, after stressful events, evening wind-down",
-            source: "Sports science recovery protocols",
+            purpose: "Cortisol reduction, nervous system recovery".into(),
+            use_case: "Post-workout, after stressful events, evening wind-down".into(),
+            source: "Sports science recovery protocols".into(),
             color: TechniqueColor::emerald(),
             default_cycles: 8,
             category: Category::Recovery,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Mouth),
+            safety_note: None,
         },
         Technique {
-            id: "nsdr",
-            name: "NSDR Breathing",
-            tagline: "Non-Sleep Deep Rest",
-            description: "Breathing pattern for Non-Sleep Deep Rest, providing recovery benefits similar to sleep. Achieves deep relaxation while maintaining awareness.",
-            pattern: "4-6-6",
+            id: "nsdr".into(),
+            name: "NSDR Breathing".into(),
+            tagline: "Non-Sleep Deep Rest".into(),
+            description: "Breathing pattern for Non-Sleep Deep Rest, providing recovery benefits similar to sleep. Achieves deep relaxation while maintaining awareness.".into(),
+            pattern: "4-6-6".into(),
             phases: vec![
-                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Gentle Inhale" },
-                Phase { name: PhaseName::Hold, duration_secs: 6.0, instruction: "Restful Hold" },
-                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Melting Exhale" },
+                Phase { name: PhaseName::Inhale, duration_secs: 4.0, instruction: "Gentle Inhale".into() },
+                Phase { name: PhaseName::Hold, duration_secs: 6.0, instruction: "Restful Hold".into() },
+                Phase { name: PhaseName::Exhale, duration_secs: 6.0, instruction: "Melting Exhale".into() },
             ],
-            purpose: "Deep rest without sleep, recovery, focus restoration",
-            use_case: "Afternoon recharge, sleep debt recovery, mental reset",
-            source: "Dr. Andrew Huberman, Stanford protocols",
+            purpose: "Deep rest without sleep, recovery, focus restoration".into(),
+            use_case: "Afternoon recharge, sleep debt recovery, mental reset".into(),
+            source: "Dr. Andrew Huberman, Stanford protocols".into(),
             color: TechniqueColor::purple(),
             default_cycles: 10,
             category: Category::Recovery,
             difficulty: Difficulty::Beginner,
+            inhale_route: Some(BreathRoute::Nose),
+            exhale_route: Some(BreathRoute::Nose),
+            safety_note: None,
         },
     ]
 }
 
+/// Default location of the user technique file
+pub fn user_techniques_path() -> Option<PathBuf> {
+    crate::config::config_dir().map(|dir| dir.join("techniques.toml"))
+}
+
+/// Make user techniques available to `all_techniques()`. Only the first
+/// call has any effect, and only if the catalog hasn't been read yet.
+pub fn install_user_techniques(techniques: Vec<Technique>) {
+    let mut catalog = builtin_techniques().to_vec();
+    catalog.extend(techniques);
+    let _ = CATALOG.set(catalog);
+}
+
+/// Owned mirror of `Technique` for user definitions, one `[[technique]]`
+/// table each
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UserTechnique {
+    id: String,
+    name: String,
+    phases: Vec<UserPhase>,
+    #[serde(default)]
+    tagline: String,
+    #[serde(default)]
+    description: String,
+    /// Defaults to the phase durations joined with dashes
+    pattern: Option<String>,
+    #[serde(default)]
+    purpose: String,
+    #[serde(default)]
+    use_case: String,
+    #[serde(default)]
+    source: String,
+    /// "#rrggbb"
+    color: Option<String>,
+    default_cycles: Option<u32>,
+    category: Option<String>,
+    difficulty: Option<String>,
+    inhale_route: Option<String>,
+    exhale_route: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UserPhase {
+    name: String,
+    secs: f64,
+    instruction: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserTechniqueFile {
+    #[serde(default, rename = "technique")]
+    techniques: Vec<UserTechnique>,
+}
+
+/// Seconds per phase from a dash-separated pattern like "4-7-8"
+fn parse_durations(pattern: &str) -> Result<Vec<f64>> {
+    pattern
+        .split('-')
+        .map(|part| {
+            let secs: f64 = part.trim().parse().with_context(|| {
+                format!("invalid pattern '{}': '{}' is not a number of seconds", pattern, part)
+            })?;
+            if !secs.is_finite() || secs <= 0.0 {
+                bail!("invalid pattern '{}': durations must be more than 0 seconds", pattern);
+            }
+            Ok(secs)
+        })
+        .collect()
+}
+
+/// Lowercase with separators removed, so "Hold After Exhale",
+/// "hold-after-exhale" and "HoldAfterExhale" all match
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn parse_phase_name(text: &str) -> Option<PhaseName> {
+    match normalize(text).as_str() {
+        "inhale" => Some(PhaseName::Inhale),
+        "hold" => Some(PhaseName::Hold),
+        "exhale" => Some(PhaseName::Exhale),
+        "holdafterexhale" | "holdempty" => Some(PhaseName::HoldAfterExhale),
+        _ => None,
+    }
+}
+
+fn parse_category(text: &str) -> Option<Category> {
+    match normalize(text).as_str() {
+        "focus" => Some(Category::Focus),
+        "calm" => Some(Category::Calm),
+        "sleep" => Some(Category::Sleep),
+        "energy" => Some(Category::Energy),
+        "recovery" => Some(Category::Recovery),
+        _ => None,
+    }
+}
+
+fn parse_difficulty(text: &str) -> Option<Difficulty> {
+    match normalize(text).as_str() {
+        "beginner" => Some(Difficulty::Beginner),
+        "intermediate" => Some(Difficulty::Intermediate),
+        "advanced" => Some(Difficulty::Advanced),
+        _ => None,
+    }
+}
+
+fn parse_route(text: &str) -> Option<BreathRoute> {
+    match normalize(text).as_str() {
+        "nose" => Some(BreathRoute::Nose),
+        "mouth" => Some(BreathRoute::Mouth),
+        _ => None,
+    }
+}
+
+impl UserTechnique {
+    fn into_technique(self) -> Result<Technique> {
+        let id = self.id.trim().to_string();
+        if id.is_empty() || id.contains(char::is_whitespace) || id.contains(',') || id.contains(':') {
+            bail!("invalid technique id '{}': use letters, digits and dashes", self.id);
+        }
+        if builtin_techniques().iter().any(|t| t.id == id) {
+            bail!("technique '{}' is already built in; pick another id", id);
+        }
+        if self.phases.is_empty() {
+            bail!("technique '{}' has no phases", id);
+        }
+
+        let mut names = Vec::with_capacity(self.phases.len());
+        for (i, phase) in self.phases.iter().enumerate() {
+            let name = parse_phase_name(&phase.name).with_context(|| {
+                format!(
+                    "technique '{}', phase {}: unknown phase name '{}' (expected inhale, hold, exhale or hold-after-exhale)",
+                    id, i + 1, phase.name
+                )
+            })?;
+            if !phase.secs.is_finite() || phase.secs <= 0.0 {
+                bail!("technique '{}', phase {}: duration must be more than zero seconds", id, i + 1);
+            }
+            names.push(name);
+        }
+
+        // Built like any ad-hoc technique, then dressed with the user's fields
+        let durations: Vec<String> = self.phases.iter().map(|p| p.secs.to_string()).collect();
+        let base = Technique::from_pattern(&self.name, &durations.join("-"), &names)?;
+        let phases = base
+            .phases
+            .iter()
+            .zip(self.phases)
+            .map(|(phase, user)| Phase {
+                // Your own cue, in any language, or the standard one
+                instruction: match user.instruction.as_deref().map(str::trim) {
+                    Some(text) if !text.is_empty() => text.to_string().into(),
+                    _ => phase.instruction.clone(),
+                },
+                ..phase.clone()
+            })
+            .collect();
+
+        let color = match self.color {
+            Some(text) => match crate::theme::parse_hex_color(&text) {
+                Some(ratatui::style::Color::Rgb(r, g, b)) => TechniqueColor::new(r, g, b),
+                _ => bail!("technique '{}': invalid color '{}' (expected \"#rrggbb\")", id, text),
+            },
+            None => base.color,
+        };
+        let category = match self.category {
+            Some(text) => parse_category(&text)
+                .with_context(|| format!("technique '{}': unknown category '{}'", id, text))?,
+            None => base.category,
+        };
+        let difficulty = match self.difficulty {
+            Some(text) => parse_difficulty(&text)
+                .with_context(|| format!("technique '{}': unknown difficulty '{}'", id, text))?,
+            None => base.difficulty,
+        };
+        let route = |text: Option<String>| -> Result<Option<BreathRoute>> {
+            match text {
+                Some(text) => parse_route(&text)
+                    .map(Some)
+                    .with_context(|| format!("technique '{}': unknown breathing route '{}'", id, text)),
+                None => Ok(None),
+            }
+        };
+        let inhale_route = route(self.inhale_route)?;
+        let exhale_route = route(self.exhale_route)?;
+
+        let default_cycles = self.default_cycles.unwrap_or(base.default_cycles);
+        if default_cycles == 0 {
+            bail!("technique '{}': default_cycles must be at least 1", id);
+        }
+        let pattern = self.pattern.map_or(base.pattern, Cow::Owned);
+
+        Ok(Technique {
+            id: id.into(),
+            name: base.name,
+            tagline: self.tagline.into(),
+            description: self.description.into(),
+            pattern,
+            phases,
+            purpose: self.purpose.into(),
+            use_case: self.use_case.into(),
+            source: self.source.into(),
+            color,
+            default_cycles,
+            category,
+            difficulty,
+            inhale_route,
+            exhale_route,
+            safety_note: None,
+        })
+    }
+}
+
+/// Parse user technique definitions from TOML text
+pub fn parse_user_techniques(text: &str) -> Result<Vec<Technique>> {
+    let file: UserTechniqueFile = toml::from_str(text)?;
+    let mut techniques: Vec<Technique> = Vec::with_capacity(file.techniques.len());
+    for user in file.techniques {
+        let technique = user.into_technique()?;
+        if techniques.iter().any(|t| t.id == technique.id) {
+            bail!("technique '{}' is defined more than once", technique.id);
+        }
+        techniques.push(technique);
+    }
+    Ok(techniques)
+}
+
+/// Load user technique definitions from a TOML file
+pub fn load_user_techniques(path: &Path) -> Result<Vec<Technique>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("couldn't read {}", path.display()))?;
+    parse_user_techniques(&text).with_context(|| format!("invalid techniques in {}", path.display()))
+}
+
+/// Everything wrong with one technique's definition: a pattern string that
+/// doesn't match the phase durations, empty or non-positive phases, blank
+/// instructions
+pub fn technique_problems(technique: &Technique) -> Vec<String> {
+    let mut problems = Vec::new();
+    if technique.phases.is_empty() {
+        problems.push("has no phases".to_string());
+    }
+    for (i, phase) in technique.phases.iter().enumerate() {
+        if !phase.duration_secs.is_finite() || phase.duration_secs <= 0.0 {
+            problems.push(format!("phase {} lasts {}s", i + 1, phase.duration_secs));
+        }
+        if phase.instruction.trim().is_empty() {
+            problems.push(format!("phase {} has no instruction", i + 1));
+        }
+    }
+    let cycle = technique.cycle_duration();
+    if !cycle.is_finite() || cycle <= 0.0 {
+        problems.push("cycle lasts no time at all".to_string());
+    }
+
+    let pattern: Option<Vec<f64>> =
+        technique.pattern.split('-').map(|part| part.trim().parse().ok()).collect();
+    let matches = pattern.is_some_and(|durations| {
+        durations.len() == technique.phases.len()
+            && durations
+                .iter()
+                .zip(&technique.phases)
+                .all(|(secs, phase)| (secs - phase.duration_secs).abs() < 1e-9)
+    });
+    if !matches {
+        let durations: Vec<String> =
+            technique.phases.iter().map(|p| p.duration_secs.to_string()).collect();
+        problems.push(format!(
+            "pattern '{}' doesn't match the phases ({})",
+            technique.pattern,
+            durations.join("-")
+        ));
+    }
+    problems
+}
+
+/// Check every technique, with problems listed per id; ids used more than
+/// once are reported on each
+pub fn validate_techniques(techniques: &[Technique]) -> Vec<(&str, Vec<String>)> {
+    techniques
+        .iter()
+        .map(|technique| {
+            let mut problems = technique_problems(technique);
+            if techniques.iter().filter(|t| t.id == technique.id).count() > 1 {
+                problems.push("id is used more than once".to_string());
+            }
+            (technique.id.as_ref(), problems)
+        })
+        .collect()
+}
+
 pub fn get_technique(id: &str) -> Option<Technique> {
-    all_techniques().into_iter().find(|t| t.id == id)
+    all_techniques().iter().find(|t| t.id == id).cloned()
+}
+
+/// Parse a comma-separated technique list such as "energize:10,coherent:5".
+/// The cycle count after each id is optional and defaults to
+/// `default_cycles`, or else the technique's own default.
+pub fn parse_technique_list(
+    spec: &str,
+    default_cycles: Option<u32>,
+) -> Result<Vec<(Technique, u32)>, String> {
+    let mut plan = Vec::new();
+
+    for item in spec.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+        let (id, cycles) = match item.split_once(':') {
+            Some((id, cycles)) => (id.trim(), Some(cycles.trim())),
+            None => (item, None),
+        };
+
+        let technique = get_technique(id)
+            .ok_or_else(|| format!("unknown technique '{}' (see `breathe list`)", id))?;
+        let cycles = match cycles {
+            Some(text) => match text.parse::<u32>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(format!("invalid cycle count '{}' for {}", text, id)),
+            },
+            None => default_cycles.unwrap_or(technique.default_cycles),
+        };
+
+        plan.push((technique, cycles));
+    }
+
+    if plan.is_empty() {
+        return Err("no techniques given".to_string());
+    }
+    Ok(plan)
+}
+
+pub fn get_techniques_by_category(category: Category) -> Vec<&'static Technique> {
+    all_techniques().iter().filter(|t| t.category == category).collect()
+}
+
+/// A technique picked uniformly at random, optionally from one category
+pub fn random_technique(category: Option<Category>, seed: u64) -> Option<Technique> {
+    let pool = match category {
+        Some(category) => get_techniques_by_category(category),
+        None => all_techniques().iter().collect(),
+    };
+    if pool.is_empty() {
+        return None;
+    }
+    let index = (Rng::new(seed).next_u64() % pool.len() as u64) as usize;
+    Some(pool[index].clone())
 }
 
-#[allow(dead_code)]
-pub fn get_techniques_by_category(category: Category) -> Vec<Technique> {
-    all_techniques().into_iter().filter(|t| t.category == category).collect()
+/// The technique of the day: the same for everyone on a given date, moving
+/// on once a day. Only built-ins take part, so custom techniques don't
+/// shift the rotation.
+pub fn daily_technique(date: NaiveDate) -> Technique {
+    let pool = builtin_techniques();
+    let index = date.num_days_from_ce().rem_euclid(pool.len() as i32) as usize;
+    pool[index].clone()
 }
 
-#[allow(dead_code)]
 pub fn all_categories() -> Vec<Category> {
     vec![
         Category::Focus,
@@ -484,3 +1118,193 @@ pub fn all_categories() -> Vec<Category> {
         Category::Recovery,
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inhales_per_cycle() {
+        assert_eq!(get_technique("box").unwrap().inhales_per_cycle(), 1);
+        assert_eq!(get_technique("sigh").unwrap().inhales_per_cycle(), 2);
+    }
+
+    #[test]
+    fn test_format_secs_keeps_fractions() {
+        assert_eq!(format_secs(4.0), "4");
+        assert_eq!(format_secs(2.5), "2.5");
+    }
+
+    #[test]
+    fn test_user_techniques_parse_and_validate() {
+        let techniques = parse_user_techniques(
+            r##"
+            [[technique]]
+            id = "triangle"
+            name = "Triangle Breathing"
+            color = "#22c55e"
+            category = "focus"
+            phases = [
+                { name = "inhale", secs = 4 },
+                { name = "hold", secs = 4, instruction = " Quieto " },
+                { name = "Hold After Exhale", secs = 4, instruction = "" },
+            ]
+            "##,
+        )
+        .unwrap();
+        assert_eq!(techniques.len(), 1);
+        let triangle = &techniques[0];
+        assert_eq!(triangle.pattern, "4-4-4");
+        assert_eq!(triangle.category, Category::Focus);
+        assert_eq!(triangle.phases[0].instruction, PhaseName::Inhale.default_instruction());
+        assert_eq!(triangle.phases[1].instruction, "Quieto");
+        assert_eq!(triangle.phases[2].name, PhaseName::HoldAfterExhale);
+        assert_eq!(triangle.phases[2].instruction, PhaseName::HoldAfterExhale.default_instruction());
+
+        let bad_phase = r#"[[technique]]
+            id = "x"
+            name = "X"
+            phases = [{ name = "sniff", secs = 2 }]"#;
+        let error = format!("{:#}", parse_user_techniques(bad_phase).unwrap_err());
+        assert!(error.contains("unknown phase name 'sniff'"), "{error}");
+
+        let zero = r#"[[technique]]
+            id = "x"
+            name = "X"
+            phases = [{ name = "inhale", secs = 0 }]"#;
+        assert!(parse_user_techniques(zero).is_err());
+
+        let clash = r#"[[technique]]
+            id = "box"
+            name = "My Box"
+            phases = [{ name = "inhale", secs = 4 }]"#;
+        assert!(parse_user_techniques(clash).is_err());
+    }
+
+    #[test]
+    fn test_category_from_str() {
+        assert_eq!("Sleep".parse::<Category>().unwrap(), Category::Sleep);
+        let err = "naps".parse::<Category>().unwrap_err().to_string();
+        assert!(err.contains("focus, calm, sleep, energy or recovery"));
+        assert_eq!(get_techniques_by_category(Category::Sleep).len(), 3);
+    }
+
+    #[test]
+    fn test_random_technique_is_reproducible() {
+        let first = random_technique(None, 42).unwrap();
+        assert_eq!(random_technique(None, 42).unwrap().id, first.id);
+
+        let sleepy = random_technique(Some(Category::Sleep), 7).unwrap();
+        assert_eq!(sleepy.category, Category::Sleep);
+
+        let picked: std::collections::HashSet<_> =
+            (0..50).map(|seed| random_technique(None, seed).unwrap().id).collect();
+        assert!(picked.len() > 1);
+    }
+
+    #[test]
+    fn test_daily_technique_rotates_once_a_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let count = builtin_techniques().len() as u64;
+        assert_eq!(daily_technique(day).id, daily_technique(day).id);
+        assert_ne!(daily_technique(day).id, daily_technique(day.succ_opt().unwrap()).id);
+        assert_eq!(
+            daily_technique(day).id,
+            daily_technique(day + chrono::Days::new(count)).id
+        );
+    }
+
+    #[test]
+    fn test_only_phase_keeps_matching_phases() {
+        let exhales = get_technique("478").unwrap().only_phase(PhaseName::Exhale).unwrap();
+        assert_eq!(exhales.pattern, "8");
+        assert_eq!(exhales.phases.len(), 1);
+        assert_eq!(exhales.phases[0].name, PhaseName::Exhale);
+
+        assert_eq!("hold-after-exhale".parse::<PhaseName>().unwrap(), PhaseName::HoldAfterExhale);
+        assert!("sneeze".parse::<PhaseName>().is_err());
+        let error = get_technique("478").unwrap().only_phase(PhaseName::HoldAfterExhale).unwrap_err();
+        assert!(error.to_string().contains("no rest phase"));
+    }
+
+    #[test]
+    fn test_pattern_override_keeps_phase_names() {
+        let slow_box = get_technique("box").unwrap().with_pattern("5-5-5-5.5").unwrap();
+        assert_eq!(slow_box.pattern, "5-5-5-5.5");
+        assert_eq!(slow_box.phases[3].duration_secs, 5.5);
+        assert_eq!(slow_box.phases[1].name, PhaseName::Hold);
+
+        let box_technique = get_technique("box").unwrap();
+        assert!(box_technique.with_pattern("4-7-8").unwrap_err().to_string().contains("4 phases"));
+        assert!(box_technique.with_pattern("4-x-4-4").unwrap_err().to_string().contains("'x'"));
+        assert!(box_technique.with_pattern("4-0-4-4").is_err());
+    }
+
+    #[test]
+    fn test_from_pattern_builds_a_valid_technique() {
+        use PhaseName::*;
+        let triangle = Technique::from_pattern("Quick Triangle", "4-4-6", &[Inhale, Hold, Exhale]).unwrap();
+        assert_eq!(triangle.id, "quick-triangle");
+        assert_eq!(triangle.cycle_duration(), 14.0);
+        assert_eq!(triangle.phases[1].instruction, Hold.default_instruction());
+        assert!(technique_problems(&triangle).is_empty());
+
+        assert!(Technique::from_pattern("x", "4-4", &[Inhale, Hold, Exhale]).is_err());
+        assert!(Technique::from_pattern("x", "4-0-4", &[Inhale, Hold, Exhale]).is_err());
+    }
+
+    #[test]
+    fn test_timeline_runs_end_to_end() {
+        let technique = get_technique("478").unwrap();
+        let timeline = technique.timeline();
+        assert_eq!(timeline.len(), technique.phases.len());
+        assert_eq!(timeline[0].0, 0.0);
+        assert_eq!(timeline[1].0, timeline[0].1);
+        assert_eq!(timeline.last().unwrap().1, technique.cycle_duration());
+    }
+
+    #[test]
+    fn test_catalog_is_built_once() {
+        assert!(std::ptr::eq(all_techniques(), all_techniques()));
+        assert!(std::ptr::eq(builtin_techniques(), builtin_techniques()));
+        assert!(all_techniques().len() >= builtin_techniques().len());
+    }
+
+    #[test]
+    fn test_builtin_techniques_are_valid() {
+        for (id, problems) in validate_techniques(builtin_techniques()) {
+            assert!(problems.is_empty(), "{}: {:?}", id, problems);
+        }
+    }
+
+    #[test]
+    fn test_validation_catches_bad_definitions() {
+        let mut broken = get_technique("box").unwrap();
+        broken.pattern = "4-4-4".into();
+        broken.phases[1].instruction = " ".into();
+        broken.phases[2].duration_secs = 0.0;
+        let problems = technique_problems(&broken);
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+
+        let twice = vec![get_technique("box").unwrap(), get_technique("box").unwrap()];
+        assert!(validate_techniques(&twice).iter().all(|(_, problems)| problems.len() == 1));
+    }
+
+    #[test]
+    fn test_parse_technique_list() {
+        let plan = parse_technique_list("energize:10, coherent", None).unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!((plan[0].0.id.as_ref(), plan[0].1), ("energize", 10));
+        assert_eq!(plan[1].1, get_technique("coherent").unwrap().default_cycles);
+
+        // A shared default only fills in the counts that weren't given
+        let plan = parse_technique_list("box,coherent:2,478", Some(3)).unwrap();
+        let cycles: Vec<u32> = plan.iter().map(|(_, cycles)| *cycles).collect();
+        assert_eq!(cycles, [3, 2, 3]);
+
+        assert!(parse_technique_list("", None).is_err());
+        assert!(parse_technique_list("nope:3", None).is_err());
+        assert!(parse_technique_list("box:0", None).is_err());
+        assert!(parse_technique_list("box:x", None).is_err());
+    }
+}
@@ -2,31 +2,106 @@
 
 use crate::animation::lerp_u8;
 use crate::techniques::PhaseName;
+use anyhow::Context;
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The built-in themes, selectable with --theme or `display.theme`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// Phase color palettes; the colorblind-friendly ones keep the phases apart
+/// for each kind of color vision deficiency
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteName {
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl FromStr for PaletteName {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Self> {
+        match text.to_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "deuteranopia" => Ok(Self::Deuteranopia),
+            "protanopia" => Ok(Self::Protanopia),
+            "tritanopia" => Ok(Self::Tritanopia),
+            _ => anyhow::bail!(
+                "unknown palette '{}' (expected default, deuteranopia, protanopia or tritanopia)",
+                text
+            ),
+        }
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Self> {
+        match text.to_lowercase().as_str() {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            _ => anyhow::bail!("unknown theme '{}' (expected dark or light)", text),
+        }
+    }
+}
 
 /// Main theme configuration
-#[derive(Debug, Clone)]
+///
+/// Serializes to TOML with `#RRGGBB` colors; any section missing from a
+/// theme file falls back to the dark theme.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Theme {
+    #[serde(with = "hex_color")]
     pub background: Color,
+    #[serde(with = "hex_color")]
     pub background_dark: Color,
     pub phase_colors: PhaseColorScheme,
     pub ui: UiColors,
+    /// How much brighter the visualizer draws the phase colors than the
+    /// text and bars (1.0 = the same)
+    pub vibrance: f64,
 }
 
 /// Colors for UI elements
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UiColors {
+    #[serde(with = "hex_color")]
     pub text_primary: Color,
+    #[serde(with = "hex_color")]
     pub text_secondary: Color,
+    #[serde(with = "hex_color")]
     pub text_muted: Color,
+    #[serde(with = "hex_color")]
     pub accent: Color,
+    #[serde(with = "hex_color")]
     pub border: Color,
+    /// Fill for cards such as the technique summary
+    #[serde(with = "hex_color")]
+    pub surface: Color,
+    #[serde(with = "hex_color")]
     pub success: Color,
+    #[serde(with = "hex_color")]
     pub warning: Color,
 }
 
 /// Color scheme for each breathing phase
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhaseColorScheme {
     pub inhale: PhaseColors,
     pub hold: PhaseColors,
@@ -35,13 +110,19 @@ pub struct PhaseColorScheme {
 }
 
 /// Colors for a single phase
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PhaseColors {
+    #[serde(with = "hex_color")]
     pub primary: Color,      // Main color for the phase
+    #[serde(with = "hex_color")]
     pub glow: Color,         // Outer glow/halo color
+    #[serde(with = "hex_color")]
     pub text: Color,         // Text label color
+    #[serde(with = "hex_color")]
     pub particle: Color,     // Particle color
+    #[serde(with = "hex_color")]
     pub core: Color,         // Inner core glow
+    #[serde(with = "hex_color")]
     pub ambient: Color,      // Background ambient color
 }
 
@@ -63,6 +144,20 @@ impl PhaseColors {
             ambient,
         }
     }
+
+    /// A full set from one main color: lighter shades for the glow,
+    /// particles and core, a deep shade for the ambient background
+    pub fn from_primary(primary: Color) -> Self {
+        let white = Color::Rgb(255, 255, 255);
+        Self::new(
+            primary,
+            blend_color(primary, white, 0.3),
+            primary,
+            blend_color(primary, white, 0.45),
+            blend_color(primary, white, 0.65),
+            blend_color(primary, Color::Rgb(0, 0, 0), 0.7),
+        )
+    }
 }
 
 impl Default for Theme {
@@ -78,18 +173,119 @@ impl Theme {
             background: Color::Rgb(10, 22, 40),
             background_dark: Color::Rgb(5, 11, 20),
             phase_colors: PhaseColorScheme::default(),
+            ui: UiColors::default(),
+            vibrance: 1.2,
+        }
+    }
+
+    /// Light backgrounds with deeper phase colors, for light terminals
+    pub fn light() -> Self {
+        Self {
+            background: Color::Rgb(244, 246, 250),
+            background_dark: Color::Rgb(228, 232, 240),
+            phase_colors: PhaseColorScheme {
+                inhale: PhaseColors::new(
+                    Color::Rgb(37, 99, 175),    // Primary: Deep blue
+                    Color::Rgb(74, 144, 217),   // Glow: Arctic blue
+                    Color::Rgb(30, 80, 150),    // Text: Navy
+                    Color::Rgb(59, 130, 205),   // Particle: Blue
+                    Color::Rgb(20, 70, 140),    // Core: Dark blue
+                    Color::Rgb(205, 222, 242),  // Ambient: Pale blue
+                ),
+                hold: PhaseColors::new(
+                    Color::Rgb(161, 120, 10),   // Primary: Dark gold
+                    Color::Rgb(201, 162, 39),   // Glow: Gold
+                    Color::Rgb(130, 95, 5),     // Text: Bronze
+                    Color::Rgb(190, 145, 25),   // Particle: Amber
+                    Color::Rgb(120, 85, 0),     // Core: Deep amber
+                    Color::Rgb(242, 232, 200),  // Ambient: Pale gold
+                ),
+                exhale: PhaseColors::new(
+                    Color::Rgb(109, 62, 216),   // Primary: Deep purple
+                    Color::Rgb(139, 92, 246),   // Glow: Purple
+                    Color::Rgb(90, 50, 180),    // Text: Indigo
+                    Color::Rgb(124, 80, 230),   // Particle: Violet
+                    Color::Rgb(80, 40, 170),    // Core: Dark violet
+                    Color::Rgb(225, 215, 245),  // Ambient: Pale lavender
+                ),
+                hold_empty: PhaseColors::new(
+                    Color::Rgb(71, 85, 105),    // Primary: Dark slate
+                    Color::Rgb(100, 116, 139),  // Glow: Slate
+                    Color::Rgb(51, 65, 85),     // Text: Charcoal
+                    Color::Rgb(90, 105, 125),   // Particle: Gray
+                    Color::Rgb(45, 55, 72),     // Core: Deep slate
+                    Color::Rgb(220, 225, 232),  // Ambient: Pale gray
+                ),
+            },
+            ui: UiColors {
+                text_primary: Color::Rgb(15, 23, 42),
+                text_secondary: Color::Rgb(71, 85, 105),
+                text_muted: Color::Rgb(120, 132, 150),
+                accent: Color::Rgb(37, 99, 175),
+                border: Color::Rgb(203, 213, 225),
+                surface: Color::Rgb(255, 255, 255),
+                success: Color::Rgb(21, 128, 61),
+                warning: Color::Rgb(161, 120, 10),
+            },
+            // Brightening would wash the colors out against a light canvas
+            vibrance: 1.0,
+        }
+    }
+
+    /// One of the built-in themes by name
+    pub fn named(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+        }
+    }
+
+    /// White and gray only, for NO_COLOR
+    pub fn monochrome() -> Self {
+        let phase = |primary: Color, core: Color| {
+            PhaseColors::new(primary, Color::Gray, primary, Color::Gray, core, Color::DarkGray)
+        };
+        Self {
+            background: Color::Reset,
+            background_dark: Color::Reset,
+            phase_colors: PhaseColorScheme {
+                inhale: phase(Color::White, Color::White),
+                hold: phase(Color::Gray, Color::White),
+                exhale: phase(Color::White, Color::Gray),
+                hold_empty: phase(Color::DarkGray, Color::Gray),
+            },
             ui: UiColors {
                 text_primary: Color::White,
-                text_secondary: Color::Rgb(148, 163, 184),
-                text_muted: Color::Rgb(100, 116, 139),
-                accent: Color::Rgb(74, 144, 217),
-                border: Color::Rgb(30, 41, 59),
-                success: Color::Rgb(34, 197, 94),
-                warning: Color::Rgb(201, 162, 39),
+                text_secondary: Color::Gray,
+                text_muted: Color::DarkGray,
+                accent: Color::White,
+                border: Color::DarkGray,
+                surface: Color::Reset,
+                success: Color::White,
+                warning: Color::Gray,
             },
+            vibrance: 1.0,
         }
     }
 
+    /// Parse a theme from TOML text
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Load a theme file
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read theme file {}", path.display()))?;
+        Self::from_toml(&text)
+            .with_context(|| format!("invalid theme file {}", path.display()))
+    }
+
+    /// Serialize the theme as TOML, e.g. as a template for a theme file
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("theme always serializes")
+    }
+
     /// Get phase colors for a specific phase
     pub fn get_phase_colors(&self, phase: PhaseName) -> &PhaseColors {
         match phase {
@@ -101,6 +297,21 @@ impl Theme {
     }
 }
 
+impl Default for UiColors {
+    fn default() -> Self {
+        Self {
+            text_primary: Color::White,
+            text_secondary: Color::Rgb(148, 163, 184),
+            text_muted: Color::Rgb(100, 116, 139),
+            accent: Color::Rgb(74, 144, 217),
+            border: Color::Rgb(30, 41, 59),
+            surface: Color::Rgb(15, 30, 50),
+            success: Color::Rgb(34, 197, 94),
+            warning: Color::Rgb(201, 162, 39),
+        }
+    }
+}
+
 impl Default for PhaseColorScheme {
     fn default() -> Self {
         Self {
@@ -147,6 +358,59 @@ impl Default for PhaseColorScheme {
     }
 }
 
+impl PhaseColorScheme {
+    fn from_primaries(inhale: Color, hold: Color, exhale: Color, hold_empty: Color) -> Self {
+        Self {
+            inhale: PhaseColors::from_primary(inhale),
+            hold: PhaseColors::from_primary(hold),
+            exhale: PhaseColors::from_primary(exhale),
+            hold_empty: PhaseColors::from_primary(hold_empty),
+        }
+    }
+
+    /// Red-green (green-weak): sky blue, yellow and vermillion, which also
+    /// differ clearly in brightness
+    pub fn deuteranopia() -> Self {
+        Self::from_primaries(
+            Color::Rgb(86, 180, 233),
+            Color::Rgb(240, 228, 66),
+            Color::Rgb(213, 94, 0),
+            Color::Rgb(185, 185, 185),
+        )
+    }
+
+    /// Red-green (red-weak): reds read as dark, so the phases stay on the
+    /// blue-yellow axis with a light and a deep blue
+    pub fn protanopia() -> Self {
+        Self::from_primaries(
+            Color::Rgb(86, 180, 233),
+            Color::Rgb(240, 228, 66),
+            Color::Rgb(30, 100, 210),
+            Color::Rgb(125, 125, 125),
+        )
+    }
+
+    /// Blue-yellow: cyan, red and pink
+    pub fn tritanopia() -> Self {
+        Self::from_primaries(
+            Color::Rgb(0, 190, 200),
+            Color::Rgb(235, 60, 70),
+            Color::Rgb(245, 160, 200),
+            Color::Rgb(160, 160, 160),
+        )
+    }
+
+    /// Phase colors for a palette
+    pub fn named(name: PaletteName) -> Self {
+        match name {
+            PaletteName::Default => Self::default(),
+            PaletteName::Deuteranopia => Self::deuteranopia(),
+            PaletteName::Protanopia => Self::protanopia(),
+            PaletteName::Tritanopia => Self::tritanopia(),
+        }
+    }
+}
+
 /// Blend between two phase color sets
 pub fn blend_phase_colors(from: &PhaseColors, to: &PhaseColors, t: f64) -> PhaseColors {
     PhaseColors {
@@ -189,7 +453,6 @@ pub fn with_opacity(color: Color, opacity: f64) -> Color {
 }
 
 /// Brighten a color by a factor (1.0 = no change, >1.0 = brighter)
-#[allow(dead_code)]
 pub fn brighten(color: Color, factor: f64) -> Color {
     match color {
         Color::Rgb(r, g, b) => {
@@ -232,7 +495,196 @@ pub fn technique_to_phase_colors(r: u8, g: u8, b: u8) -> PhaseColors {
     }
 }
 
-/// Get the default theme
+/// Get the default theme: monochrome when NO_COLOR is set, dark otherwise
 pub fn default_theme() -> Theme {
-    Theme::dark()
+    if no_color() {
+        Theme::monochrome()
+    } else {
+        Theme::dark()
+    }
+}
+
+/// Whether the user asked for no color (https://no-color.org: any
+/// non-empty NO_COLOR value)
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Perceived brightness of an RGB color, 0-255
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    (0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64).round() as u8
+}
+
+/// The same brightness in gray
+pub fn grayscale(color: Color) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            let l = luma(r, g, b);
+            Color::Rgb(l, l, l)
+        }
+        _ => color,
+    }
+}
+
+/// Map an RGB color to the nearest basic gray, so no truecolor escapes are
+/// emitted. Dark backgrounds fall back to the terminal's own.
+pub fn to_monochrome(color: Color, background: bool) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    match luma(r, g, b) {
+        0..=47 if background => Color::Reset,
+        0..=47 => Color::Black,
+        48..=109 => Color::DarkGray,
+        110..=179 => Color::Gray,
+        _ => Color::White,
+    }
+}
+
+/// Parse a `#RRGGBB` hex color
+pub fn parse_hex_color(text: &str) -> Option<Color> {
+    let hex = text.trim().strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// RGB components of a color, mapping common named colors to their usual
+/// values. Returns None for colors with no fixed RGB (e.g. Reset).
+pub fn color_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Black => Some((0, 0, 0)),
+        Color::White => Some((255, 255, 255)),
+        Color::Gray => Some((128, 128, 128)),
+        Color::DarkGray => Some((64, 64, 64)),
+        _ => None,
+    }
+}
+
+/// Format a color as `#rrggbb` (named colors map to their usual RGB values)
+pub fn to_hex_color(color: Color) -> String {
+    let (r, g, b) = color_rgb(color).unwrap_or((255, 255, 255));
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Night-vision friendly version of a color: keeps its lightness but
+/// shifts the hue to deep red, then scales by `brightness`
+pub fn night_shift(color: Color, brightness: f64) -> Color {
+    let Some((r, g, b)) = color_rgb(color) else {
+        return color;
+    };
+    let luminance = 0.3 * r as f64 + 0.59 * g as f64 + 0.11 * b as f64;
+    with_opacity(
+        Color::Rgb(
+            luminance.min(255.0) as u8,
+            (luminance * 0.2) as u8,
+            (luminance * 0.08) as u8,
+        ),
+        brightness,
+    )
+}
+
+/// Serde adapter storing colors as `#RRGGBB` strings
+mod hex_color {
+    use super::{parse_hex_color, to_hex_color};
+    use ratatui::style::Color;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex_color(*color))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_hex_color(&text)
+            .ok_or_else(|| D::Error::custom(format!("invalid color {:?}, expected #RRGGBB", text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_monochrome_maps_rgb_to_basic_grays() {
+        assert_eq!(to_monochrome(Color::Rgb(10, 22, 40), true), Color::Reset);
+        assert_eq!(to_monochrome(Color::Rgb(10, 22, 40), false), Color::Black);
+        assert_eq!(to_monochrome(Color::Rgb(74, 144, 217), false), Color::Gray);
+        assert_eq!(to_monochrome(Color::Rgb(255, 240, 180), false), Color::White);
+        assert_eq!(to_monochrome(Color::DarkGray, false), Color::DarkGray);
+        assert_eq!(grayscale(Color::Rgb(255, 0, 0)), Color::Rgb(76, 76, 76));
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#4a90d9"), Some(Color::Rgb(74, 144, 217)));
+        assert_eq!(parse_hex_color("#FFFFFF"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(parse_hex_color("4a90d9"), None);
+        assert_eq!(parse_hex_color("#4a90d"), None);
+        assert_eq!(parse_hex_color("#4a90zz"), None);
+        assert_eq!(parse_hex_color("#ééé"), None);
+    }
+
+    #[test]
+    fn test_exported_theme_round_trips() {
+        let exported = Theme::dark().to_toml();
+        let theme = Theme::from_toml(&exported).unwrap();
+        assert_eq!(theme.ui.accent, Color::Rgb(74, 144, 217));
+        assert_eq!(theme.ui.text_primary, Color::Rgb(255, 255, 255));
+        assert_eq!(theme.phase_colors.exhale.primary, Color::Rgb(139, 92, 246));
+        assert_eq!(theme.vibrance, 1.2);
+    }
+
+    #[test]
+    fn test_partial_theme_falls_back_to_dark() {
+        let theme = Theme::from_toml("[ui]\naccent = \"#ff0000\"\n").unwrap();
+        assert_eq!(theme.ui.accent, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.ui.success, Color::Rgb(34, 197, 94));
+        assert_eq!(theme.background, Color::Rgb(10, 22, 40));
+    }
+
+    #[test]
+    fn test_theme_names() {
+        assert_eq!("light".parse::<ThemeName>().unwrap(), ThemeName::Light);
+        assert_eq!("Dark".parse::<ThemeName>().unwrap(), ThemeName::Dark);
+        assert!("sepia".parse::<ThemeName>().is_err());
+
+        // Light text on dark, dark text on light
+        let dark = Theme::named(ThemeName::Dark);
+        let light = Theme::named(ThemeName::Light);
+        assert_eq!(dark.ui.text_primary, Color::White);
+        assert_eq!(light.background, Color::Rgb(244, 246, 250));
+        assert_eq!(light.ui.text_primary, Color::Rgb(15, 23, 42));
+    }
+
+    #[test]
+    fn test_colorblind_palettes_keep_phases_apart() {
+        assert_eq!("Tritanopia".parse::<PaletteName>().unwrap(), PaletteName::Tritanopia);
+        assert!("rainbow".parse::<PaletteName>().is_err());
+
+        for name in [PaletteName::Deuteranopia, PaletteName::Protanopia, PaletteName::Tritanopia] {
+            let scheme = PhaseColorScheme::named(name);
+            let lumas: Vec<u8> = [scheme.inhale, scheme.hold, scheme.exhale, scheme.hold_empty]
+                .iter()
+                .map(|colors| match colors.primary {
+                    Color::Rgb(r, g, b) => luma(r, g, b),
+                    _ => unreachable!(),
+                })
+                .collect();
+            // Brightness alone separates every pair of phases
+            for i in 0..lumas.len() {
+                for j in i + 1..lumas.len() {
+                    assert!(lumas[i].abs_diff(lumas[j]) >= 10, "{:?}: {:?}", name, lumas);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_color_is_rejected() {
+        assert!(Theme::from_toml("background = \"blue\"\n").is_err());
+    }
 }
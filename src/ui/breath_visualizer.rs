@@ -6,7 +6,7 @@
 use crate::app::App;
 use crate::particles::ParticleType;
 use crate::techniques::PhaseName;
-use crate::theme::{blend_color, with_opacity};
+use crate::theme::{blend_color, brighten, grayscale, no_color, with_opacity, PhaseColors, Theme};
 use ratatui::{
     layout::Rect,
     style::Color,
@@ -15,40 +15,35 @@ use ratatui::{
 };
 use std::f64::consts::{PI, TAU};
 
-/// Get vibrant phase colors - much brighter than theme defaults
-fn get_vibrant_colors(phase: PhaseName) -> (Color, Color, Color) {
-    match phase {
-        PhaseName::Inhale => (
-            Color::Rgb(80, 180, 255),   // Bright sky blue - primary
-            Color::Rgb(150, 220, 255),  // Light cyan - glow
-            Color::Rgb(200, 240, 255),  // Near white blue - core
-        ),
-        PhaseName::Hold => (
-            Color::Rgb(255, 200, 50),   // Bright gold - primary
-            Color::Rgb(255, 230, 120),  // Light gold - glow
-            Color::Rgb(255, 250, 200),  // Near white gold - core
-        ),
-        PhaseName::Exhale => (
-            Color::Rgb(180, 100, 255),  // Vibrant purple - primary
-            Color::Rgb(210, 160, 255),  // Light purple - glow
-            Color::Rgb(240, 210, 255),  // Near white purple - core
-        ),
-        PhaseName::HoldAfterExhale => (
-            Color::Rgb(120, 160, 200),  // Steel blue - primary (brighter)
-            Color::Rgb(160, 195, 225),  // Light steel - glow (brighter)
-            Color::Rgb(200, 220, 240),  // Near white steel - core (brighter)
-        ),
+/// Brightening of the core color for the single brightest point, e.g. the
+/// center of the circle
+const HIGHLIGHT_BOOST: f64 = 1.5;
+
+/// Vibrant phase colors from the theme: the primary and particle shades
+/// brightened by the theme's vibrance, and the core
+fn get_vibrant_colors(theme: &Theme, phase: PhaseName) -> (Color, Color, Color) {
+    let colors = theme.get_phase_colors(phase);
+    let primary = brighten(colors.primary, theme.vibrance);
+    let glow = brighten(colors.particle, theme.vibrance);
+    let core = colors.core;
+
+    // NO_COLOR keeps the same brightness levels, in gray
+    if no_color() {
+        (grayscale(primary), grayscale(glow), grayscale(core))
+    } else {
+        (primary, glow, core)
     }
 }
 
 /// Blend colors between phases for smooth transitions
 fn blend_vibrant_colors(
+    theme: &Theme,
     from_phase: PhaseName,
     to_phase: PhaseName,
     t: f64,
 ) -> (Color, Color, Color) {
-    let (from_primary, from_glow, from_core) = get_vibrant_colors(from_phase);
-    let (to_primary, to_glow, to_core) = get_vibrant_colors(to_phase);
+    let (from_primary, from_glow, from_core) = get_vibrant_colors(theme, from_phase);
+    let (to_primary, to_glow, to_core) = get_vibrant_colors(theme, to_phase);
 
     (
         blend_color(from_primary, to_primary, t),
@@ -57,8 +52,26 @@ fn blend_vibrant_colors(
     )
 }
 
+/// Technique that is drawn as a box tracer instead of the breathing circle
+const BOX_TECHNIQUE_ID: &str = "box";
+
+/// The countdown rings cover at most the last this-many seconds of a phase
+const COUNTDOWN_SECS: f64 = 2.0;
+
+/// Particle brightness at the canvas edge, relative to the center
+const PARTICLE_EDGE_BRIGHTNESS: f64 = 0.45;
+
+/// Smallest canvas (in cells) the coordinate system can be mapped onto
+const MIN_CANVAS_SIZE: u16 = 2;
+
 /// FULL-SCREEN breathing visualizer
 pub fn render_breath_visualizer(frame: &mut Frame, app: &App, area: Rect) {
+    // Transient or extreme layouts can hand us a degenerate area, which would
+    // make the aspect ratio (and the canvas bounds) NaN or infinite
+    if area.width < MIN_CANVAS_SIZE || area.height < MIN_CANVAS_SIZE {
+        return;
+    }
+
     let scale = app.breath_scale();
     let phase = app.current_phase().name;
     let progress = app.phase_progress();
@@ -67,38 +80,70 @@ pub fn render_breath_visualizer(frame: &mut Frame, app: &App, area: Rect) {
     // Get vibrant colors (with transition blending)
     let transition_t = app.phase_transition_progress;
     let (primary, glow, core) = if transition_t < 1.0 {
-        if let Some(prev) = get_previous_phase(app) {
-            blend_vibrant_colors(prev, phase, transition_t)
+        if let Some(prev) = app.previous_phase() {
+            blend_vibrant_colors(&app.theme, prev, phase, transition_t)
         } else {
-            get_vibrant_colors(phase)
+            get_vibrant_colors(&app.theme, phase)
         }
     } else {
-        get_vibrant_colors(phase)
+        get_vibrant_colors(&app.theme, phase)
+    };
+
+    // Particles follow the same blended colors as the rest of the session UI
+    let particle_colors = app.get_blended_phase_colors();
+
+    // Box breathing gets its canonical visual: a dot tracing a square
+    let technique = app.current_technique();
+    let box_tracer = technique.id == BOX_TECHNIQUE_ID && technique.phases.len() == 4;
+    let phase_index = app.current_phase_index;
+
+    // Reduced motion keeps only the core guidance: the breathing circle
+    let calm = app.reduced_motion;
+
+    // End-of-phase countdown, tinted toward the upcoming phase's color
+    let countdown = if calm {
+        None
+    } else {
+        countdown_progress(progress, app.effective_phase_duration()).map(|t| {
+            let phases = &technique.phases;
+            let next = phases[(phase_index + 1) % phases.len()].name;
+            let (next_primary, _, _) = get_vibrant_colors(&app.theme, next);
+            (t, blend_color(glow, next_primary, 0.4 + t * 0.6))
+        })
     };
 
     // Calculate canvas bounds to fill the ENTIRE area
     let aspect = area.width as f64 / (area.height as f64 * 2.0);
     let y_range = 50.0; // Larger coordinate system
-    let x_range = y_range * aspect;
+    let x_range = (y_range * aspect).max(1.0);
 
-    // Rich dark background for high contrast - near black with slight blue tint
-    let bg_color = Color::Rgb(5, 8, 15);
+    // Deepest theme background, for contrast with the glow
+    let bg_color = app.theme.background_dark;
 
     let canvas = Canvas::default()
         .x_bounds([-x_range, x_range])
         .y_bounds([-y_range, y_range])
-        .marker(ratatui::symbols::Marker::Braille)
+        .marker(super::canvas_marker(app))
         .background_color(bg_color)
         .paint(move |ctx| {
             // ═══════════════════════════════════════════════════════════════
             // LAYER 1: BACKGROUND GRADIENT FIELD
             // ═══════════════════════════════════════════════════════════════
-            draw_background_field(ctx, x_range, y_range, time, scale, primary);
+            if !calm {
+                draw_background_field(ctx, x_range, y_range, time, scale, primary);
+            }
+
+            if box_tracer {
+                draw_box_tracer(ctx, y_range, phase_index, progress, primary, glow, core);
+                return;
+            }
 
             // ═══════════════════════════════════════════════════════════════
             // LAYER 2: MASSIVE PULSING RINGS (fills most of the screen)
             // ═══════════════════════════════════════════════════════════════
-            draw_massive_rings(ctx, x_range, y_range, time, scale, phase, primary, glow);
+            if !calm {
+                draw_massive_rings(ctx, x_range, y_range, time, scale, primary, glow);
+            }
 
             // ═══════════════════════════════════════════════════════════════
             // LAYER 3: BREATHING CIRCLE (the main visual)
@@ -108,17 +153,25 @@ pub fn render_breath_visualizer(frame: &mut Frame, app: &App, area: Rect) {
             // ═══════════════════════════════════════════════════════════════
             // LAYER 4: PHASE-SPECIFIC EFFECTS
             // ═══════════════════════════════════════════════════════════════
-            match phase {
-                PhaseName::Inhale => draw_inhale_effect(ctx, y_range, progress, time, primary, glow),
-                PhaseName::Exhale => draw_exhale_effect(ctx, y_range, progress, time, primary, glow),
-                PhaseName::Hold => draw_hold_effect(ctx, y_range, time, primary, glow, core),
-                PhaseName::HoldAfterExhale => draw_rest_effect(ctx, y_range, time, primary),
+            if !calm {
+                match phase {
+                    PhaseName::Inhale => draw_inhale_effect(ctx, y_range, progress, time, primary, glow),
+                    PhaseName::Exhale => draw_exhale_effect(ctx, y_range, progress, time, primary, glow),
+                    PhaseName::Hold => draw_hold_effect(ctx, y_range, time, primary, glow, core),
+                    PhaseName::HoldAfterExhale => draw_rest_effect(ctx, y_range, time, primary),
+                }
+            }
+
+            if let Some((t, color)) = countdown {
+                draw_countdown_rings(ctx, y_range, scale, t, color);
             }
 
             // ═══════════════════════════════════════════════════════════════
             // LAYER 5: PARTICLE STREAMS
             // ═══════════════════════════════════════════════════════════════
-            draw_particle_streams(ctx, app, y_range, primary, glow);
+            if !calm {
+                draw_particle_streams(ctx, app, y_range, &particle_colors);
+            }
 
             // ═══════════════════════════════════════════════════════════════
             // LAYER 6: BRIGHT CORE
@@ -129,16 +182,6 @@ pub fn render_breath_visualizer(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(canvas, area);
 }
 
-/// Get previous phase for transition blending
-fn get_previous_phase(app: &App) -> Option<PhaseName> {
-    let phases = &app.current_technique().phases;
-    if app.current_phase_index == 0 {
-        Some(phases.last()?.name)
-    } else {
-        Some(phases[app.current_phase_index - 1].name)
-    }
-}
-
 /// Layer 1: Background gradient field with floating orbs
 fn draw_background_field(ctx: &mut Context, x_range: f64, y_range: f64, time: f64, scale: f64, primary: Color) {
 
@@ -189,7 +232,6 @@ fn draw_massive_rings(
     y_range: f64,
     time: f64,
     scale: f64,
-    _phase: PhaseName,
     primary: Color,
     glow: Color,
 ) {
@@ -542,8 +584,46 @@ fn draw_rest_effect(ctx: &mut Context, y_range: f64, time: f64, primary: Color)
     }
 }
 
-/// Layer 5: Particle streams from particle system
-fn draw_particle_streams(ctx: &mut Context, app: &App, y_range: f64, primary: Color, glow: Color) {
+/// How far (0..1) into the end-of-phase countdown we are, if it is active.
+/// The window is the last 20% of the phase, capped at COUNTDOWN_SECS.
+fn countdown_progress(progress: f64, duration: f64) -> Option<f64> {
+    let window = (COUNTDOWN_SECS / duration.max(f64::EPSILON)).min(0.2);
+    let start = 1.0 - window;
+    if progress <= start || window <= 0.0 {
+        return None;
+    }
+    Some(((progress - start) / window).clamp(0.0, 1.0))
+}
+
+/// Layer 4b: Countdown rings collapsing toward the circle as the phase ends
+fn draw_countdown_rings(ctx: &mut Context, y_range: f64, scale: f64, t: f64, color: Color) {
+    let outer = y_range * 0.95;
+    let inner = y_range * (0.25 + scale * 0.35);
+
+    for ring in 0..3 {
+        // Stagger the rings so they arrive one after another
+        let ring_t = ((t * 1.5) - ring as f64 * 0.25).clamp(0.0, 1.0);
+        if ring_t <= 0.0 || ring_t >= 1.0 {
+            continue;
+        }
+
+        let eased = ring_t * ring_t;
+        let radius = outer + (inner - outer) * eased;
+        let ring_color = with_opacity(color, 0.3 + eased * 0.6);
+
+        let points_count = 120;
+        for i in 0..points_count {
+            let angle = (i as f64 / points_count as f64) * TAU;
+            ctx.draw(&Points {
+                coords: &[(angle.cos() * radius, angle.sin() * radius)],
+                color: ring_color,
+            });
+        }
+    }
+}
+
+/// Layer 5: Particle streams from particle system, colored from the blended phase colors
+fn draw_particle_streams(ctx: &mut Context, app: &App, y_range: f64, colors: &PhaseColors) {
     for particle in app.particle_system.iter() {
         let opacity = particle.opacity();
         if opacity < 0.1 {
@@ -561,28 +641,114 @@ fn draw_particle_streams(ctx: &mut Context, app: &App, y_range: f64, primary: Co
             if trail_opacity > 0.08 {
                 let trail_x = tx * scale_factor;
                 let trail_y = ty * scale_factor * 0.6;
+                let falloff = center_falloff(trail_x, trail_y, y_range);
                 ctx.draw(&Points {
                     coords: &[(trail_x, trail_y)],
-                    color: with_opacity(primary, trail_opacity),
+                    color: with_opacity(colors.primary, trail_opacity * falloff),
                 });
             }
         }
 
         // Draw particle
         let particle_color = match particle.particle_type {
-            ParticleType::Inward => glow,
-            ParticleType::Outward => with_opacity(glow, opacity * 0.8),
-            ParticleType::Orbital => primary,
-            _ => with_opacity(glow, opacity),
+            ParticleType::Inward => colors.particle,
+            ParticleType::Outward => with_opacity(colors.glow, opacity * 0.8),
+            ParticleType::Orbital => colors.core,
+            _ => with_opacity(colors.glow, opacity),
         };
 
+        // Brighter near the core, fading toward the edges
+        let falloff = center_falloff(px, py, y_range);
+
         ctx.draw(&Points {
             coords: &[(px, py)],
-            color: with_opacity(particle_color, opacity),
+            color: with_opacity(particle_color, opacity * falloff),
         });
     }
 }
 
+/// Brightness multiplier by distance from the center: 1.0 at the core,
+/// easing down to PARTICLE_EDGE_BRIGHTNESS at the edge of the canvas
+fn center_falloff(x: f64, y: f64, y_range: f64) -> f64 {
+    let distance = (x * x + y * y).sqrt() / y_range;
+    let t = distance.min(1.0);
+    1.0 - (1.0 - PARTICLE_EDGE_BRIGHTNESS) * t * t
+}
+
+/// Point on the box perimeter for a phase (one side each, clockwise from
+/// the bottom-left corner: up, across the top, down, back along the bottom)
+fn box_tracer_position(side: usize, progress: f64, half: f64) -> (f64, f64) {
+    let t = progress.clamp(0.0, 1.0);
+    let along = -half + t * half * 2.0;
+    match side % 4 {
+        0 => (-half, along),
+        1 => (along, half),
+        2 => (half, -along),
+        _ => (-along, -half),
+    }
+}
+
+/// Box breathing: a dot traces the square's perimeter, one side per phase.
+/// Sides already covered this cycle stay lit.
+fn draw_box_tracer(
+    ctx: &mut Context,
+    y_range: f64,
+    phase_index: usize,
+    progress: f64,
+    primary: Color,
+    glow: Color,
+    core: Color,
+) {
+    let half = y_range * 0.6;
+    let points_per_side = 80;
+
+    for side in 0..4 {
+        for i in 0..=points_per_side {
+            let t = i as f64 / points_per_side as f64;
+            let opacity = if side < phase_index || (side == phase_index && t <= progress) {
+                0.9
+            } else {
+                0.25
+            };
+            let color = if opacity > 0.5 { glow } else { primary };
+
+            ctx.draw(&Points {
+                coords: &[box_tracer_position(side, t, half)],
+                color: with_opacity(color, opacity),
+            });
+        }
+    }
+
+    // Fading trail behind the dot
+    for step in 1..12 {
+        let t = progress - step as f64 * 0.02;
+        if t < 0.0 {
+            break;
+        }
+        ctx.draw(&Points {
+            coords: &[box_tracer_position(phase_index, t, half)],
+            color: with_opacity(glow, 1.0 - step as f64 * 0.08),
+        });
+    }
+
+    // The dot itself, drawn as a small bright disc
+    let (x, y) = box_tracer_position(phase_index, progress, half);
+    for ring in 0..3 {
+        let radius = 0.6 + ring as f64 * 0.7;
+        for i in 0..16 {
+            let angle = (i as f64 / 16.0) * TAU;
+            ctx.draw(&Points {
+                coords: &[(x + angle.cos() * radius, y + angle.sin() * radius)],
+                color: with_opacity(core, 1.0 - ring as f64 * 0.25),
+            });
+        }
+    }
+    ctx.draw(&Points {
+        coords: &[(x, y)],
+        color: brighten(core, HIGHLIGHT_BOOST),
+    });
+}
+
 /// Layer 6: Bright glowing core
 fn draw_bright_core(ctx: &mut Context, y_range: f64, scale: f64, time: f64, core: Color) {
     let pulse = (time * 2.5).sin() * 0.1 + 1.0;
@@ -614,7 +780,7 @@ fn draw_bright_core(ctx: &mut Context, y_range: f64, scale: f64, time: f64, core
     // Bright center point
     ctx.draw(&Points {
         coords: &[(0.0, 0.0)],
-        color: Color::Rgb(255, 255, 255),
+        color: brighten(core, HIGHLIGHT_BOOST),
     });
 
     // Cross-shaped flare
@@ -634,3 +800,44 @@ fn draw_bright_core(ctx: &mut Context, y_range: f64, scale: f64, time: f64, core
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::PhaseColorScheme;
+
+    #[test]
+    fn test_countdown_covers_end_of_phase_only() {
+        // Long phase: only the last two seconds
+        assert!(countdown_progress(0.7, 20.0).is_none());
+        assert!(countdown_progress(0.89, 20.0).is_none());
+        let t = countdown_progress(0.95, 20.0).unwrap();
+        assert!((t - 0.5).abs() < 1e-9);
+
+        // Short phase: the last 20%
+        assert!(countdown_progress(0.79, 1.0).is_none());
+        assert!((countdown_progress(1.0, 1.0).unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vibrant_colors_follow_the_palette() {
+        let mut theme = Theme::dark();
+        let (default_hold, _, _) = get_vibrant_colors(&theme, PhaseName::Hold);
+        theme.phase_colors = PhaseColorScheme::tritanopia();
+        let (hold, _, core) = get_vibrant_colors(&theme, PhaseName::Hold);
+        assert_ne!(hold, default_hold);
+        assert_eq!(core, theme.phase_colors.hold.core);
+    }
+
+    #[test]
+    fn test_vibrance_brightens_the_theme_colors() {
+        let mut theme = Theme::dark();
+        theme.vibrance = 1.0;
+        let (primary, _, _) = get_vibrant_colors(&theme, PhaseName::Inhale);
+        assert_eq!(primary, theme.phase_colors.inhale.primary);
+
+        theme.vibrance = 1.2;
+        let (primary, _, _) = get_vibrant_colors(&theme, PhaseName::Inhale);
+        assert_eq!(primary, Color::Rgb(88, 172, 255));
+    }
+}
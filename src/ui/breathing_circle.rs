@@ -40,7 +40,7 @@ pub fn render_breathing_circle(frame: &mut Frame, app: &App, area: Rect) {
     let canvas = Canvas::default()
         .x_bounds([-50.0, 50.0])
         .y_bounds([-25.0, 25.0])
-        .marker(ratatui::symbols::Marker::Braille)
+        .marker(super::canvas_marker(app))
         .paint(move |ctx| {
             // Outer glow rings (3 layers)
             for i in 0..3 {
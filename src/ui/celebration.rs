@@ -1,14 +1,14 @@
 //! Celebration animation for session completion
 
 use crate::animation::ease_out_cubic;
+use crate::rng::{time_seed, Rng};
 use ratatui::{
     layout::Rect,
     style::Color,
+    symbols::Marker,
     widgets::canvas::{Canvas, Context, Points},
     Frame,
 };
-use std::collections::hash_map::RandomState;
-use std::hash::{BuildHasher, Hasher};
 use std::f64::consts::TAU;
 
 /// A celebration particle for the completion animation
@@ -27,8 +27,8 @@ pub struct CelebrationParticle {
 }
 
 impl CelebrationParticle {
-    pub fn new(x: f64, y: f64, angle: f64, speed: f64, color: Color) -> Self {
-        let life = 2.0 + rand_f64() * 1.5;
+    pub fn new(x: f64, y: f64, angle: f64, speed: f64, color: Color, rng: &mut Rng) -> Self {
+        let life = rng.range(2.0, 3.5);
         Self {
             x,
             y,
@@ -37,7 +37,7 @@ impl CelebrationParticle {
             life,
             max_life: life,
             color,
-            size: 1.0 + rand_f64() * 0.5,
+            size: rng.range(1.0, 1.5),
             trail: Vec::with_capacity(6),
         }
     }
@@ -81,11 +81,17 @@ pub struct CelebrationAnimation {
     center_x: f64,
     center_y: f64,
     burst_complete: bool,
+    rng: Rng,
 }
 
 impl CelebrationAnimation {
     /// Create a new celebration animation with an initial burst of particles
     pub fn new() -> Self {
+        Self::with_seed(time_seed())
+    }
+
+    /// Same as `new`, with a reproducible burst
+    pub fn with_seed(seed: u64) -> Self {
         Self {
             particles: Vec::with_capacity(100),
             progress: 0.0,
@@ -93,6 +99,7 @@ impl CelebrationAnimation {
             center_x: 0.0,
             center_y: 0.0,
             burst_complete: false,
+            rng: Rng::new(seed),
         }
     }
 
@@ -123,10 +130,10 @@ impl CelebrationAnimation {
         for i in 0..80 {
             // Distribute evenly around the circle with some randomness
             let base_angle = (i as f64 / 80.0) * TAU;
-            let angle = base_angle + (rand_f64() - 0.5) * 0.3;
+            let angle = base_angle + (self.rng.next_f64() - 0.5) * 0.3;
 
             // Vary speed for natural feel
-            let speed = 15.0 + rand_f64() * 25.0;
+            let speed = self.rng.range(15.0, 40.0);
 
             // Pick a celebration color
             let color_idx = i % colors.len();
@@ -138,19 +145,21 @@ impl CelebrationAnimation {
                 angle,
                 speed,
                 color,
+                &mut self.rng,
             ));
         }
 
         // Add extra "sparkle" particles
         for _ in 0..20 {
-            let angle = rand_f64() * TAU;
-            let speed = 20.0 + rand_f64() * 15.0;
+            let angle = self.rng.next_f64() * TAU;
+            let speed = self.rng.range(20.0, 35.0);
             self.particles.push(CelebrationParticle::new(
                 self.center_x,
                 self.center_y,
                 angle,
                 speed,
                 Color::Rgb(255, 255, 255),
+                &mut self.rng,
             ));
         }
 
@@ -179,19 +188,20 @@ impl CelebrationAnimation {
     }
 
     /// Render the celebration animation
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    pub fn render(&self, frame: &mut Frame, area: Rect, marker: Marker, bg_color: Color) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
         // Calculate canvas bounds based on area
         let aspect = area.width as f64 / (area.height as f64 * 2.0);
         let y_range = 30.0;
-        let x_range = y_range * aspect;
-
-        // Rich dark background matching the visualizer
-        let bg_color = Color::Rgb(5, 8, 15);
+        let x_range = (y_range * aspect).max(1.0);
 
         let canvas = Canvas::default()
             .x_bounds([-x_range, x_range])
             .y_bounds([-y_range, y_range])
-            .marker(ratatui::symbols::Marker::Braille)
+            .marker(marker)
             .background_color(bg_color)
             .paint(|ctx| {
                 self.render_particles(ctx);
@@ -248,15 +258,3 @@ fn apply_opacity(color: Color, opacity: f64) -> Color {
         _ => color,
     }
 }
-
-/// Simple random number generator
-fn rand_f64() -> f64 {
-    let mut hasher = RandomState::new().build_hasher();
-    hasher.write_u64(
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64,
-    );
-    (hasher.finish() as f64) / (u64::MAX as f64)
-}
@@ -6,38 +6,52 @@ pub mod celebration;
 mod overlays;
 mod widgets;
 
-use crate::app::{App, AppState};
-use crate::techniques::PhaseName;
-use crate::theme::default_theme;
+use crate::animation::pulse_breath;
+use crate::app::{score_label, session_score, App, AppState, HitAreas, Pace, SelectorRow};
+use crate::audio::AudioStatus;
+use crate::i18n::{t, translate, Lang};
+use crate::keys::{state_actions, Action, KeyBindings};
+use crate::techniques::{format_secs, PhaseName, Technique};
+use crate::theme::{blend_color, to_monochrome, with_opacity, Theme};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph},
     Frame,
 };
+use widgets::{render_key_hints, KeyHint};
 
 pub use breath_visualizer::render_breath_visualizer;
 #[allow(unused_imports)]
 pub use breathing_circle::render_breathing_circle;
 
 /// Main render function
-pub fn render(frame: &mut Frame, app: &mut App) {
-    let area = frame.area();
-    let theme = default_theme();
+pub fn render(frame: &mut Frame, app: &mut App, keys: &KeyBindings) {
+    *app.hit_areas.get_mut() = HitAreas::default();
+    let theme = &app.theme;
 
-    // Dark background
-    frame.render_widget(Clear, area);
+    // Dark background (also letterboxes a fixed-size area)
+    frame.render_widget(Clear, frame.area());
     frame.render_widget(
         Block::default().style(Style::default().bg(theme.background)),
-        area,
+        frame.area(),
     );
 
+    let area = match app.fixed_size {
+        Some((cols, rows)) => fixed_area(frame.area(), cols, rows),
+        None => frame.area(),
+    };
+
     match app.state {
-        AppState::Selecting => render_selector_screen(frame, app, area),
-        AppState::Ready => render_ready_screen(frame, app, area),
-        AppState::Breathing | AppState::Paused => render_session(frame, app, area),
-        AppState::Complete => render_complete_screen(frame, app, area),
+        AppState::Selecting => render_selector_screen(frame, app, keys, area),
+        AppState::Ready => render_ready_screen(frame, app, keys, area),
+        AppState::Countdown | AppState::Breathing | AppState::Paused => {
+            render_session(frame, app, keys, area)
+        }
+        AppState::Complete => render_complete_screen(frame, app, keys, area),
+        AppState::Transition => render_transition_screen(frame, app, keys, area),
     }
 
     // Overlays
@@ -45,25 +59,140 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_guide_overlay(frame, app, area);
     }
     if app.show_help {
-        render_help_overlay(frame, app, area);
+        render_help_overlay(frame, app, keys, area);
+    }
+    if app.show_intro {
+        render_intro_overlay(frame, app, area);
+    }
+    if app.show_disclaimer {
+        render_disclaimer_overlay(frame, app, area);
+    }
+
+    // Brightness and night mode are applied to the finished frame, so every
+    // color (theme, technique and visualizer) is graded the same way
+    if app.night_mode || app.brightness < 1.0 || app.monochrome {
+        grade_buffer(frame, app);
+    }
+
+    if app.use_ascii {
+        asciify_buffer(frame);
+    }
+}
+
+/// Canvas marker for the visualizer and celebration: braille gives the
+/// finest detail but needs Unicode, so ASCII mode falls back to dots
+pub(crate) fn canvas_marker(app: &App) -> Marker {
+    if app.use_ascii {
+        Marker::Dot
+    } else {
+        Marker::Braille
+    }
+}
+
+/// "Round 2 of 3"
+fn round_label(round: u32, rounds: u32, lang: Lang) -> String {
+    format!("{} {} {} {}", t("Round", lang), round, t("of", lang), rounds)
+}
+
+/// "🔥 5 day streak", without the flame where Unicode isn't available
+fn streak_label(days: u32, ascii: bool, lang: Lang) -> String {
+    let flame = if ascii { "" } else { "🔥 " };
+    format!("{}{} {}", flame, days, t("day streak", lang))
+}
+
+/// Phase name as shown under the visualizer
+fn phase_label(phase: PhaseName, ascii: bool, lang: Lang) -> String {
+    let marker = match (phase, ascii) {
+        (PhaseName::Inhale, false) => "▲",
+        (PhaseName::Hold, false) => "●",
+        (PhaseName::Exhale, false) => "▼",
+        (PhaseName::HoldAfterExhale, false) => "○",
+        (PhaseName::Inhale, true) => "^",
+        (PhaseName::Hold, true) => "=",
+        (PhaseName::Exhale, true) => "v",
+        (PhaseName::HoldAfterExhale, true) => "-",
+    };
+    format!("{0} {1} {0}", marker, t(phase.display(), lang))
+}
+
+/// Progress bar fill, different for every phase so the bar doesn't rely
+/// on color alone
+fn phase_bar_char(phase: PhaseName) -> &'static str {
+    match phase {
+        PhaseName::Inhale => "▓",
+        PhaseName::Hold => "█",
+        PhaseName::Exhale => "▒",
+        PhaseName::HoldAfterExhale => "▄",
+    }
+}
+
+/// Closest plain-ASCII stand-in for a symbol drawn by the UI
+fn ascii_fallback(symbol: &str) -> &'static str {
+    match symbol {
+        "●" | "•" | "◉" | "♪" | "◆" | "◈" | "★" => "*",
+        "○" | "◇" | "◎" | "◐" => "o",
+        "█" | "▓" => "#",
+        "▄" => "_",
+        "░" | "▒" | "─" | "━" | "═" | "—" | "–" => "-",
+        "│" | "┃" | "║" | "▏" => "|",
+        "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "┼" | "├" | "┤" | "┬" | "┴" => "+",
+        "▲" | "↑" => "^",
+        "▼" | "↓" => "v",
+        "←" => "<",
+        "→" => ">",
+        "·" | "…" => ".",
+        "✓" => "+",
+        "⏸" => "=",
+        _ => "?",
+    }
+}
+
+/// Replace every remaining non-ASCII symbol in the frame, so terminals
+/// without Unicode never show garbage boxes
+fn asciify_buffer(frame: &mut Frame) {
+    for cell in frame.buffer_mut().content.iter_mut() {
+        let symbol = cell.symbol();
+        if symbol.is_ascii() {
+            continue;
+        }
+        // Combining marks (like the slash in "♪̸") ride on the previous cell
+        let base: String = symbol.chars().take(1).collect();
+        let replacement = ascii_fallback(&base);
+        cell.set_symbol(replacement);
     }
 }
 
-fn render_selector_screen(frame: &mut Frame, app: &mut App, area: Rect) {
-    let theme = default_theme();
+fn grade_buffer(frame: &mut Frame, app: &App) {
+    for cell in frame.buffer_mut().content.iter_mut() {
+        cell.fg = app.grade(cell.fg);
+        cell.bg = app.grade(cell.bg);
+        if app.monochrome {
+            cell.fg = to_monochrome(cell.fg, false);
+            cell.bg = to_monochrome(cell.bg, true);
+        }
+    }
+}
 
+fn render_selector_screen(frame: &mut Frame, app: &mut App, keys: &KeyBindings, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),  // Header
             Constraint::Min(8),     // Technique list
             Constraint::Length(6),  // Description
+            Constraint::Length(if app.weekly_goal().is_some() { 1 } else { 0 }),  // Weekly goal
             Constraint::Length(3),  // Footer
         ])
         .split(area);
 
     // Header
-    render_selector_header(frame, chunks[0]);
+    render_selector_header(frame, app, chunks[0]);
+
+    if let Some((minutes, goal)) = app.weekly_goal() {
+        let progress = Paragraph::new(Line::from(weekly_goal_spans(&app.theme, minutes, goal, app.lang)))
+            .alignment(Alignment::Center);
+        frame.render_widget(progress, chunks[3]);
+    }
 
     // Technique list with margins
     let list_area = Layout::default()
@@ -74,12 +203,36 @@ fn render_selector_screen(frame: &mut Frame, app: &mut App, area: Rect) {
             Constraint::Length(2),
         ])
         .split(chunks[1])[1];
+    app.scroll_selection_into_view(list_area.height as usize);
+    let theme = &app.theme;
 
-    // Build technique list items
-    let items: Vec<ListItem> = app.techniques
+    // Build technique list items, narrowed by any search and grouped under
+    // category headings
+    let rows = app.selector_rows();
+    let items: Vec<ListItem> = rows
         .iter()
-        .enumerate()
-        .map(|(i, technique)| {
+        .map(|row| {
+            let (i, technique) = match *row {
+                SelectorRow::Favorites => {
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(" ★ ", Style::default().fg(theme.ui.accent)),
+                        Span::styled(
+                            t("FAVORITES", app.lang),
+                            Style::default().fg(theme.ui.text_muted).add_modifier(Modifier::BOLD),
+                        ),
+                    ]));
+                }
+                SelectorRow::Header(category) => {
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(format!(" {} ", category.icon()), Style::default().fg(theme.ui.accent)),
+                        Span::styled(
+                            category.display().to_uppercase(),
+                            Style::default().fg(theme.ui.text_muted).add_modifier(Modifier::BOLD),
+                        ),
+                    ]));
+                }
+                SelectorRow::Technique(i) => (i, &app.techniques[i]),
+            };
             let tc = technique.color;
             let is_selected = i == app.selected_index;
 
@@ -92,7 +245,10 @@ fn render_selector_screen(frame: &mut Frame, app: &mut App, area: Rect) {
 
             let content = Line::from(vec![
                 Span::styled(prefix, Style::default().fg(theme.ui.accent)),
-                Span::styled("● ", Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b))),
+                Span::styled(
+                    if app.is_favorite(i) { "★ " } else { "● " },
+                    Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b)),
+                ),
                 Span::styled(format!("{:<20}", technique.name), style),
                 Span::styled(
                     format!(" {}", technique.pattern),
@@ -110,6 +266,18 @@ fn render_selector_screen(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Use stateful rendering for scrolling
     frame.render_stateful_widget(list, list_area, &mut app.list_state);
+    app.hit_areas.borrow_mut().list = list_area;
+
+    if rows.is_empty() {
+        let none = Paragraph::new(Line::from(Span::styled(
+            t("No techniques match", app.lang),
+            Style::default().fg(theme.ui.text_muted),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(none, list_area);
+        render_footer(frame, app, keys, chunks[4]);
+        return;
+    }
 
     // Selected technique description panel
     let selected = app.selected_technique();
@@ -133,7 +301,7 @@ fn render_selector_screen(frame: &mut Frame, app: &mut App, area: Rect) {
     let inner = desc_block.inner(desc_area);
 
     // Wrap description text
-    let wrapped = wrap_text(selected.description, inner.width.saturating_sub(2) as usize);
+    let wrapped = wrap_text(&selected.description, inner.width.saturating_sub(2) as usize);
     let desc_lines: Vec<Line> = wrapped.into_iter()
         .take(3)  // Max 3 lines
         .map(|s| Line::from(Span::styled(s, Style::default().fg(theme.ui.text_secondary))))
@@ -143,47 +311,66 @@ fn render_selector_screen(frame: &mut Frame, app: &mut App, area: Rect) {
     frame.render_widget(desc_text, inner);
 
     // Footer
-    render_selector_footer(frame, chunks[3]);
+    render_footer(frame, app, keys, chunks[4]);
+}
+
+/// Width of the weekly goal bar in cells
+const GOAL_BAR_WIDTH: u32 = 20;
+
+/// Minutes breathed this week against the weekly goal, or a note once the
+/// goal is reached
+fn weekly_goal_spans(theme: &Theme, minutes: u32, goal: u32, lang: Lang) -> Vec<Span<'static>> {
+    if minutes >= goal {
+        return vec![Span::styled(
+            format!("✓ {} · {}/{} min", t("Weekly goal reached", lang), minutes, goal),
+            Style::default().fg(theme.ui.success).add_modifier(Modifier::BOLD),
+        )];
+    }
+    let filled = (minutes * GOAL_BAR_WIDTH / goal.max(1)) as usize;
+    vec![
+        Span::styled(t("This week ", lang), Style::default().fg(theme.ui.text_muted)),
+        Span::styled("━".repeat(filled), Style::default().fg(theme.ui.success)),
+        Span::styled("─".repeat(GOAL_BAR_WIDTH as usize - filled), Style::default().fg(theme.ui.border)),
+        Span::styled(format!(" {}/{} min", minutes, goal), Style::default().fg(theme.ui.text_muted)),
+    ]
 }
 
-fn render_selector_header(frame: &mut Frame, area: Rect) {
-    let theme = default_theme();
+fn render_selector_header(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
 
-    let header = Paragraph::new(Line::from(vec![
+    let prompt = if app.searching || !app.search_query.is_empty() {
+        let cursor = if app.searching { "▏" } else { "" };
+        Span::styled(
+            format!("/{}{}", app.search_query, cursor),
+            Style::default().fg(theme.ui.accent),
+        )
+    } else {
+        Span::styled(t("Select a technique", app.lang), Style::default().fg(theme.ui.text_secondary))
+    };
+
+    let mut spans = vec![
         Span::styled("◉ ", Style::default().fg(theme.ui.accent)),
         Span::styled("BREATHE", Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD)),
         Span::styled("  ·  ", Style::default().fg(theme.ui.border)),
-        Span::styled("Select a technique", Style::default().fg(theme.ui.text_secondary)),
-    ]))
-    .alignment(Alignment::Center)
-    .block(Block::default().padding(Padding::vertical(1)));
-
-    frame.render_widget(header, area);
-}
-
-fn render_selector_footer(frame: &mut Frame, area: Rect) {
-    let theme = default_theme();
+        prompt,
+    ];
+    let streak = app.streak();
+    if streak > 0 {
+        spans.push(Span::styled("  ·  ", Style::default().fg(theme.ui.border)));
+        spans.push(Span::styled(streak_label(streak, app.use_ascii, app.lang), Style::default().fg(theme.ui.warning)));
+    }
 
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(theme.ui.accent)),
-        Span::styled(" navigate  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("g", Style::default().fg(theme.ui.accent)),
-        Span::styled(" guide  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("ENTER", Style::default().fg(theme.ui.accent)),
-        Span::styled(" select  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("q", Style::default().fg(theme.ui.accent)),
-        Span::styled(" quit", Style::default().fg(theme.ui.text_muted)),
-    ]))
+    let header = Paragraph::new(Line::from(spans))
     .alignment(Alignment::Center)
     .block(Block::default().padding(Padding::vertical(1)));
 
-    frame.render_widget(footer, area);
+    frame.render_widget(header, area);
 }
 
-fn render_ready_screen(frame: &mut Frame, app: &App, area: Rect) {
+fn render_ready_screen(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
     let technique = app.current_technique();
     let tc = technique.color;
-    let theme = default_theme();
+    let theme = &app.theme;
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -197,13 +384,17 @@ fn render_ready_screen(frame: &mut Frame, app: &App, area: Rect) {
     // Header
     render_header(frame, app, chunks[0]);
 
-    // Center content
+    // Center content, with room above the start prompt for a safety note
+    let note_lines: Vec<String> = app
+        .safety_note()
+        .map(|note| wrap_text(note, 60))
+        .unwrap_or_default();
     let center_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(15),
             Constraint::Length(14),
-            Constraint::Length(4),
+            Constraint::Length(4 + note_lines.len() as u16),
             Constraint::Min(0),
         ])
         .split(chunks[1]);
@@ -213,26 +404,43 @@ fn render_ready_screen(frame: &mut Frame, app: &App, area: Rect) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b)))
         .padding(Padding::horizontal(2))
-        .style(Style::default().bg(Color::Rgb(15, 30, 50)));
+        .style(Style::default().bg(theme.ui.surface));
 
     let technique_area = centered_rect(60, 100, center_chunks[1]);
     frame.render_widget(technique_block.clone(), technique_area);
 
     let inner = technique_block.inner(technique_area);
+    let mut cycles_line = vec![
+        Span::styled("← ", Style::default().fg(theme.ui.text_muted)),
+        Span::styled("Cycles: ", Style::default().fg(theme.ui.text_muted)),
+        Span::styled(
+            if app.is_endless() { "∞".to_string() } else { app.cycles_target.to_string() },
+            Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" →", Style::default().fg(theme.ui.text_muted)),
+    ];
+    if let Some(estimate) = app.estimated_duration() {
+        cycles_line.push(Span::styled(
+            format!("   ~{}", App::format_time(estimate)),
+            Style::default().fg(theme.ui.text_secondary),
+        ));
+    }
+
     let technique_text = vec![
         Line::from(""),
         Line::from(
             Span::styled(
-                technique.name,
+                technique.name.clone(),
                 Style::default()
                     .fg(theme.ui.text_primary)
                     .add_modifier(Modifier::BOLD),
             )
         ).centered(),
+        technique_badges(technique, theme).centered(),
         Line::from(""),
         Line::from(
             Span::styled(
-                technique.description,
+                technique.description.clone(),
                 Style::default().fg(theme.ui.text_secondary),
             )
         ).centered(),
@@ -240,22 +448,14 @@ fn render_ready_screen(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(vec![
             Span::styled("Pattern: ", Style::default().fg(theme.ui.text_muted)),
             Span::styled(
-                technique.pattern,
+                technique.pattern.clone(),
                 Style::default()
                     .fg(Color::Rgb(tc.r, tc.g, tc.b))
                     .add_modifier(Modifier::BOLD),
             ),
         ]).centered(),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("← ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled("Cycles: ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(
-                format!("{}", app.cycles_target),
-                Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" →", Style::default().fg(theme.ui.text_muted)),
-        ]).centered(),
+        Line::from(cycles_line).centered(),
         Line::from(""),
     ];
 
@@ -264,81 +464,205 @@ fn render_ready_screen(frame: &mut Frame, app: &App, area: Rect) {
         inner,
     );
 
-    // Start instruction
-    let start_text = Line::from(vec![
-        Span::styled("Press ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("SPACE", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-        Span::styled(" to begin", Style::default().fg(theme.ui.text_muted)),
-    ]).centered();
+    // Start instruction - gently pulses to draw the eye
+    let time = app.ready_since.elapsed().as_secs_f64();
+    let pulse = pulse_breath(time, 0.25);
+    let start_color = with_opacity(theme.ui.accent, 0.5 + pulse * 0.5);
+
+    let warning = Style::default().fg(theme.ui.warning);
+    let mut start_text: Vec<Line> = note_lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let marker = if i == 0 { "⚠ " } else { "  " };
+            Line::from(Span::styled(format!("{}{}", marker, line), warning)).centered()
+        })
+        .collect();
+    if !start_text.is_empty() {
+        start_text.push(Line::from(""));
+    }
+    start_text.push(if app.needs_safety_ack() {
+        Line::from(vec![
+            Span::styled(t("Press ", app.lang), Style::default().fg(theme.ui.text_muted)),
+            Span::styled(
+                key_label(keys, Action::Acknowledge, AppState::Ready),
+                Style::default().fg(start_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t(" to acknowledge", app.lang), Style::default().fg(theme.ui.text_muted)),
+        ]).centered()
+    } else {
+        Line::from(vec![
+            Span::styled(t("Press ", app.lang), Style::default().fg(theme.ui.text_muted)),
+            Span::styled(
+                key_label(keys, Action::Confirm, AppState::Ready),
+                Style::default().fg(start_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t(" to begin", app.lang), Style::default().fg(theme.ui.text_muted)),
+        ]).centered()
+    });
 
     frame.render_widget(
         Paragraph::new(start_text),
         center_chunks[2],
     );
 
-    // Footer
-    render_ready_footer(frame, app, chunks[2]);
-}
-
-fn render_ready_footer(frame: &mut Frame, app: &App, area: Rect) {
-    let theme = default_theme();
-    let audio_icon = if app.audio_enabled { "♪" } else { "♪̸" };
-
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled("←→", Style::default().fg(theme.ui.accent)),
-        Span::styled(" cycles  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("g", Style::default().fg(theme.ui.accent)),
-        Span::styled(" guide  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("a", Style::default().fg(theme.ui.accent)),
-        Span::styled(format!(" {}  ", audio_icon), Style::default().fg(theme.ui.text_muted)),
-        Span::styled("ESC", Style::default().fg(theme.ui.accent)),
-        Span::styled(" back  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("q", Style::default().fg(theme.ui.accent)),
-        Span::styled(" quit", Style::default().fg(theme.ui.text_muted)),
-    ]))
-    .alignment(Alignment::Center)
-    .block(Block::default().padding(Padding::vertical(1)));
+    // Source attribution, always visible before starting
+    let source_text = Line::from(vec![
+        Span::styled("Source: ", Style::default().fg(theme.ui.text_muted)),
+        Span::styled(
+            technique.source.clone(),
+            Style::default().fg(theme.ui.text_secondary).add_modifier(Modifier::ITALIC),
+        ),
+        Span::styled(
+            format!("  ·  {}", t("Not medical advice", app.lang)),
+            Style::default().fg(theme.ui.text_muted),
+        ),
+    ]).centered();
+
+    frame.render_widget(Paragraph::new(source_text), center_chunks[3]);
 
-    frame.render_widget(footer, area);
+    // Footer
+    render_footer(frame, app, keys, chunks[2]);
 }
 
-fn render_session(frame: &mut Frame, app: &App, area: Rect) {
+/// Below this many rows the session uses the compact layout
+const COMPACT_HEIGHT: u16 = 20;
+
+/// Rows taken by the phase info block (name, bar, countdown, cycle dots,
+/// session bar)
+const PHASE_INFO_HEIGHT: u16 = 6;
+
+fn render_session(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
+    // Small terminals (or --compact) skip the visualizer so the essential
+    // guidance always has room
+    let compact = app.compact || area.height < COMPACT_HEIGHT;
+
     // Responsive layout - larger visualizer area
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(12),    // Breathing visualizer (expanded)
-            Constraint::Length(6),  // Phase info with overlays
-            Constraint::Length(3),  // Footer
-        ])
+        .constraints(if compact {
+            [
+                Constraint::Length(3),  // Header
+                Constraint::Length(0),  // No visualizer
+                Constraint::Min(PHASE_INFO_HEIGHT),
+                Constraint::Length(3),  // Footer
+            ]
+        } else {
+            [
+                Constraint::Length(3),  // Header
+                Constraint::Min(12),    // Breathing visualizer (expanded)
+                Constraint::Length(6),  // Phase info with overlays
+                Constraint::Length(3),  // Footer
+            ]
+        })
         .split(area);
 
     // Header with timer
     render_session_header(frame, app, chunks[0]);
 
-    // New anatomical breath visualizer (centered with responsive bounds)
-    let viz_area = chunks[1];
-    render_breath_visualizer(frame, app, viz_area);
+    if app.state == AppState::Countdown {
+        render_countdown(frame, app, if compact { chunks[2] } else { chunks[1] });
+        render_footer(frame, app, keys, chunks[3]);
+        return;
+    }
 
-    // Enhanced phase indicator with progress bar and countdown
-    render_enhanced_phase_info(frame, app, chunks[2]);
+    // Enhanced phase indicator with progress bar and countdown; compact mode
+    // centers it in the space the visualizer would have used
+    if compact {
+        let height = PHASE_INFO_HEIGHT.min(chunks[2].height);
+        let info_area = Rect {
+            y: chunks[2].y + (chunks[2].height - height) / 2,
+            height,
+            ..chunks[2]
+        };
+        render_enhanced_phase_info(frame, app, info_area);
+    } else {
+        // New anatomical breath visualizer (centered with responsive bounds)
+        render_breath_visualizer(frame, app, chunks[1]);
+        render_enhanced_phase_info(frame, app, chunks[2]);
+    }
 
     // Footer
-    render_session_footer(frame, chunks[3]);
+    render_footer(frame, app, keys, chunks[3]);
+
+    if app.metronome {
+        render_metronome(frame, app, chunks[3]);
+    }
 
     // Pause overlay
     if app.state == AppState::Paused {
-        render_pause_overlay(frame, area);
+        render_pause_overlay(frame, app, keys, area);
     }
 }
 
+/// Block digits for the pre-session countdown, five rows each
+const BIG_DIGITS: [[&str; 5]; 10] = [
+    ["███", "█ █", "█ █", "█ █", "███"],
+    [" █ ", "██ ", " █ ", " █ ", "███"],
+    ["███", "  █", "███", "█  ", "███"],
+    ["███", "  █", "███", "  █", "███"],
+    ["█ █", "█ █", "███", "  █", "  █"],
+    ["███", "█  ", "███", "  █", "███"],
+    ["███", "█  ", "███", "█ █", "███"],
+    ["███", "  █", "  █", "  █", "  █"],
+    ["███", "█ █", "███", "█ █", "███"],
+    ["███", "█ █", "███", "  █", "███"],
+];
+
+/// "3… 2… 1… Begin" in large type before the first breath
+fn render_countdown(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let tc = app.current_technique().color;
+    let color = Color::Rgb(tc.r, tc.g, tc.b);
+
+    let mut lines = match app.countdown_number() {
+        Some(number) => {
+            let digits: Vec<usize> = number
+                .to_string()
+                .bytes()
+                .map(|b| (b - b'0') as usize)
+                .collect();
+            (0..5)
+                .map(|row| {
+                    // Each glyph cell is doubled so the digits aren't too narrow
+                    let text: String = digits
+                        .iter()
+                        .map(|&d| BIG_DIGITS[d][row].chars().flat_map(|c| [c, c]).collect::<String>())
+                        .collect::<Vec<_>>()
+                        .join("  ");
+                    Line::from(Span::styled(text, Style::default().fg(color))).centered()
+                })
+                .collect::<Vec<_>>()
+        }
+        None => vec![
+            Line::from(""),
+            Line::from(""),
+            Line::from(Span::styled(
+                t("B E G I N", app.lang),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )).centered(),
+            Line::from(""),
+            Line::from(""),
+        ],
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("{} {}", t("First:", app.lang), app.current_phase().instruction),
+        Style::default().fg(theme.ui.text_muted),
+    )).centered());
+
+    let height = lines.len() as u16;
+    let top = area.y + area.height.saturating_sub(height) / 2;
+    let text_area = Rect::new(area.x, top, area.width, height.min(area.height));
+    frame.render_widget(Paragraph::new(lines), text_area);
+}
+
 /// Enhanced phase info with giant indicator, progress bar, and countdown
 fn render_enhanced_phase_info(frame: &mut Frame, app: &App, area: Rect) {
-    let theme = default_theme();
+    let theme = &app.theme;
     let phase = app.current_phase();
     let progress = app.phase_progress();
-    let remaining = phase.duration_secs * (1.0 - progress);
+    let remaining = app.effective_phase_duration() * (1.0 - progress);
     let time = app.session_elapsed().as_secs_f64();
 
     // Get blended phase colors
@@ -353,27 +677,55 @@ fn render_enhanced_phase_info(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Length(1),  // Progress bar
             Constraint::Length(1),  // Instruction + countdown
             Constraint::Length(1),  // Cycle dots
+            Constraint::Length(1),  // Session progress
         ])
         .split(info_area);
 
     // Phase name with glow effect
-    let phase_display = match phase.name {
-        PhaseName::Inhale => "▲ INHALE ▲",
-        PhaseName::Hold => "● HOLD ●",
-        PhaseName::Exhale => "▼ EXHALE ▼",
-        PhaseName::HoldAfterExhale => "○ REST ○",
-    };
+    let phase_display = phase_label(phase.name, app.use_ascii, app.lang);
 
     let phase_color = phase_colors.text;
-    let phase_text = Paragraph::new(Line::from(vec![
+
+    // Brief ♪ flash when a cue tone fires; a blank of equal width otherwise
+    // keeps the phase name from shifting
+    let cue_flash = app.cue_flash();
+    let cue_span = if cue_flash > 0.0 {
+        Span::styled("♪ ", Style::default().fg(with_opacity(theme.ui.accent, cue_flash)))
+    } else {
+        Span::raw("  ")
+    };
+
+    let mut phase_spans = vec![
+        cue_span,
         Span::styled(
             phase_display,
             Style::default()
                 .fg(phase_color)
                 .add_modifier(Modifier::BOLD),
         )
-    ]))
-    .alignment(Alignment::Center);
+    ];
+
+    // Subtle nose/mouth reminder for the intended breathing route
+    if app.show_route_hint {
+        if let Some(route) = app.current_technique().route_for(phase.name) {
+            phase_spans.push(Span::styled(
+                format!("  ({})", route.label()),
+                Style::default().fg(theme.ui.text_muted),
+            ));
+        }
+    }
+
+    // Manual mode: how the last tap compared with the target duration
+    if let Some(pace) = app.pace_feedback() {
+        let color = if pace == Pace::OnTarget { theme.ui.success } else { theme.ui.warning };
+        phase_spans.push(Span::styled(
+            format!("  · {}", pace.feedback()),
+            Style::default().fg(color),
+        ));
+    }
+
+    let phase_text = Paragraph::new(Line::from(phase_spans))
+        .alignment(Alignment::Center);
 
     frame.render_widget(phase_text, chunks[0]);
 
@@ -382,11 +734,7 @@ fn render_enhanced_phase_info(frame: &mut Frame, app: &App, area: Rect) {
     let filled = ((bar_width as f64 * progress) as usize).min(bar_width);
     let empty = bar_width.saturating_sub(filled);
 
-    let bar_char = match phase.name {
-        PhaseName::Inhale => "▓",
-        PhaseName::Exhale => "▒",
-        _ => "█",
-    };
+    let bar_char = phase_bar_char(phase.name);
 
     let bar_line = Line::from(vec![
         Span::styled("│", Style::default().fg(theme.ui.border)),
@@ -399,7 +747,7 @@ fn render_enhanced_phase_info(frame: &mut Frame, app: &App, area: Rect) {
 
     // Instruction and countdown
     let instruction_line = Line::from(vec![
-        Span::styled(phase.instruction, Style::default().fg(theme.ui.text_secondary)),
+        Span::styled(phase.instruction.clone(), Style::default().fg(theme.ui.text_secondary)),
         Span::styled("  ·  ", Style::default().fg(theme.ui.border)),
         Span::styled(format!("{:.1}s", remaining.max(0.0)), Style::default().fg(theme.ui.text_muted)),
     ]);
@@ -407,22 +755,64 @@ fn render_enhanced_phase_info(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Paragraph::new(instruction_line).alignment(Alignment::Center), chunks[2]);
 
     // Cycle dots
-    let completed = app.cycles_completed as usize;
-    let target = app.cycles_target as usize;
+    let dots = cycle_progress_spans(theme, app.cycles_completed, app.cycles_target, time);
+    frame.render_widget(Paragraph::new(Line::from(dots)).alignment(Alignment::Center), chunks[3]);
 
-    let max_display = 12;
-    let (display_completed, display_target) = if target > max_display {
-        let ratio = completed as f64 / target as f64;
-        ((ratio * max_display as f64).round() as usize, max_display)
-    } else {
-        (completed, target)
-    };
+    // Thin whole-session bar (endless sessions have no end to measure)
+    if let Some(session) = app.session_progress() {
+        let width = (bar_width / 2) as f64;
+        let filled = (width * session).round() as usize;
+        let line = Line::from(vec![
+            Span::styled("━".repeat(filled), Style::default().fg(theme.ui.text_muted)),
+            Span::styled("─".repeat(width as usize - filled), Style::default().fg(theme.ui.border)),
+        ]);
+        frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), chunks[4]);
+    }
+}
+
+/// Completed cycles kept on screen in endless mode, trailing the current one
+const ENDLESS_TRAIL: u32 = 5;
 
-    let mut dots = Vec::new();
-    for i in 0..display_target {
-        if i < display_completed {
-            dots.push(Span::styled("●", Style::default().fg(theme.ui.success)));
-        } else if i == display_completed {
+/// Most cycles shown as individual dots before switching to a compact bar
+const MAX_CYCLE_DOTS: u32 = 12;
+
+/// Width of the compact cycle bar in cells
+const CYCLE_BAR_WIDTH: u64 = 20;
+
+/// Cycle progress: one dot per cycle for short sessions, or a mini bar with
+/// a numeric count once the target no longer fits as dots
+fn cycle_progress_spans(theme: &Theme, completed: u32, target: u32, time: f64) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+
+    // Endless: a rolling trail of the last few cycles, fading out behind
+    // the current one, and a running count
+    if target == 0 {
+        let trail = completed.min(ENDLESS_TRAIL);
+        for i in 0..trail {
+            let color = if i + 2 < trail { theme.ui.text_muted } else { theme.ui.success };
+            spans.push(Span::styled("● ", Style::default().fg(color)));
+        }
+        spans.push(Span::styled("◉", Style::default().fg(theme.ui.accent)));
+        spans.push(Span::styled(format!("  {}", completed), Style::default().fg(theme.ui.text_muted)));
+        return spans;
+    }
+
+    if target > MAX_CYCLE_DOTS {
+        let filled = (completed.min(target) as u64 * CYCLE_BAR_WIDTH / target as u64) as usize;
+        let empty = CYCLE_BAR_WIDTH as usize - filled;
+        spans.push(Span::styled("━".repeat(filled), Style::default().fg(theme.ui.success)));
+        spans.push(Span::styled("─".repeat(empty), Style::default().fg(theme.ui.text_muted)));
+        spans.push(Span::styled(
+            format!(" {}/{}", completed, target),
+            Style::default().fg(theme.ui.text_muted),
+        ));
+        return spans;
+    }
+
+    for i in 0..target {
+        if i < completed {
+            spans.push(Span::styled("●", Style::default().fg(theme.ui.success)));
+        } else if i == completed {
             let pulse_val = (time * 3.0).sin() * 0.3 + 0.7;
             let pulse_color = if let Color::Rgb(r, g, b) = theme.ui.accent {
                 Color::Rgb(
@@ -433,30 +823,26 @@ fn render_enhanced_phase_info(frame: &mut Frame, app: &App, area: Rect) {
             } else {
                 theme.ui.accent
             };
-            dots.push(Span::styled("◉", Style::default().fg(pulse_color)));
+            spans.push(Span::styled("◉", Style::default().fg(pulse_color)));
         } else {
-            dots.push(Span::styled("○", Style::default().fg(theme.ui.text_muted)));
+            spans.push(Span::styled("○", Style::default().fg(theme.ui.text_muted)));
         }
-        if i < display_target - 1 {
-            dots.push(Span::raw(" "));
+        if i + 1 < target {
+            spans.push(Span::raw(" "));
         }
     }
 
-    if target > max_display {
-        dots.push(Span::styled(format!(" ({}/{})", completed, target), Style::default().fg(theme.ui.text_muted)));
-    }
-
-    frame.render_widget(Paragraph::new(Line::from(dots)).alignment(Alignment::Center), chunks[3]);
+    spans
 }
 
-fn render_complete_screen(frame: &mut Frame, app: &App, area: Rect) {
+fn render_complete_screen(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
     let technique = app.current_technique();
     let tc = technique.color;
-    let theme = default_theme();
+    let theme = &app.theme;
 
     // Render celebration animation if active
     if let Some(ref celebration) = app.celebration {
-        celebration.render(frame, area);
+        celebration.render(frame, area, canvas_marker(app), app.theme.background_dark);
     }
 
     let chunks = Layout::default()
@@ -470,11 +856,14 @@ fn render_complete_screen(frame: &mut Frame, app: &App, area: Rect) {
 
     render_header(frame, app, chunks[0]);
 
+    let mut pace_lines = interval_summary_lines(app);
+    pace_lines.extend(pace_summary_lines(app));
+
     let center_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(20),
-            Constraint::Length(14),
+            Constraint::Length(if pace_lines.is_empty() { 16 } else { 17 + pace_lines.len() as u16 }),
             Constraint::Length(3),
             Constraint::Min(0),
         ])
@@ -485,64 +874,250 @@ fn render_complete_screen(frame: &mut Frame, app: &App, area: Rect) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b)))
         .padding(Padding::horizontal(2))
-        .style(Style::default().bg(Color::Rgb(15, 30, 50)));
+        .style(Style::default().bg(theme.ui.surface));
 
     let complete_area = centered_rect(50, 100, center_chunks[1]);
     frame.render_widget(complete_block.clone(), complete_area);
 
     let inner = complete_block.inner(complete_area);
     let elapsed = App::format_time(app.session_elapsed());
+    let score = session_score(app);
 
-    let complete_text = vec![
+    // Labels line up in one column, as wide as the longest translation
+    let label_width = ["Technique", "Cycles", "Breaths", "Duration", "Focus"]
+        .iter()
+        .map(|&key| t(key, app.lang).chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let label = |key: &'static str| {
+        Span::styled(
+            format!("{:<width$}", t(key, app.lang), width = label_width),
+            Style::default().fg(theme.ui.text_muted),
+        )
+    };
+
+    let mut complete_text = vec![
         Line::from(""),
         Line::from(
             Span::styled(
-                "✓ Session Complete",
+                format!("✓ {}", t("Session Complete", app.lang)),
                 Style::default()
                     .fg(theme.ui.success)
                     .add_modifier(Modifier::BOLD),
             )
         ).centered(),
+        Line::from(Span::styled(
+            streak_label(app.streak_with_session(), app.use_ascii, app.lang),
+            Style::default().fg(theme.ui.warning),
+        )).centered(),
         Line::from(""),
+        Line::from(vec![
+            label("Technique"),
+            Span::styled(technique.name.clone(), Style::default().fg(theme.ui.text_primary)),
+        ]).centered(),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Technique  ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(technique.name, Style::default().fg(theme.ui.text_primary)),
+            label("Cycles"),
+            Span::styled(format!("{}", app.total_cycles()), Style::default().fg(theme.ui.text_primary)),
         ]).centered(),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Cycles     ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(format!("{}", app.cycles_completed), Style::default().fg(theme.ui.text_primary)),
+            label("Breaths"),
+            Span::styled(format!("{}", app.total_breaths()), Style::default().fg(theme.ui.text_primary)),
         ]).centered(),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Duration   ", Style::default().fg(theme.ui.text_muted)),
+            label("Duration"),
             Span::styled(elapsed, Style::default().fg(theme.ui.text_primary)),
         ]).centered(),
         Line::from(""),
+        Line::from(vec![
+            label("Focus"),
+            Span::styled(
+                format!("{} · {}", score, t(score_label(score), app.lang)),
+                Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD),
+            ),
+        ]).centered(),
+        Line::from(""),
     ];
 
+    complete_text.extend(pace_lines);
+
     frame.render_widget(Paragraph::new(complete_text), inner);
 
     // Restart instruction
+    let key = |action| {
+        Span::styled(
+            key_label(keys, action, AppState::Complete),
+            Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD),
+        )
+    };
+    let muted = |text: String| Span::styled(text, Style::default().fg(theme.ui.text_muted));
     let restart_text = Line::from(vec![
-        Span::styled("Press ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("R", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-        Span::styled(" to restart  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("B", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-        Span::styled(" techniques  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("Q", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-        Span::styled(" quit", Style::default().fg(theme.ui.text_muted)),
+        muted(t("Press ", app.lang).to_string()),
+        key(Action::Again),
+        muted(format!("{}  ", t(" to go again", app.lang))),
+        key(Action::Restart),
+        muted(format!(" {}  ", t("restart", app.lang))),
+        key(Action::Menu),
+        muted(format!(" {}  ", t("techniques", app.lang))),
+        key(Action::Note),
+        muted(format!(" {}  ", t("note", app.lang))),
+        key(Action::Quit),
+        muted(format!(" {}", t("quit", app.lang))),
     ]).centered();
 
-    frame.render_widget(Paragraph::new(restart_text), center_chunks[2]);
+    // Optional journal note
+    let note_line = if app.editing_note {
+        Line::from(vec![
+            Span::styled(t("Note: ", app.lang), Style::default().fg(theme.ui.text_muted)),
+            Span::styled(app.note.clone(), Style::default().fg(theme.ui.text_primary)),
+            Span::styled("▏", Style::default().fg(theme.ui.accent)),
+        ]).centered()
+    } else if !app.note.is_empty() {
+        Line::from(vec![
+            Span::styled(t("Note: ", app.lang), Style::default().fg(theme.ui.text_muted)),
+            Span::styled(format!("\u{201c}{}\u{201d}", app.note), Style::default().fg(theme.ui.text_secondary)),
+        ]).centered()
+    } else {
+        Line::from("")
+    };
+
+    frame.render_widget(
+        Paragraph::new(vec![restart_text, Line::from(""), note_line]),
+        center_chunks[2],
+    );
+
+    render_footer(frame, app, keys, chunks[2]);
+}
+
+/// Manual-mode accuracy for the complete screen: an overall score and a
+/// per-phase breakdown. Empty when the session wasn't manually paced.
+fn pace_summary_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let Some((on_target, total)) = app.pace_score() else {
+        return Vec::new();
+    };
+
+    let percent = on_target * 100 / total;
+    let mut breakdown = Vec::new();
+    for (phase, (hits, taps)) in app.current_technique().phases.iter().zip(app.pace_by_phase()) {
+        if taps == 0 {
+            continue;
+        }
+        if !breakdown.is_empty() {
+            breakdown.push(Span::styled(" · ", Style::default().fg(theme.ui.text_muted)));
+        }
+        breakdown.push(Span::styled(
+            format!("{} {}/{}", t(phase.name.display(), app.lang), hits, taps),
+            Style::default().fg(theme.ui.text_secondary),
+        ));
+    }
 
-    render_footer(frame, chunks[2]);
+    vec![
+        Line::from(vec![
+            Span::styled(format!("{:<11}", t("On pace", app.lang)), Style::default().fg(theme.ui.text_muted)),
+            Span::styled(
+                format!("{}/{} ({}%)", on_target, total, percent),
+                Style::default().fg(theme.ui.text_primary),
+            ),
+        ]).centered(),
+        Line::from(breakdown).centered(),
+    ]
+}
+
+/// Between interval segments: a card announcing the next technique
+fn render_transition_screen(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
+    let theme = &app.theme;
+    let Some(next) = app.next_segment() else {
+        return;
+    };
+    let tc = next.technique.color;
+    let next_color = Color::Rgb(tc.r, tc.g, tc.b);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    render_header(frame, app, chunks[0]);
+
+    let card_area = centered_rect(50, 60, chunks[1]);
+    let card = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(next_color))
+        .padding(Padding::horizontal(2))
+        .style(Style::default().bg(theme.ui.surface));
+    frame.render_widget(card.clone(), card_area);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(t("UP NEXT", app.lang), Style::default().fg(theme.ui.text_muted))).centered(),
+        Line::from(""),
+        Line::from(Span::styled(
+            next.technique.name.clone(),
+            Style::default().fg(next_color).add_modifier(Modifier::BOLD),
+        )).centered(),
+        Line::from(Span::styled(
+            format!("{} · {} cycles", next.technique.pattern, next.cycles),
+            Style::default().fg(theme.ui.text_secondary),
+        )).centered(),
+        Line::from(""),
+    ];
+    if app.rounds > 1 {
+        lines.push(Line::from(Span::styled(
+            round_label(app.round_of(app.segment_index + 1), app.rounds, app.lang),
+            Style::default().fg(theme.ui.text_primary),
+        )).centered());
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(t("Starting in ", app.lang), Style::default().fg(theme.ui.text_muted)),
+        Span::styled(
+            format!("{}", app.transition_remaining().ceil() as u32),
+            Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD),
+        ),
+    ]).centered());
+
+    frame.render_widget(Paragraph::new(lines), card.inner(card_area));
+
+    render_footer(frame, app, keys, chunks[2]);
+}
+
+/// Interval sessions and sequences: total rounds plus cycles and time per
+/// technique
+fn interval_summary_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    if app.segment_results.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = Vec::new();
+    if app.rounds > 1 {
+        lines.push(Line::from(vec![
+            Span::styled("Rounds     ", Style::default().fg(theme.ui.text_muted)),
+            Span::styled(format!("{}", app.rounds), Style::default().fg(theme.ui.text_primary)),
+        ]).centered());
+    }
+
+    for (name, cycles, duration) in app.technique_totals() {
+        lines.push(Line::from(Span::styled(
+            format!("{} · {} cycles · {}", name, cycles, App::format_time(duration)),
+            Style::default().fg(theme.ui.text_secondary),
+        )).centered());
+    }
+
+    lines
 }
 
 fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let tc = app.current_technique().color;
-    let theme = default_theme();
+    let theme = &app.theme;
 
     let header = Paragraph::new(Line::from(vec![
         Span::styled("◉ ", Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b))),
@@ -558,7 +1133,7 @@ fn render_session_header(frame: &mut Frame, app: &App, area: Rect) {
     let technique = app.current_technique();
     let elapsed = App::format_time(app.session_elapsed());
     let tc = technique.color;
-    let theme = default_theme();
+    let theme = &app.theme;
 
     let header_chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -572,69 +1147,198 @@ fn render_session_header(frame: &mut Frame, app: &App, area: Rect) {
     // Left: technique name
     let left = Paragraph::new(Line::from(vec![
         Span::styled("◉ ", Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b))),
-        Span::styled(technique.name, Style::default().fg(theme.ui.text_secondary)),
+        Span::styled(technique.name.clone(), Style::default().fg(theme.ui.text_secondary)),
     ]))
     .block(Block::default().padding(Padding::new(2, 0, 1, 0)));
     frame.render_widget(left, header_chunks[0]);
 
-    // Center: cycle count
-    let center = Paragraph::new(Line::from(vec![
-        Span::styled(
-            format!("{}", app.cycles_completed + 1),
+    // Center: cycle count, prefixed with the round in multi-round sessions
+    let mut center_spans = Vec::new();
+    if app.rounds > 1 {
+        center_spans.push(Span::styled(
+            format!("{}   ", round_label(app.current_round(), app.rounds, app.lang)),
+            Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if app.segments.get(app.segment_index).is_some_and(|segment| segment.retention) {
+        center_spans.push(Span::styled(
+            t("Retention", app.lang),
             Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            format!(" / {}", app.cycles_target),
-            Style::default().fg(theme.ui.text_muted),
-        ),
-    ]))
-    .alignment(Alignment::Center)
-    .block(Block::default().padding(Padding::vertical(1)));
+        ));
+    } else {
+        center_spans.extend([
+            Span::styled(
+                format!("{}", app.current_cycle()),
+                Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                if app.is_endless() { " / ∞".to_string() } else { format!(" / {}", app.cycles_target) },
+                Style::default().fg(theme.ui.text_muted),
+            ),
+        ]);
+    }
+    let center = Paragraph::new(Line::from(center_spans))
+        .alignment(Alignment::Center)
+        .block(Block::default().padding(Padding::vertical(1)));
     frame.render_widget(center, header_chunks[1]);
 
-    // Right: timer
-    let right = Paragraph::new(Line::from(
-        Span::styled(elapsed, Style::default().fg(theme.ui.text_secondary)),
-    ))
+    // Right: timer, after the pace when it isn't the default
+    let mut right_spans = Vec::new();
+    if (app.pace - 1.0).abs() > f64::EPSILON {
+        right_spans.push(Span::styled(
+            format!("{:.1}x   ", app.pace),
+            Style::default().fg(theme.ui.accent),
+        ));
+    }
+    right_spans.push(Span::styled(elapsed, Style::default().fg(theme.ui.text_secondary)));
+    let right = Paragraph::new(Line::from(right_spans))
     .alignment(Alignment::Right)
     .block(Block::default().padding(Padding::new(0, 2, 1, 0)));
     frame.render_widget(right, header_chunks[2]);
 }
 
-fn render_footer(frame: &mut Frame, area: Rect) {
-    let theme = default_theme();
+/// Dot at the left of the footer that brightens on each second of the phase
+fn render_metronome(frame: &mut Frame, app: &App, area: Rect) {
+    if area.width < 4 || area.height < 2 {
+        return;
+    }
+    let theme = &app.theme;
+    let color = blend_color(theme.ui.text_muted, theme.ui.accent, app.metronome_pulse());
+    let dot = Rect::new(area.x + 2, area.y + 1, 1, 1);
+    frame.render_widget(Paragraph::new(Span::styled("●", Style::default().fg(color))), dot);
+}
 
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled("?", Style::default().fg(theme.ui.accent)),
-        Span::styled(" help  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("q", Style::default().fg(theme.ui.accent)),
-        Span::styled(" quit", Style::default().fg(theme.ui.text_muted)),
-    ]))
-    .alignment(Alignment::Center)
-    .block(Block::default().padding(Padding::vertical(1)));
+/// Footer legend listing the shortcuts that are relevant right now
+fn render_footer(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
+    let mut hints = footer_hints(app, keys);
+    for hint in &mut hints {
+        if let Some(text) = translate(&hint.action, app.lang) {
+            hint.action = text.to_string();
+        }
+    }
+    app.hit_areas.borrow_mut().hints = render_key_hints(frame, area, &app.theme, hints);
+}
 
-    frame.render_widget(footer, area);
+/// Hints for the current screen, from what its keys do. Typing prompts
+/// read raw keys, so theirs are fixed.
+fn footer_hints(app: &App, keys: &KeyBindings) -> Vec<KeyHint> {
+    if app.searching {
+        return vec![
+            KeyHint::new("↑↓", "navigate"),
+            KeyHint::new("ENTER", "select"),
+            KeyHint::new("ESC", "clear"),
+        ];
+    }
+    if app.editing_note {
+        return vec![KeyHint::new("ENTER", "save note"), KeyHint::new("ESC", "discard")];
+    }
+
+    // Leaving goes last, so it's what survives a narrow terminal
+    let actions = state_actions(app.state);
+    let (leave, rest): (Vec<Action>, Vec<Action>) = actions
+        .iter()
+        .copied()
+        .partition(|action| matches!(action, Action::Back | Action::Quit));
+
+    let mut hints: Vec<KeyHint> = Vec::new();
+    for action in rest.into_iter().chain(leave.into_iter().rev()) {
+        let (Some(label), Some(key)) = (hint_label(app, action), keys.label(action, actions)) else {
+            continue;
+        };
+        // Pairs like pace down / up share one hint, which isn't clickable
+        match hints.iter_mut().find(|hint| hint.action == label) {
+            Some(hint) => {
+                hint.key = format!("{}/{}", hint.key, key);
+                hint.target = None;
+            }
+            None => hints.push(KeyHint::new(key, label).on_click(action)),
+        }
+    }
+    hints
 }
 
-fn render_session_footer(frame: &mut Frame, area: Rect) {
-    let theme = default_theme();
+/// The keys reaching `action` on `state`'s screen, or a dash when none do
+fn key_label(keys: &KeyBindings, action: Action, state: AppState) -> String {
+    keys.label(action, state_actions(state)).unwrap_or_else(|| "—".to_string())
+}
 
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled("SPACE", Style::default().fg(theme.ui.accent)),
-        Span::styled(" pause  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("?", Style::default().fg(theme.ui.accent)),
-        Span::styled(" help  ", Style::default().fg(theme.ui.text_muted)),
-        Span::styled("q", Style::default().fg(theme.ui.accent)),
-        Span::styled(" quit", Style::default().fg(theme.ui.text_muted)),
-    ]))
-    .alignment(Alignment::Center)
-    .block(Block::default().padding(Padding::vertical(1)));
+/// Keys column of the help overlay: the keys of each action on `state`'s
+/// screen, together
+fn help_keys(keys: &KeyBindings, state: AppState, actions: &[Action]) -> String {
+    let labels: Vec<String> = actions
+        .iter()
+        .filter_map(|&action| keys.label(action, state_actions(state)))
+        .collect();
+    if labels.is_empty() {
+        return "—".to_string();
+    }
+    labels.join(" / ")
+}
 
-    frame.render_widget(footer, area);
+/// What an action's footer hint says on the current screen, or None when
+/// it isn't worth a hint there
+fn hint_label(app: &App, action: Action) -> Option<String> {
+    use Action::*;
+    let state = app.state;
+    let label = match action {
+        // Quit and b already cover these, and Esc does nothing mid-breath
+        Back if !matches!(state, AppState::Ready | AppState::Countdown | AppState::Transition) => {
+            return None
+        }
+        Back => "back",
+        // q wraps up an endless session rather than quitting
+        Quit if app.is_endless() && matches!(state, AppState::Breathing | AppState::Paused) => {
+            "finish"
+        }
+        Quit => "quit",
+        Menu if matches!(state, AppState::Paused | AppState::Complete) => "techniques",
+        Menu => return None,
+        Confirm => match state {
+            AppState::Selecting => "select",
+            AppState::Ready if !app.needs_safety_ack() => "start",
+            AppState::Countdown | AppState::Transition => "start now",
+            AppState::Breathing if app.manual => "next phase",
+            _ => return None,
+        },
+        Acknowledge if app.needs_safety_ack() => "acknowledge",
+        Acknowledge => return None,
+        Up | Down if state == AppState::Selecting => "navigate",
+        Up | Down | CyclesDown | CyclesUp => "cycles",
+        CyclesDownMore | CyclesUpMore => return None,
+        PageUp | PageDown => "page",
+        Pause if state == AppState::Paused => "resume",
+        Pause => "pause",
+        Next => "skip",
+        Prev => "previous",
+        PaceDown | PaceUp => "pace",
+        Restart => "restart",
+        Search => "search",
+        Favorite => "favorite",
+        Guide => "guide",
+        Help => "help",
+        Note => "note",
+        Again => "again",
+        Audio => return Some(audio_label(app)),
+        Drone if app.drone => "drone",
+        Metronome if app.metronome => "metronome",
+        Drone | Metronome => return None,
+        VolumeDown | VolumeUp => "volume",
+    };
+    Some(label.to_string())
 }
 
-fn render_pause_overlay(frame: &mut Frame, area: Rect) {
-    let theme = default_theme();
+/// The audio hint: its status, or the cue volume when it's on
+fn audio_label(app: &App) -> String {
+    match app.audio_status {
+        AudioStatus::Initializing => "initializing audio…".to_string(),
+        _ if app.audio_unavailable_notice() => "audio unavailable".to_string(),
+        _ if app.audio_enabled => format!("♪ {:.0}%", app.volume * 100.0),
+        _ => "♪̸".to_string(),
+    }
+}
+
+fn render_pause_overlay(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
+    let theme = &app.theme;
 
     // Darken background
     let dim_block = Block::default()
@@ -648,126 +1352,243 @@ fn render_pause_overlay(frame: &mut Frame, area: Rect) {
     let pause_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.ui.warning))
-        .style(Style::default().bg(Color::Rgb(15, 30, 50)));
+        .style(Style::default().bg(theme.ui.surface));
 
     frame.render_widget(pause_block.clone(), overlay_area);
 
     let inner = pause_block.inner(overlay_area);
-    let pause_text = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(
-            Span::styled("⏸  PAUSED", Style::default().fg(theme.ui.warning).add_modifier(Modifier::BOLD))
+            Span::styled(format!("⏸  {}", t("PAUSED", app.lang)), Style::default().fg(theme.ui.warning).add_modifier(Modifier::BOLD))
         ).centered(),
         Line::from(""),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("SPACE", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-            Span::styled("  resume", Style::default().fg(theme.ui.text_secondary)),
-        ]).centered(),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("R", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-            Span::styled("      restart", Style::default().fg(theme.ui.text_secondary)),
-        ]).centered(),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("B", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-            Span::styled("      back to menu", Style::default().fg(theme.ui.text_secondary)),
-        ]).centered(),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Q", Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
-            Span::styled("      quit", Style::default().fg(theme.ui.text_secondary)),
-        ]).centered(),
-    ]);
+    ];
 
-    frame.render_widget(pause_text, inner);
+    // Keys padded to the widest, so the actions line up
+    let options = [
+        (Action::Pause, t("resume", app.lang)),
+        (Action::Restart, t("restart", app.lang)),
+        (Action::Menu, t("back to menu", app.lang)),
+        (Action::Quit, t("quit", app.lang)),
+    ];
+    let labels: Vec<String> = options
+        .iter()
+        .map(|&(action, _)| key_label(keys, action, AppState::Paused))
+        .collect();
+    let width = labels.iter().map(|label| Span::raw(label.as_str()).width()).max().unwrap_or(0);
+    for (label, (_, text)) in labels.into_iter().zip(options) {
+        let padding = " ".repeat(width - Span::raw(label.as_str()).width() + 2);
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(label, Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}{}", padding, text), Style::default().fg(theme.ui.text_secondary)),
+        ]).centered());
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
 }
 
-fn render_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
-    let theme = default_theme();
+fn render_help_overlay(frame: &mut Frame, app: &App, keys: &KeyBindings, area: Rect) {
+    let theme = &app.theme;
+    let lang = app.lang;
     let overlay_area = centered_rect(55, 65, area);
 
     frame.render_widget(Clear, overlay_area);
 
     let help_block = Block::default()
-        .title(" Keyboard Shortcuts ")
+        .title(format!(" {} ", t("Keyboard Shortcuts", lang)))
         .title_style(Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.ui.accent))
         .padding(Padding::uniform(1))
-        .style(Style::default().bg(Color::Rgb(15, 30, 50)));
+        .style(Style::default().bg(theme.ui.surface));
 
     frame.render_widget(help_block.clone(), overlay_area);
 
     let inner = help_block.inner(overlay_area);
 
-    let help_lines = match app.state {
+    use Action::*;
+    let restart = if app.state == AppState::Breathing { "Restart phase" } else { "Restart session" };
+    let adjust_cycles = format!(
+        "{} ({} {}, {} {})",
+        t("Adjust cycles", lang),
+        help_keys(keys, AppState::Ready, &[Up, Down]),
+        t("by 10", lang),
+        help_keys(keys, AppState::Ready, &[CyclesUpMore, CyclesDownMore]),
+        t("by 5", lang),
+    );
+    let mut rows: Vec<(String, String)> = match app.state {
         AppState::Selecting => vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  ↑ / k       ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Previous technique", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  ↓ / j       ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Next technique", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  ENTER       ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Select technique", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  Q / ESC     ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Quit", Style::default().fg(theme.ui.text_secondary)),
-            ]),
+            (help_keys(keys, app.state, &[Up]), t("Previous technique", lang).to_string()),
+            (help_keys(keys, app.state, &[Down]), t("Next technique", lang).to_string()),
+            (help_keys(keys, app.state, &[Confirm]), t("Select technique", lang).to_string()),
+            (help_keys(keys, app.state, &[Search]), t("Search (ESC clears)", lang).to_string()),
+            (help_keys(keys, app.state, &[Quit, Back]), t("Quit", lang).to_string()),
         ],
         _ => vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  SPACE       ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Start / Pause / Resume", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  ← / →       ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Adjust cycles", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  R           ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Restart session", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  B / ESC     ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Back to techniques", Style::default().fg(theme.ui.text_secondary)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  Q           ", Style::default().fg(theme.ui.accent)),
-                Span::styled("Quit", Style::default().fg(theme.ui.text_secondary)),
-            ]),
+            (help_keys(keys, AppState::Breathing, &[Pause]), t("Start / Pause / Resume", lang).to_string()),
+            (help_keys(keys, AppState::Ready, &[CyclesDown, CyclesUp]), adjust_cycles),
+            (help_keys(keys, AppState::Breathing, &[PaceDown, PaceUp]), t("Shorter / longer phases", lang).to_string()),
+            (help_keys(keys, AppState::Breathing, &[Next]), t("Skip to next phase", lang).to_string()),
+            (help_keys(keys, AppState::Breathing, &[Prev]), t("Back one phase", lang).to_string()),
+            (help_keys(keys, AppState::Breathing, &[VolumeUp, VolumeDown]), t("Cue volume", lang).to_string()),
+            (help_keys(keys, AppState::Breathing, &[Drone]), t("Background drone", lang).to_string()),
+            (help_keys(keys, AppState::Breathing, &[Metronome]), t("Metronome", lang).to_string()),
+            (help_keys(keys, AppState::Paused, &[Restart]), t(restart, lang).to_string()),
+            (help_keys(keys, AppState::Paused, &[Menu, Back]), t("Back to techniques", lang).to_string()),
+            (help_keys(keys, AppState::Paused, &[Quit]), t("Quit", lang).to_string()),
         ],
     };
+    if app.manual && app.state != AppState::Selecting {
+        let row = (help_keys(keys, AppState::Breathing, &[Confirm]), t("Next phase (manual pacing)", lang).to_string());
+        rows.insert(1, row);
+    }
+
+    let mut lines = Vec::new();
+    for (label, description) in rows {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<11} ", label), Style::default().fg(theme.ui.accent)),
+            Span::styled(description, Style::default().fg(theme.ui.text_secondary)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(
+        Line::from(
+            Span::styled(t("Press any key to close", lang), Style::default().fg(theme.ui.text_muted))
+        ).centered()
+    );
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_disclaimer_overlay(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let overlay_area = centered_rect(60, 60, area);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let disclaimer_block = Block::default()
+        .title(format!(" {} ", t("Before You Begin", app.lang)))
+        .title_style(Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.ui.warning))
+        .padding(Padding::uniform(1))
+        .style(Style::default().bg(theme.ui.surface));
+
+    frame.render_widget(disclaimer_block.clone(), overlay_area);
+
+    let inner = disclaimer_block.inner(overlay_area);
+    let width = inner.width.saturating_sub(2) as usize;
+
+    let paragraphs = [
+        "BREATHE is a wellness tool, not medical advice. Technique descriptions and their \
+         cited sources are provided for general information only.",
+        "Stop if you feel dizzy, lightheaded or unwell. Never practice breath holds or \
+         rapid breathing while driving, standing, or in or near water.",
+        "If you are pregnant or have a heart, lung or blood-pressure condition, talk to a \
+         doctor before practicing.",
+    ];
+
+    let mut lines = vec![
+        Line::from(
+            Span::styled(format!("⚠  {}", t("Not Medical Advice", app.lang)), Style::default().fg(theme.ui.warning).add_modifier(Modifier::BOLD))
+        ).centered(),
+        Line::from(""),
+    ];
+
+    for paragraph in paragraphs {
+        for line in wrap_text(t(paragraph, app.lang), width) {
+            lines.push(Line::from(Span::styled(line, Style::default().fg(theme.ui.text_secondary))));
+        }
+        lines.push(Line::from(""));
+    }
 
-    let mut lines = help_lines;
     lines.push(Line::from(""));
+    lines.push(
+        Line::from(
+            Span::styled(t("Press any key to continue", app.lang), Style::default().fg(theme.ui.text_muted))
+        ).centered()
+    );
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// First-run walkthrough of the essential keys
+fn render_intro_overlay(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let overlay_area = centered_rect(60, 70, area);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let intro_block = Block::default()
+        .title(format!(" {} ", t("Welcome", app.lang)))
+        .title_style(Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.ui.accent))
+        .padding(Padding::uniform(1))
+        .style(Style::default().bg(theme.ui.surface));
+
+    frame.render_widget(intro_block.clone(), overlay_area);
+
+    let inner = intro_block.inner(overlay_area);
+    let width = inner.width.saturating_sub(2) as usize;
+
+    let mut lines = vec![
+        Line::from(
+            Span::styled(format!("◉ {}", t("Welcome to BREATHE", app.lang)), Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD))
+        ).centered(),
+        Line::from(""),
+    ];
+    let intro = "Follow the circle: it grows as you breathe in and shrinks as you breathe out. \
+                 Box breathing is selected, a calm place to start: in, hold, out, hold, four seconds each.";
+    for line in wrap_text(t(intro, app.lang), width) {
+        lines.push(Line::from(Span::styled(line, Style::default().fg(theme.ui.text_secondary))));
+    }
+    lines.push(Line::from(""));
+
+    let controls = [
+        ("↑↓", "choose a technique"),
+        ("ENTER", "start it"),
+        ("SPACE", "pause and resume"),
+        ("a", "sound on or off"),
+        ("?", "every key"),
+        ("q", "quit"),
+    ];
+    for (key, action) in controls {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>8}  ", key), Style::default().fg(theme.ui.accent).add_modifier(Modifier::BOLD)),
+            Span::styled(t(action, app.lang), Style::default().fg(theme.ui.text_secondary)),
+        ]));
+    }
+
     lines.push(Line::from(""));
     lines.push(
         Line::from(
-            Span::styled("Press any key to close", Style::default().fg(theme.ui.text_muted))
+            Span::styled(t("Press any key to begin", app.lang), Style::default().fg(theme.ui.text_muted))
         ).centered()
     );
 
     frame.render_widget(Paragraph::new(lines), inner);
 }
 
+/// "◎ Focus & Performance · Advanced", so nobody starts a hard pattern cold
+fn technique_badges(technique: &Technique, theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(
+            "{} {} · {}",
+            technique.category.icon(),
+            technique.category.display(),
+            technique.difficulty.display()
+        ),
+        Style::default().fg(theme.ui.text_muted),
+    ))
+}
+
 fn render_guide_overlay(frame: &mut Frame, app: &App, area: Rect) {
-    let theme = default_theme();
+    let theme = &app.theme;
     let technique = if app.technique.is_some() {
         app.current_technique()
     } else {
@@ -785,7 +1606,7 @@ fn render_guide_overlay(frame: &mut Frame, app: &App, area: Rect) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b)))
         .padding(Padding::uniform(1))
-        .style(Style::default().bg(Color::Rgb(15, 30, 50)));
+        .style(Style::default().bg(theme.ui.surface));
 
     frame.render_widget(guide_block.clone(), overlay_area);
 
@@ -795,19 +1616,33 @@ fn render_guide_overlay(frame: &mut Frame, app: &App, area: Rect) {
     let mut lines = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled(technique.tagline, Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b)).add_modifier(Modifier::ITALIC)),
+            Span::styled(technique.tagline.clone(), Style::default().fg(Color::Rgb(tc.r, tc.g, tc.b)).add_modifier(Modifier::ITALIC)),
         ]).centered(),
+        technique_badges(technique, theme).centered(),
         Line::from(""),
-        Line::from(""),
+    ];
+
+    if let Some(note) = &technique.safety_note {
+        for (i, line) in wrap_text(note, 60).into_iter().enumerate() {
+            let marker = if i == 0 { "⚠ " } else { "  " };
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", marker, line),
+                Style::default().fg(theme.ui.warning).add_modifier(Modifier::BOLD),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines.extend(vec![
         // Description
         Line::from(vec![
             Span::styled("About", Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
-    ];
+    ]);
 
     // Word-wrap description
-    for line in wrap_text(technique.description, 60) {
+    for line in wrap_text(&technique.description, 60) {
         lines.push(Line::from(Span::styled(line, Style::default().fg(theme.ui.text_secondary))));
     }
 
@@ -816,22 +1651,22 @@ fn render_guide_overlay(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled("Pattern  ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(technique.pattern, Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(technique.pattern.clone(), Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Purpose  ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(technique.purpose, Style::default().fg(theme.ui.text_secondary)),
+            Span::styled(technique.purpose.clone(), Style::default().fg(theme.ui.text_secondary)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Best For ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(technique.use_case, Style::default().fg(theme.ui.text_secondary)),
+            Span::styled(technique.use_case.clone(), Style::default().fg(theme.ui.text_secondary)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Source   ", Style::default().fg(theme.ui.text_muted)),
-            Span::styled(technique.source, Style::default().fg(theme.ui.text_muted).add_modifier(Modifier::ITALIC)),
+            Span::styled(technique.source.clone(), Style::default().fg(theme.ui.text_muted).add_modifier(Modifier::ITALIC)),
         ]),
         Line::from(""),
         Line::from(""),
@@ -843,29 +1678,61 @@ fn render_guide_overlay(frame: &mut Frame, app: &App, area: Rect) {
 
     // Add phase breakdown
     for (i, phase) in technique.phases.iter().enumerate() {
-        let phase_color = match phase.name {
-            PhaseName::Inhale => Color::Rgb(74, 144, 217),
-            PhaseName::Hold => Color::Rgb(201, 162, 39),
-            PhaseName::Exhale => Color::Rgb(139, 92, 246),
-            PhaseName::HoldAfterExhale => Color::Rgb(100, 116, 139),
-        };
+        let phase_color = theme.get_phase_colors(phase.name).primary;
         lines.push(Line::from(vec![
             Span::styled(format!("  {}. ", i + 1), Style::default().fg(theme.ui.text_muted)),
-            Span::styled(format!("{:<8}", phase.name.display()), Style::default().fg(phase_color)),
-            Span::styled(format!("{:>4}s  ", phase.duration_secs as u32), Style::default().fg(theme.ui.text_primary)),
-            Span::styled(phase.instruction, Style::default().fg(theme.ui.text_secondary)),
+            Span::styled(format!("{:<8}", t(phase.name.display(), app.lang)), Style::default().fg(phase_color)),
+            Span::styled(format!("{:>4}s  ", format_secs(phase.duration_secs)), Style::default().fg(theme.ui.text_primary)),
+            Span::styled(phase.instruction.clone(), Style::default().fg(theme.ui.text_secondary)),
         ]));
     }
 
+    // The cycle at a glance: one bar, each phase as wide as its share
+    let bar_width = (inner.width as usize).saturating_sub(4).min(GUIDE_RATIO_WIDTH);
+    let durations: Vec<f64> = technique.phases.iter().map(|p| p.duration_secs).collect();
+    let mut bar = vec![Span::raw("  ")];
+    for (phase, width) in technique.phases.iter().zip(ratio_widths(&durations, bar_width)) {
+        bar.push(Span::styled("█".repeat(width), Style::default().fg(theme.get_phase_colors(phase.name).primary)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(bar));
+
     lines.extend(vec![
         Line::from(""),
         Line::from(""),
-        Line::from(Span::styled("Press any key to close", Style::default().fg(theme.ui.text_muted))).centered(),
+        Line::from(Span::styled(t("Press any key to close", app.lang), Style::default().fg(theme.ui.text_muted))).centered(),
     ]);
 
     frame.render_widget(Paragraph::new(lines), inner);
 }
 
+/// Longest the guide's phase ratio bar gets
+const GUIDE_RATIO_WIDTH: usize = 60;
+
+/// Split `width` cells between the durations in proportion, every phase
+/// getting at least one cell so short ones stay visible
+fn ratio_widths(durations: &[f64], width: usize) -> Vec<usize> {
+    let total: f64 = durations.iter().sum();
+    if total <= 0.0 || width < durations.len() {
+        return vec![0; durations.len()];
+    }
+
+    let mut widths = Vec::with_capacity(durations.len());
+    let mut start = 0;
+    let mut elapsed = 0.0;
+    for (i, duration) in durations.iter().enumerate() {
+        elapsed += duration;
+        // Leave a cell for each phase still to come
+        let remaining = durations.len() - i - 1;
+        let end = ((elapsed / total * width as f64).round() as usize)
+            .max(start + 1)
+            .min(width - remaining);
+        widths.push(end - start);
+        start = end;
+    }
+    widths
+}
+
 /// Simple text wrapper
 fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     let mut lines = Vec::new();
@@ -890,8 +1757,22 @@ fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     lines
 }
 
+/// A cols x rows region centered in `area`, clamped to fit inside it
+fn fixed_area(area: Rect, cols: u16, rows: u16) -> Rect {
+    let width = cols.min(area.width);
+    let height = rows.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
 /// Helper to create a centered rect
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let percent_x = percent_x.min(100);
+    let percent_y = percent_y.min(100);
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -910,3 +1791,350 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::techniques::get_technique;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn buffer_text(terminal: &Terminal<TestBackend>) -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_each_phase_has_its_own_bar_fill() {
+        let phases = [PhaseName::Inhale, PhaseName::Hold, PhaseName::Exhale, PhaseName::HoldAfterExhale];
+        let fills: std::collections::HashSet<_> = phases.iter().map(|&p| phase_bar_char(p)).collect();
+        assert_eq!(fills.len(), phases.len());
+    }
+
+    #[test]
+    fn test_short_terminal_uses_compact_layout() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.start();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 14)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        // Phase, countdown and cycles all fit, with no canvas drawn
+        let text = buffer_text(&terminal);
+        assert!(text.contains("INHALE"));
+        assert!(text.contains("4.0s"));
+        assert!(!text.chars().any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+    }
+
+    #[test]
+    fn test_spanish_interface() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.lang = Lang::Es;
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("Pulsa ENTER/SPACE para empezar"));
+        assert!(text.contains("No es consejo médico"));
+
+        app.start();
+        app.begin_breathing();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("INHALA"));
+        assert!(text.contains("pausa"));
+
+        app.toggle_pause();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("EN PAUSA"));
+        assert!(text.contains("r      reiniciar"));
+
+        app.toggle_pause();
+        for _ in 0..16 {
+            app.skip_phase();
+        }
+        app.celebration = None;
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        assert!(buffer_text(&terminal).contains("Respiraciones  4"));
+    }
+
+    #[test]
+    fn test_large_cycle_target_renders_compact_bar() {
+        let mut app = App::new_with_technique(get_technique("energize").unwrap(), 300);
+        app.start();
+        app.cycles_completed = 150;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        let text = buffer_text(&terminal);
+        assert!(text.contains("150/300"));
+        assert!(text.contains(&"━".repeat(10)));
+    }
+
+    #[test]
+    fn test_selector_shows_weekly_goal_progress() {
+        let mut app = App::new_interactive();
+        app.prefs.weekly_goal_minutes = Some(70);
+        app.week_minutes = 35;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("35/70 min"));
+        assert!(text.contains(&"━".repeat(10)));
+
+        app.week_minutes = 80;
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        assert!(buffer_text(&terminal).contains("Weekly goal reached"));
+    }
+
+    #[test]
+    fn test_clicks_find_rows_and_footer_hints() {
+        let mut app = App::new_interactive();
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        let list = app.hit_areas.borrow().list;
+        assert!(matches!(app.row_at(list.x, list.y), Some(SelectorRow::Header(_))));
+        let Some(SelectorRow::Technique(index)) = app.row_at(list.x + 4, list.y + 2) else {
+            panic!("expected a technique on the third row");
+        };
+        assert_eq!(app.row_at(list.x, list.bottom()), None);
+
+        app.click_technique(index);
+        assert_eq!(app.selected_index, index);
+        assert_eq!(app.state, AppState::Selecting);
+        app.click_technique(index);
+        assert_eq!(app.state, AppState::Ready);
+
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        assert_eq!(app.hit_areas.borrow().list, Rect::default());
+        let (area, _) = app.hit_areas.borrow().hints.iter().find(|(_, action)| *action == Action::Quit).copied().unwrap();
+        assert_eq!(app.hint_at(area.x, area.y), Some(Action::Quit));
+        assert_eq!(terminal.backend().buffer()[(area.x, area.y)].symbol(), "q");
+        assert_eq!(app.hint_at(area.right(), area.y), None);
+    }
+
+    #[test]
+    fn test_short_selector_keeps_the_selection_on_screen() {
+        let mut app = App::new_interactive();
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        assert!(app.hit_areas.borrow().list.height < app.selector_rows().len() as u16);
+
+        app.select_prev();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        let name = app.selected_technique().name.clone();
+        assert!(buffer_text(&terminal).contains(&format!("▸ ● {}", name)));
+
+        app.select_page(-1);
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+        let name = app.selected_technique().name.clone();
+        assert!(buffer_text(&terminal).contains(&format!("▸ ● {}", name)));
+    }
+
+    #[test]
+    fn test_session_renders_in_tiny_areas() {
+        for (width, height) in [(1, 1), (2, 2), (3, 1), (80, 1)] {
+            let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+            app.start();
+
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+            let mut area = Rect::new(0, 0, width, height);
+            terminal
+                .draw(|frame| render_breath_visualizer(frame, &app, area))
+                .unwrap();
+            area.height = 0;
+            terminal
+                .draw(|frame| render_breath_visualizer(frame, &app, area))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_every_screen_renders_in_tiny_areas() {
+        let screens: [fn(&mut App); 6] = [
+            |app| app.back_to_selection(),
+            |_| {},
+            |app| {
+                app.countdown_secs = 3;
+                app.start();
+            },
+            |app| {
+                app.start();
+                app.toggle_pause();
+            },
+            |app| {
+                app.start();
+                app.finish();
+            },
+            |app| app.toggle_help(),
+        ];
+        for (width, height) in [(1, 1), (2, 2), (5, 3), (80, 1), (1, 40)] {
+            for screen in screens {
+                let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+                screen(&mut app);
+                let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+                terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+            }
+        }
+
+        // Out-of-range percentages are clamped rather than underflowing
+        assert_eq!(centered_rect(150, 50, Rect::new(0, 0, 80, 24)).width, 80);
+    }
+
+    #[test]
+    fn test_cycle_bar_never_overflows() {
+        for (completed, target) in [(0, 13), (299, 300), (300, 300), (500, 300), (1, u32::MAX)] {
+            let spans = cycle_progress_spans(&Theme::dark(), completed, target, 0.0);
+            let bar_cells: usize = spans[..2].iter().map(|s| s.content.chars().count()).sum();
+            assert_eq!(bar_cells, CYCLE_BAR_WIDTH as usize);
+        }
+    }
+
+    #[test]
+    fn test_footer_keeps_quit_on_narrow_terminals() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+
+        let mut terminal = Terminal::new(TestBackend::new(24, 3)).unwrap();
+        terminal
+            .draw(|frame| render_footer(frame, &app, &KeyBindings::default(), frame.area()))
+            .unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("q quit"));
+        assert!(!text.contains("cycles"));
+
+        app.start();
+        let mut terminal = Terminal::new(TestBackend::new(80, 3)).unwrap();
+        terminal
+            .draw(|frame| render_footer(frame, &app, &KeyBindings::default(), frame.area()))
+            .unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("SPACE pause"));
+        assert!(!text.contains("ENTER"));
+    }
+
+    #[test]
+    fn test_prompts_name_the_bound_keys() {
+        let keys: KeyBindings = toml::from_str("confirm = [\"s\"]\npause = [\"p\"]\n").unwrap();
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &keys)).unwrap();
+        assert!(buffer_text(&terminal).contains("Press s to begin"));
+
+        app.start();
+        app.begin_breathing();
+        app.toggle_pause();
+        terminal.draw(|frame| render(frame, &mut app, &keys)).unwrap();
+        let text = buffer_text(&terminal);
+        assert!(text.contains("p  resume"));
+        assert!(!text.contains("SPACE"));
+    }
+
+    #[test]
+    fn test_footer_follows_the_key_bindings() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.start();
+        let hints = footer_hints(&app, &KeyBindings::default());
+        let shown = |hints: &[KeyHint], key: &str, action: &str| {
+            hints.iter().any(|hint| hint.key == key && hint.action == action)
+        };
+        assert!(shown(&hints, "n/.", "skip"));
+        assert!(shown(&hints, "[/]", "pace"));
+        assert!(!hints.iter().any(|hint| hint.action == "drone"));
+        assert_eq!(hints.last().map(|hint| hint.key.as_str()), Some("q"));
+
+        app.drone = true;
+        let keys: KeyBindings = toml::from_str("pause = [\"p\"]\n").unwrap();
+        let hints = footer_hints(&app, &keys);
+        assert!(shown(&hints, "p", "pause"));
+        assert!(shown(&hints, "d", "drone"));
+    }
+
+    #[test]
+    fn test_fixed_area_is_centered_and_clamped() {
+        let screen = Rect::new(0, 0, 200, 60);
+        assert_eq!(fixed_area(screen, 80, 24), Rect::new(60, 18, 80, 24));
+        assert_eq!(fixed_area(screen, 300, 100), screen);
+    }
+
+    #[test]
+    fn test_ascii_mode_draws_only_ascii() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.use_ascii = true;
+        app.start();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert!(buffer.content().iter().all(|cell| cell.symbol().is_ascii()));
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("^ INHALE ^"));
+    }
+
+    #[test]
+    fn test_ratio_widths_fill_the_bar_in_proportion() {
+        assert_eq!(ratio_widths(&[4.0, 1.0, 8.0], 26), vec![8, 2, 16]);
+        assert_eq!(ratio_widths(&[4.0, 4.0, 4.0, 4.0], 40), vec![10; 4]);
+        // The physiological sigh's short top-up still shows up
+        let widths = ratio_widths(&[2.0, 0.1, 10.0], 20);
+        assert_eq!(widths.iter().sum::<usize>(), 20);
+        assert!(widths.iter().all(|w| *w >= 1));
+    }
+
+    #[test]
+    fn test_countdown_shows_a_big_number() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.countdown_secs = 3;
+        app.start();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        let text: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("██████"));
+        assert!(text.contains("First: Breathe In"));
+    }
+
+    #[test]
+    fn test_monochrome_drops_all_rgb() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.theme = Theme::monochrome();
+        app.monochrome = true;
+        app.start();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        for cell in terminal.backend().buffer().content() {
+            assert!(!matches!(cell.fg, Color::Rgb(..)), "fg {:?}", cell.fg);
+            assert!(!matches!(cell.bg, Color::Rgb(..)), "bg {:?}", cell.bg);
+        }
+    }
+
+    #[test]
+    fn test_night_mode_red_shifts_every_cell() {
+        let mut app = App::new_with_technique(get_technique("box").unwrap(), 4);
+        app.enable_night_mode();
+        app.start();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app, &KeyBindings::default())).unwrap();
+
+        for cell in terminal.backend().buffer().content() {
+            for color in [cell.fg, cell.bg] {
+                if let Color::Rgb(r, g, b) = color {
+                    assert!(g <= r && b <= r, "{:?} is not red-shifted", color);
+                }
+            }
+        }
+    }
+}
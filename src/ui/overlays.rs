@@ -2,8 +2,9 @@
 
 use crate::animation::{ease_breath, pulse_breath};
 use crate::app::App;
+use crate::i18n::t;
 use crate::techniques::PhaseName;
-use crate::theme::{default_theme, with_opacity};
+use crate::theme::with_opacity;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -17,7 +18,7 @@ use ratatui::{
 pub fn render_giant_phase_indicator(frame: &mut Frame, app: &App, area: Rect) {
     let phase = app.current_phase();
     let time = app.session_elapsed().as_secs_f64();
-    let theme = default_theme();
+    let theme = &app.theme;
 
     let phase_colors = theme.get_phase_colors(phase.name);
 
@@ -51,12 +52,7 @@ pub fn render_giant_phase_indicator(frame: &mut Frame, app: &App, area: Rect) {
     };
 
     // Create the phase name display with visual emphasis
-    let phase_display = match phase.name {
-        PhaseName::Inhale => "▲ INHALE ▲",
-        PhaseName::Hold => "● HOLD ●",
-        PhaseName::Exhale => "▼ EXHALE ▼",
-        PhaseName::HoldAfterExhale => "○ REST ○",
-    };
+    let phase_display = super::phase_label(phase.name, app.use_ascii, app.lang);
 
     let phase_text = Paragraph::new(Line::from(vec![Span::styled(
         phase_display,
@@ -73,7 +69,7 @@ pub fn render_giant_phase_indicator(frame: &mut Frame, app: &App, area: Rect) {
     let instruction_color = with_opacity(theme.ui.text_secondary, instruction_opacity);
 
     let instruction_text = Paragraph::new(Line::from(Span::styled(
-        phase.instruction,
+        phase.instruction.clone(),
         Style::default().fg(instruction_color),
     )))
     .alignment(Alignment::Center);
@@ -87,7 +83,7 @@ pub fn render_breathing_progress_bar(frame: &mut Frame, app: &App, area: Rect) {
     let progress = app.phase_progress();
     let phase = app.current_phase();
     let time = app.session_elapsed().as_secs_f64();
-    let theme = default_theme();
+    let theme = &app.theme;
 
     let phase_colors = theme.get_phase_colors(phase.name);
 
@@ -144,12 +140,12 @@ pub fn render_countdown_timer(frame: &mut Frame, app: &App, area: Rect) {
     let phase = app.current_phase();
     let progress = app.phase_progress();
     let time = app.session_elapsed().as_secs_f64();
-    let theme = default_theme();
+    let theme = &app.theme;
 
     let phase_colors = theme.get_phase_colors(phase.name);
 
     // Calculate remaining time
-    let remaining = phase.duration_secs * (1.0 - progress);
+    let remaining = app.effective_phase_duration() * (1.0 - progress);
     let remaining_display = format!("{:.1}s", remaining.max(0.0));
 
     // Pulse effect near end of phase
@@ -189,7 +185,7 @@ pub fn render_cycle_dots(frame: &mut Frame, app: &App, area: Rect) {
     let completed = app.cycles_completed as usize;
     let target = app.cycles_target as usize;
     let time = app.session_elapsed().as_secs_f64();
-    let theme = default_theme();
+    let theme = &app.theme;
 
     // Limit displayed dots for very long sessions
     let max_display = 12;
@@ -279,13 +275,13 @@ pub fn render_phase_info_panel(frame: &mut Frame, app: &App, area: Rect) {
 /// Render session stats in a compact format
 #[allow(dead_code)]
 pub fn render_session_stats(frame: &mut Frame, app: &App, area: Rect) {
-    let theme = default_theme();
+    let theme = &app.theme;
     let elapsed = crate::app::App::format_time(app.session_elapsed());
 
     let stats_line = Line::from(vec![
-        Span::styled("Cycle ", Style::default().fg(theme.ui.text_muted)),
+        Span::styled(t("Cycle ", app.lang), Style::default().fg(theme.ui.text_muted)),
         Span::styled(
-            format!("{}", app.cycles_completed + 1),
+            format!("{}", app.current_cycle()),
             Style::default().fg(theme.ui.text_primary).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
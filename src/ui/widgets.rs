@@ -1,2 +1,92 @@
-// Additional widget helpers can go here
-// Currently the main widgets are in mod.rs and breathing_circle.rs
+//! Small reusable widget helpers shared across screens
+
+use crate::keys::Action;
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph},
+    Frame,
+};
+
+/// A single "key action" entry in a footer legend
+pub struct KeyHint {
+    pub key: String,
+    pub action: String,
+    /// What clicking the hint does, if it stands for a single action
+    pub target: Option<Action>,
+}
+
+impl KeyHint {
+    pub fn new(key: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            action: action.into(),
+            target: None,
+        }
+    }
+
+    /// Make the hint clickable
+    pub fn on_click(mut self, action: Action) -> Self {
+        self.target = Some(action);
+        self
+    }
+}
+
+/// Build the legend line for a set of hints
+fn key_hint_line(theme: &Theme, hints: &[KeyHint]) -> Line<'static> {
+    let mut spans = Vec::new();
+
+    for (i, hint) in hints.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(hint.key.clone(), Style::default().fg(theme.ui.accent)));
+        spans.push(Span::styled(
+            format!(" {}", hint.action),
+            Style::default().fg(theme.ui.text_muted),
+        ));
+    }
+
+    Line::from(spans)
+}
+
+/// Render a centered footer legend. When the terminal is too narrow, hints
+/// are dropped from the end, but the last one (usually quit) is always kept.
+/// Returns where each clickable hint landed, with its action, for mouse
+/// clicks.
+pub fn render_key_hints(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    mut hints: Vec<KeyHint>,
+) -> Vec<(Rect, Action)> {
+    let mut line = key_hint_line(theme, &hints);
+    while hints.len() > 1 && line.width() > area.width as usize {
+        hints.remove(hints.len() - 2);
+        line = key_hint_line(theme, &hints);
+    }
+
+    // Mirror the paragraph's centering below (half the line back from the
+    // middle), then key, space, action and a two-cell gap
+    let mut targets = Vec::new();
+    let y = area.y + 1;
+    if y < area.bottom() {
+        let mut x = area.x + (area.width / 2).saturating_sub(line.width() as u16 / 2);
+        for hint in &hints {
+            let width = (Span::raw(hint.key.as_str()).width() + 1 + Span::raw(hint.action.as_str()).width()) as u16;
+            if let Some(action) = hint.target {
+                targets.push((Rect::new(x, y, width, 1).intersection(area), action));
+            }
+            x = x.saturating_add(width + 2);
+        }
+    }
+
+    let footer = Paragraph::new(line)
+        .alignment(Alignment::Center)
+        .block(Block::default().padding(Padding::vertical(1)));
+
+    frame.render_widget(footer, area);
+    targets
+}